@@ -0,0 +1,143 @@
+//! Writing the styles of a tree one property at a time against all at once
+//! through `cssText`.
+//!
+//! The DOM is not available natively, so both ways go through the same [Boundary],
+//! which counts every call that would cross the wasm boundary. The counts are
+//! checked and named in the benchmark ids, next to the time spent building the
+//! strings on the Rust side.
+
+use core::cell::Cell;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use puppeteer_builders::{
+    Component, ComponentType, PuppetChild, PuppetColor, PuppetColumn, PuppetElement, PuppetLength,
+    PuppetRow, PuppetStyle, PuppetUnit,
+};
+
+const ROWS: usize = 100;
+const BUTTONS_PER_ROW: usize = 3;
+
+/// Stands in for `web_sys`, taking the same arguments and counting calls into
+/// JavaScript
+#[derive(Default)]
+struct Boundary {
+    crossings: Cell<usize>,
+}
+
+impl Boundary {
+    fn cross(&self) {
+        self.crossings.set(self.crossings.get() + 1);
+    }
+
+    /// `HtmlElement::style`
+    fn style(&self) -> &Self {
+        self.cross();
+
+        self
+    }
+
+    /// `CssStyleDeclaration::set_property`
+    fn set_property(&self, property: &str, value: &str) {
+        self.cross();
+        black_box((property, value));
+    }
+
+    /// `CssStyleDeclaration::set_css_text`
+    fn set_css_text(&self, css_text: &str) {
+        self.cross();
+        black_box(css_text);
+    }
+}
+
+/// Every property set, the worst case of writing properties one at a time
+fn full_style() -> PuppetStyle {
+    let mut style = PuppetStyle::reset();
+    style
+        .min_width(PuppetUnit::Pixels(40))
+        .max_width(PuppetUnit::Percentage(80))
+        .min_height(PuppetUnit::Pixels(16))
+        .max_height(PuppetUnit::Pixels(400))
+        .height(PuppetLength::Normal)
+        .background_color(PuppetColor::Rgb(250, 250, 250));
+
+    style
+}
+
+fn tree() -> PuppetElement {
+    let style = full_style();
+    let mut column = PuppetColumn::new();
+    column.set_style(&style);
+
+    for _ in 0..ROWS {
+        let mut row = PuppetRow::new();
+        row.set_style(&style);
+
+        for _ in 0..BUTTONS_PER_ROW {
+            let mut button = Component::new(ComponentType::Button, "Save");
+            button.set_style(&style);
+            row.add_component(button);
+        }
+
+        column.add_row(row);
+    }
+
+    column.to_element()
+}
+
+/// `style()` then `set_property` for every property, as styles used to be written,
+/// or `style()` then `set_css_text` once, as [PuppetStyle::to_html] does
+fn write_styles(element: &PuppetElement, boundary: &Boundary, batched: bool) {
+    if let Some(style) = element.style() {
+        let declaration = boundary.style();
+
+        match batched {
+            true => declaration.set_css_text(&style.to_css()),
+            false => {
+                for (property, value) in style.css_properties() {
+                    declaration.set_property(property, &value);
+                }
+            }
+        }
+    }
+
+    for child in element.children() {
+        if let PuppetChild::Element(child) = child {
+            write_styles(child, boundary, batched);
+        }
+    }
+}
+
+fn crossings(element: &PuppetElement, batched: bool) -> usize {
+    let boundary = Boundary::default();
+    write_styles(element, &boundary, batched);
+
+    boundary.crossings.get()
+}
+
+fn style_writes(c: &mut Criterion) {
+    let element = tree();
+    let styled = 1 + ROWS * (1 + BUTTONS_PER_ROW);
+    let properties = full_style().css_properties().len();
+
+    let per_property = crossings(&element, false);
+    let css_text = crossings(&element, true);
+    assert_eq!(per_property, styled * (1 + properties));
+    assert_eq!(css_text, styled * 2);
+
+    let boundary = Boundary::default();
+    let mut group = c.benchmark_group("style writes");
+    // Reported as styled elements written per second
+    group.throughput(Throughput::Elements(styled as u64));
+    for (name, batched, crossings) in [
+        ("per property", false, per_property),
+        ("css text", true, css_text),
+    ] {
+        group.bench_function(
+            BenchmarkId::new(name, format!("{} crossings", crossings)),
+            |b| b.iter(|| write_styles(black_box(&element), &boundary, batched)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, style_writes);
+criterion_main!(benches);
@@ -0,0 +1,450 @@
+use crate::{PuppetElement, PuppetText};
+use core::{
+    fmt,
+    fmt::{Debug, Display},
+};
+use log::trace;
+use std::borrow::Cow;
+
+/// The HTML element a [crate::PuppetColumn] or [crate::PuppetRow] is written as
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum LayoutTag {
+    /// An anonymous container, without meaning for assistive technologies
+    #[default]
+    Div,
+    /// The main content of the page, once per page
+    Main,
+    Nav,
+    Header,
+    Footer,
+    Section,
+    Aside,
+    Article,
+    /// A `ul` column, whose rows are written as `li`
+    List,
+    /// A `li` row, only within a [LayoutTag::List] column
+    ListItem,
+}
+
+impl LayoutTag {
+    pub fn to_html_tag(&self) -> &'static str {
+        match self {
+            Self::Div => "div",
+            Self::Main => "main",
+            Self::Nav => "nav",
+            Self::Header => "header",
+            Self::Footer => "footer",
+            Self::Section => "section",
+            Self::Aside => "aside",
+            Self::Article => "article",
+            Self::List => "ul",
+            Self::ListItem => "li",
+        }
+    }
+
+    /// The role browsers give the element without a `role` attribute, `None` for
+    /// the generic `div`
+    pub fn implicit_role(&self) -> Option<PuppetRole> {
+        match self {
+            Self::Div => None,
+            Self::Main => Some(PuppetRole::Main),
+            Self::Nav => Some(PuppetRole::Navigation),
+            Self::Header => Some(PuppetRole::Banner),
+            Self::Footer => Some(PuppetRole::ContentInfo),
+            Self::Section => Some(PuppetRole::Region),
+            Self::Aside => Some(PuppetRole::Complementary),
+            Self::Article => Some(PuppetRole::Article),
+            Self::List => Some(PuppetRole::List),
+            Self::ListItem => Some(PuppetRole::ListItem),
+        }
+    }
+
+    /// The tag a row of this tag is written as within a column of the tag `parent`:
+    /// rows of a list are list items, and list items cannot stand outside of one
+    pub fn within(&self, parent: LayoutTag) -> LayoutTag {
+        match (parent, self) {
+            (Self::List, _) => Self::ListItem,
+            (_, Self::ListItem) => {
+                trace!("LIST ITEM OUTSIDE OF A LIST");
+                Self::Div
+            }
+            (_, tag) => *tag,
+        }
+    }
+
+    /// Whether ARIA in HTML lets this element take `role`
+    pub fn permits(&self, role: PuppetRole) -> bool {
+        use PuppetRole::*;
+
+        if self.implicit_role() == Some(role) {
+            return true;
+        }
+
+        match self {
+            Self::Div => true,
+            Self::Main | Self::Nav => false,
+            Self::Header | Self::Footer => matches!(role, Group | None),
+            Self::Section => matches!(
+                role,
+                Alert
+                    | Banner
+                    | Complementary
+                    | ContentInfo
+                    | Dialog
+                    | Group
+                    | Main
+                    | Navigation
+                    | None
+                    | Search
+                    | Status
+                    | TabPanel
+            ),
+            Self::Aside => matches!(role, None | Region | Search),
+            Self::Article => matches!(role, Main | None | Region),
+            Self::List => matches!(role, Group | None | Toolbar),
+            Self::ListItem => matches!(role, None),
+        }
+    }
+}
+
+impl Debug for LayoutTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LayoutTag::{}", self)
+    }
+}
+
+impl Display for LayoutTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Div => "Div",
+                Self::Main => "Main",
+                Self::Nav => "Nav",
+                Self::Header => "Header",
+                Self::Footer => "Footer",
+                Self::Section => "Section",
+                Self::Aside => "Aside",
+                Self::Article => "Article",
+                Self::List => "List",
+                Self::ListItem => "ListItem",
+            }
+        )
+    }
+}
+
+/// The ARIA roles layout nodes can take
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum PuppetRole {
+    Alert,
+    Article,
+    Banner,
+    Button,
+    Complementary,
+    ContentInfo,
+    Dialog,
+    Group,
+    List,
+    ListItem,
+    Main,
+    Navigation,
+    /// Removes the meaning of the element, leaving its content
+    None,
+    Region,
+    Search,
+    Status,
+    TabPanel,
+    Toolbar,
+}
+
+impl PuppetRole {
+    pub fn to_html_role(&self) -> &'static str {
+        match self {
+            Self::Alert => "alert",
+            Self::Article => "article",
+            Self::Banner => "banner",
+            Self::Button => "button",
+            Self::Complementary => "complementary",
+            Self::ContentInfo => "contentinfo",
+            Self::Dialog => "dialog",
+            Self::Group => "group",
+            Self::List => "list",
+            Self::ListItem => "listitem",
+            Self::Main => "main",
+            Self::Navigation => "navigation",
+            Self::None => "none",
+            Self::Region => "region",
+            Self::Search => "search",
+            Self::Status => "status",
+            Self::TabPanel => "tabpanel",
+            Self::Toolbar => "toolbar",
+        }
+    }
+
+    /// Whether the role marks a region of the page users can jump to
+    pub fn is_landmark(&self) -> bool {
+        matches!(
+            self,
+            Self::Banner
+                | Self::Complementary
+                | Self::ContentInfo
+                | Self::Main
+                | Self::Navigation
+                | Self::Region
+                | Self::Search
+        )
+    }
+}
+
+impl Debug for PuppetRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PuppetRole::{}", self)
+    }
+}
+
+impl Display for PuppetRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Alert => "Alert",
+                Self::Article => "Article",
+                Self::Banner => "Banner",
+                Self::Button => "Button",
+                Self::Complementary => "Complementary",
+                Self::ContentInfo => "ContentInfo",
+                Self::Dialog => "Dialog",
+                Self::Group => "Group",
+                Self::List => "List",
+                Self::ListItem => "ListItem",
+                Self::Main => "Main",
+                Self::Navigation => "Navigation",
+                Self::None => "None",
+                Self::Region => "Region",
+                Self::Search => "Search",
+                Self::Status => "Status",
+                Self::TabPanel => "TabPanel",
+                Self::Toolbar => "Toolbar",
+            }
+        )
+    }
+}
+
+/// How assistive technologies announce changes to a live region
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum AriaLive {
+    #[default]
+    Off,
+    /// Once users are idle
+    Polite,
+    /// Right away, interrupting users
+    Assertive,
+}
+
+impl AriaLive {
+    pub fn to_html_live(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Polite => "polite",
+            Self::Assertive => "assertive",
+        }
+    }
+}
+
+impl Debug for AriaLive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AriaLive::{}", self)
+    }
+}
+
+impl Display for AriaLive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Off => "Off",
+                Self::Polite => "Polite",
+                Self::Assertive => "Assertive",
+            }
+        )
+    }
+}
+
+/// An `aria-*` attribute of a layout node
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub enum AriaAttribute {
+    Label(PuppetText),
+    Live(AriaLive),
+    /// Whether a live region is announced as a whole rather than by change
+    Atomic(bool),
+    Busy(bool),
+    Expanded(bool),
+    Hidden(bool),
+    Modal(bool),
+    /// The id of the element this one shows or hides
+    Controls(Cow<'static, str>),
+}
+
+impl AriaAttribute {
+    pub fn to_html_name(&self) -> &'static str {
+        match self {
+            Self::Label(_) => "aria-label",
+            Self::Live(_) => "aria-live",
+            Self::Atomic(_) => "aria-atomic",
+            Self::Busy(_) => "aria-busy",
+            Self::Expanded(_) => "aria-expanded",
+            Self::Hidden(_) => "aria-hidden",
+            Self::Modal(_) => "aria-modal",
+            Self::Controls(_) => "aria-controls",
+        }
+    }
+
+    pub fn to_html_value(&self) -> PuppetText {
+        match self {
+            Self::Label(label) => label.clone(),
+            Self::Live(live) => live.to_html_live().into(),
+            Self::Atomic(value)
+            | Self::Busy(value)
+            | Self::Expanded(value)
+            | Self::Hidden(value)
+            | Self::Modal(value) => value.to_string().into(),
+            Self::Controls(id) => id.clone().into(),
+        }
+    }
+
+    /// Whether the attribute is allowed on an element with the role `role`, `None`
+    /// standing for the generic role of a `div`
+    pub fn is_allowed(&self, role: Option<PuppetRole>) -> bool {
+        match self {
+            // Generic and presentational elements cannot be named
+            Self::Label(_) => !matches!(role, Option::None | Some(PuppetRole::None)),
+            Self::Expanded(_) => role == Some(PuppetRole::Button),
+            Self::Modal(_) => role == Some(PuppetRole::Dialog),
+            Self::Live(_)
+            | Self::Atomic(_)
+            | Self::Busy(_)
+            | Self::Hidden(_)
+            | Self::Controls(_) => true,
+        }
+    }
+}
+
+/// The element, role and ARIA attributes of a layout node, kept valid together:
+/// every change that would break a rule of ARIA in HTML is rejected
+///
+/// ```
+/// use puppeteer_builders::{puppet, AriaAttribute, LayoutTag, PuppetRole};
+///
+/// let sidebar = puppet! {
+///     row {
+///         tag(LayoutTag::Aside)
+///         role(PuppetRole::Main)
+///         aria(AriaAttribute::Label("Related".into()))
+///     }
+/// };
+///
+/// // An `aside` cannot be the main content
+/// assert_eq!(sidebar.semantics().get_role(), Some(PuppetRole::Complementary));
+/// assert!(sidebar.to_html_string().starts_with(r#"<aside aria-label="Related">"#));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetSemantics {
+    tag: LayoutTag,
+    role: Option<PuppetRole>,
+    attributes: Vec<AriaAttribute>,
+}
+
+impl PuppetSemantics {
+    pub fn get_tag(&self) -> LayoutTag {
+        self.tag
+    }
+
+    /// The role written on the element, or else the one it has implicitly
+    pub fn get_role(&self) -> Option<PuppetRole> {
+        self.role.or(self.tag.implicit_role())
+    }
+
+    pub fn get_attributes(&self) -> &[AriaAttribute] {
+        &self.attributes
+    }
+
+    /// Keeps the previous tag when `tag` is rejected
+    pub(crate) fn set_tag(&mut self, tag: LayoutTag) {
+        let role_allowed = self.role.is_none_or(|role| tag.permits(role));
+        let implicit_role = self.role.or(tag.implicit_role());
+
+        if !role_allowed || !self.attributes_allowed(implicit_role) {
+            trace!("INVALID TAG {} FOR {:?}", tag, self);
+
+            return;
+        }
+        self.tag = tag;
+    }
+
+    /// Keeps the previous role when `role` is rejected
+    pub(crate) fn set_role(&mut self, role: PuppetRole) {
+        if !self.tag.permits(role) || !self.attributes_allowed(Some(role)) {
+            trace!("INVALID ROLE {} FOR {:?}", role, self);
+
+            return;
+        }
+        self.role = Some(role);
+    }
+
+    /// Replaces any previous value of the same attribute
+    pub(crate) fn set_attribute(&mut self, attribute: AriaAttribute) {
+        if !attribute.is_allowed(self.get_role()) {
+            trace!(
+                "INVALID ATTRIBUTE {} FOR {:?}",
+                attribute.to_html_name(),
+                self
+            );
+
+            return;
+        }
+
+        match self
+            .attributes
+            .iter_mut()
+            .find(|previous| previous.to_html_name() == attribute.to_html_name())
+        {
+            Some(previous) => *previous = attribute,
+            None => self.attributes.push(attribute),
+        }
+    }
+
+    fn attributes_allowed(&self, role: Option<PuppetRole>) -> bool {
+        self.attributes
+            .iter()
+            .all(|attribute| attribute.is_allowed(role))
+    }
+
+    /// Write the role when it is not implied by `tag`, then the ARIA attributes,
+    /// leaving out what `tag` does not allow as a row may be written as another tag
+    /// than its own, see [LayoutTag::within]
+    pub(crate) fn write_attributes(&self, tag: LayoutTag, element: &mut PuppetElement) {
+        let role = match self.role {
+            Some(role) if !tag.permits(role) => {
+                trace!("INVALID ROLE {} FOR {}", role, tag);
+                tag.implicit_role()
+            }
+            Some(role) => Some(role),
+            None => tag.implicit_role(),
+        };
+
+        if let Some(role) = role.filter(|role| tag.implicit_role() != Some(*role)) {
+            element.attribute("role", role.to_html_role());
+        }
+
+        for attribute in &self.attributes {
+            if attribute.is_allowed(role) {
+                element.attribute(attribute.to_html_name(), attribute.to_html_value());
+            } else {
+                trace!("INVALID ATTRIBUTE {} FOR {}", attribute.to_html_name(), tag);
+            }
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use crate::{puppet, puppet_style, JsValueResult, PuppetColor, PuppetColumn, PuppetElement};
+use core::{
+    cell::{Cell, RefCell},
+    fmt,
+    fmt::{Debug, Display},
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use log::trace;
+use std::{borrow::Cow, rc::Rc};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Document, Element, Event, HtmlElement};
+
+/// Text of the button retrying a failed load
+const RETRY_LABEL: &str = "Retry";
+
+/// Starts loading the data of a [PuppetAsync] for its key
+pub type PuppetLoader<T, E> =
+    Rc<dyn Fn(Option<&str>) -> Pin<Box<dyn Future<Output = Result<T, E>>>>>;
+
+/// Renders the data of a [PuppetAsync] once it has loaded
+pub type PuppetDataRenderer<T> = Rc<dyn Fn(&T) -> PuppetColumn>;
+
+/// Renders why a [PuppetAsync] could not load its data
+pub type PuppetErrorRenderer<E> = Rc<dyn Fn(&E) -> PuppetColumn>;
+
+/// Where a [PuppetAsync] is in loading its data
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AsyncState<T, E> {
+    Pending,
+    Failed(E),
+    Ready(T),
+}
+
+impl<T, E> AsyncState<T, E> {
+    pub fn to_html_state(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Failed(_) => "failed",
+            Self::Ready(_) => "ready",
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Pending)
+    }
+}
+
+/// A part of the screen built from data that is loaded asynchronously, showing a
+/// skeleton while it loads and an error with a retry button when it fails
+///
+/// ```
+/// use puppeteer_builders::{puppet, AsyncState, PuppetAsync};
+///
+/// let mut profile = PuppetAsync::new(
+///     "profile",
+///     |_key| async { Ok::<_, String>("Ada") },
+///     |name| puppet! { column { row { heading(*name) } } },
+/// );
+/// assert!(profile.to_html_string().contains(r#"aria-busy="true" aria-live="polite" data-state="pending""#));
+///
+/// profile.resolve(Err("Offline".into()));
+/// assert_eq!(profile.state(), &AsyncState::Failed("Offline".into()));
+/// assert!(profile.to_html_string().contains("data-async-retry"));
+/// ```
+pub struct PuppetAsync<T, E> {
+    async_id: &'static str,
+    key: Option<Cow<'static, str>>,
+    loader: PuppetLoader<T, E>,
+    render: PuppetDataRenderer<T>,
+    pending_view: Rc<dyn Fn() -> PuppetColumn>,
+    error_view: PuppetErrorRenderer<E>,
+    state: AsyncState<T, E>,
+}
+
+impl<T: 'static, E: Display + 'static> PuppetAsync<T, E> {
+    pub fn new<L, F, R>(async_id: &'static str, loader: L, render: R) -> Self
+    where
+        L: Fn(Option<&str>) -> F + 'static,
+        F: Future<Output = Result<T, E>> + 'static,
+        R: Fn(&T) -> PuppetColumn + 'static,
+    {
+        Self {
+            async_id,
+            key: Option::default(),
+            loader: Rc::new(move |key| Box::pin(loader(key))),
+            render: Rc::new(render),
+            pending_view: Rc::new(skeleton),
+            error_view: Rc::new(|error: &E| {
+                let message = error.to_string();
+
+                puppet! { column { row { paragraph(message) } } }
+            }),
+            state: AsyncState::Pending,
+        }
+    }
+}
+
+impl<T: 'static, E: 'static> PuppetAsync<T, E> {
+    /// What the data is loaded for, such as the id of a user. Changing it through
+    /// [PuppetAsyncHandle::rekey] drops the data and any load still running.
+    pub fn key<K>(&mut self, key: K) -> &mut Self
+    where
+        K: Into<Cow<'static, str>>,
+    {
+        self.key = Some(key.into());
+
+        self
+    }
+
+    /// Shown while the data loads, an empty skeleton by default
+    pub fn pending_view<F>(&mut self, view: F) -> &mut Self
+    where
+        F: Fn() -> PuppetColumn + 'static,
+    {
+        self.pending_view = Rc::new(view);
+
+        self
+    }
+
+    /// Shown above the retry button when loading failed, the error message by
+    /// default
+    pub fn error_view<F>(&mut self, view: F) -> &mut Self
+    where
+        F: Fn(&E) -> PuppetColumn + 'static,
+    {
+        self.error_view = Rc::new(view);
+
+        self
+    }
+
+    pub fn get_key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    pub fn state(&self) -> &AsyncState<T, E> {
+        &self.state
+    }
+
+    /// Show the outcome of a load
+    pub fn resolve(&mut self, result: Result<T, E>) -> &mut Self {
+        self.state = match result {
+            Ok(data) => AsyncState::Ready(data),
+            Err(error) => AsyncState::Failed(error),
+        };
+
+        self
+    }
+
+    /// Show the pending view again, before loading anew
+    pub fn reset(&mut self) -> &mut Self {
+        self.state = AsyncState::Pending;
+
+        self
+    }
+
+    /// Start loading the data for the current key, to be given to
+    /// [PuppetAsync::resolve]
+    pub fn load(&self) -> Pin<Box<dyn Future<Output = Result<T, E>>>> {
+        (self.loader)(self.key.as_deref())
+    }
+
+    pub fn to_element(&self) -> PuppetElement {
+        let mut container = PuppetElement::new("div");
+        container
+            .attribute("id", self.async_id)
+            .attribute("aria-busy", self.state.is_pending().to_string())
+            .attribute("aria-live", "polite")
+            .attribute("data-state", self.state.to_html_state());
+
+        match &self.state {
+            AsyncState::Pending => {
+                container.add_child((self.pending_view)().to_element());
+            }
+            AsyncState::Failed(error) => {
+                let mut retry = PuppetElement::new("button");
+                retry
+                    .attribute("type", "button")
+                    .attribute("data-async-retry", "")
+                    .add_child(RETRY_LABEL);
+
+                let mut alert = (self.error_view)(error).to_element();
+                alert.attribute("role", "alert").add_child(retry);
+                container.add_child(alert);
+            }
+            AsyncState::Ready(data) => {
+                container.add_child((self.render)(data).to_element());
+            }
+        }
+
+        container
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+
+    /// Render the pending view at the end of `parent_node`, then the data once it
+    /// has loaded
+    pub fn mount(
+        self,
+        document: &Document,
+        parent_node: &Element,
+    ) -> JsValueResult<Rc<PuppetAsyncHandle<T, E>>> {
+        let container = document.create_element("div")?;
+        parent_node.append_with_node_1(&container)?;
+
+        let handle = Rc::new(PuppetAsyncHandle {
+            model: RefCell::new(self),
+            document: document.clone(),
+            container: container.clone(),
+            generation: Cell::new(0),
+            running: RefCell::default(),
+        });
+
+        let weak_handle = Rc::downgrade(&handle);
+        let on_click = Closure::wrap(Box::new(move |event: Event| {
+            let retry = event
+                .target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+                .and_then(|target| target.closest("[data-async-retry]").ok().flatten());
+
+            if let (Some(handle), Some(_)) = (weak_handle.upgrade(), retry) {
+                if let Err(error) = handle.reload() {
+                    trace!("{:?}", error);
+                }
+            }
+        }) as Box<dyn FnMut(Event)>);
+        container.add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref())?;
+        // The listener lives as long as the component it is attached to
+        on_click.forget();
+
+        handle.reload()?;
+
+        Ok(handle)
+    }
+}
+
+impl<T: Debug, E: Debug> Debug for PuppetAsync<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetAsync")
+            .field("async_id", &self.async_id)
+            .field("key", &self.key)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A mounted [PuppetAsync], dropping it cancels the load still running
+pub struct PuppetAsyncHandle<T, E> {
+    model: RefCell<PuppetAsync<T, E>>,
+    document: Document,
+    container: Element,
+    /// Increased by every load so that the outcome of a stale one is ignored
+    generation: Cell<u64>,
+    running: RefCell<Option<Rc<CancelToken>>>,
+}
+
+impl<T: 'static, E: 'static> PuppetAsyncHandle<T, E> {
+    pub fn with_async<F, R>(&self, callback: F) -> R
+    where
+        F: FnOnce(&PuppetAsync<T, E>) -> R,
+    {
+        callback(&self.model.borrow())
+    }
+
+    /// Load the data again, cancelling the load still running
+    pub fn reload(self: &Rc<Self>) -> JsValueResult<()> {
+        self.cancel();
+        self.model.borrow_mut().reset();
+        self.render()?;
+
+        let generation = self.generation.get().wrapping_add(1);
+        self.generation.set(generation);
+
+        let token = Rc::new(CancelToken::default());
+        *self.running.borrow_mut() = Some(Rc::clone(&token));
+
+        let load = Cancellable {
+            future: self.model.borrow().load(),
+            token,
+        };
+        let weak_handle = Rc::downgrade(self);
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let Some(result) = load.await else {
+                return;
+            };
+
+            match weak_handle.upgrade() {
+                // Components removed from the page do not render anymore
+                Some(handle)
+                    if handle.generation.get() == generation && handle.container.is_connected() =>
+                {
+                    handle.running.borrow_mut().take();
+                    handle.model.borrow_mut().resolve(result);
+
+                    if let Err(error) = handle.render() {
+                        trace!("{:?}", error);
+                    }
+                }
+                _ => trace!("STALE ASYNC LOAD {} DISCARDED", generation),
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Load the data for another key, such as after navigating to another user
+    pub fn rekey<K>(self: &Rc<Self>, key: K) -> JsValueResult<()>
+    where
+        K: Into<Cow<'static, str>>,
+    {
+        self.model.borrow_mut().key(key);
+
+        self.reload()
+    }
+
+    /// Stop the load still running, keeping what is shown
+    pub fn cancel(&self) {
+        if let Some(token) = self.running.borrow_mut().take() {
+            token.cancel();
+        }
+    }
+
+    fn render(&self) -> JsValueResult<()> {
+        let focused = self
+            .document
+            .active_element()
+            .is_some_and(|active| self.container.contains(Some(&active)));
+
+        self.container.set_inner_html("");
+        self.model
+            .borrow()
+            .to_element()
+            .to_html(&self.document, &self.container)?;
+
+        // Rendering removed the focused element, keep the focus in the component
+        if focused {
+            let target = match self.container.query_selector("[data-async-retry]")? {
+                Some(retry) => Some(retry),
+                None => self.container.first_element_child(),
+            };
+
+            if let Some(target) = target.and_then(|target| target.dyn_into::<HtmlElement>().ok()) {
+                if !target.has_attribute("data-async-retry") {
+                    target.set_attribute("tabindex", "-1")?;
+                }
+                target.focus()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, E> Drop for PuppetAsyncHandle<T, E> {
+    fn drop(&mut self) {
+        if let Some(token) = self.running.get_mut().take() {
+            token.cancel();
+        }
+    }
+}
+
+impl<T, E> Debug for PuppetAsyncHandle<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetAsyncHandle")
+            .field("generation", &self.generation.get())
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Default)]
+struct CancelToken {
+    cancelled: Cell<bool>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl CancelToken {
+    fn cancel(&self) {
+        self.cancelled.set(true);
+
+        // Wake the task so that it drops its future now rather than when the
+        // future would have made progress
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A future resolving to `None` as soon as its token is cancelled, dropping the
+/// work still to be done
+struct Cancellable<T> {
+    future: Pin<Box<dyn Future<Output = T>>>,
+    token: Rc<CancelToken>,
+}
+
+impl<T> Future for Cancellable<T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.token.cancelled.get() {
+            return Poll::Ready(None);
+        }
+
+        *self.token.waker.borrow_mut() = Some(context.waker().clone());
+
+        self.future.as_mut().poll(context).map(Some)
+    }
+}
+
+fn skeleton() -> PuppetColumn {
+    let mut column = puppet! { column { row { paragraph("") } row { paragraph("") } } };
+    column.set_style(&puppet_style! { background_color: PuppetColor::Hex("EEEEEE") });
+
+    column
+}
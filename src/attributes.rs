@@ -0,0 +1,183 @@
+use crate::{PuppetElement, PuppetText};
+use log::trace;
+use std::borrow::Cow;
+
+/// Attributes set through their own typed APIs, which cannot be set as
+/// [PuppetAttribute::Custom]
+const MANAGED_ATTRIBUTES: &[&str] = &["id", "class", "style", "role", "tabindex"];
+
+/// An HTML attribute of a [crate::PuppetColumn], [crate::PuppetRow] or
+/// [crate::Component], written and escaped alike by the DOM and string backends
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub enum PuppetAttribute {
+    /// Advisory text, shown as a tooltip
+    Title(PuppetText),
+    /// The language of the content as a BCP 47 tag, such as `fr-CA`
+    Lang(Cow<'static, str>),
+    /// Not rendered nor exposed to assistive technologies
+    Hidden,
+    /// Any other attribute, rejected when its name is not valid lowercase, starts with
+    /// `on` as event handlers would run markup as script, or has a typed API
+    Custom(&'static str, PuppetText),
+}
+
+impl PuppetAttribute {
+    pub fn to_html_name(&self) -> &'static str {
+        match self {
+            Self::Title(_) => "title",
+            Self::Lang(_) => "lang",
+            Self::Hidden => "hidden",
+            Self::Custom(name, _) => name,
+        }
+    }
+
+    pub fn to_html_value(&self) -> PuppetText {
+        match self {
+            Self::Title(title) => title.clone(),
+            Self::Lang(lang) => lang.clone().into(),
+            Self::Hidden => PuppetText::default(),
+            Self::Custom(_, value) => value.clone(),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Title(_) | Self::Hidden => true,
+            Self::Lang(lang) => is_valid_lang(lang),
+            Self::Custom(name, _) => is_valid_attribute_name(name),
+        }
+    }
+}
+
+/// Whether `name` can be set as a [PuppetAttribute::Custom]. Names are lowercase as
+/// HTML writes them, so that `HREF` or `ONCLICK` cannot slip past the checks made on
+/// `href` or `onclick`.
+pub fn is_valid_attribute_name(name: &str) -> bool {
+    name.starts_with(|character: char| character.is_ascii_lowercase())
+        && name.chars().all(|character| {
+            character.is_ascii_lowercase()
+                || character.is_ascii_digit()
+                || "-_:.".contains(character)
+        })
+        && !name.starts_with("on")
+        && !name.starts_with("aria-")
+        && !name.starts_with("data-")
+        && !MANAGED_ATTRIBUTES.contains(&name)
+}
+
+/// Whether `key` can follow `data-` in an attribute name: lowercase ASCII letters,
+/// digits and hyphens, not starting with `xml`
+pub fn is_valid_data_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with("xml")
+        && key
+            .chars()
+            .all(|character| matches!(character, 'a'..='z' | '0'..='9' | '-'))
+}
+
+/// Whether `lang` looks like a BCP 47 tag: subtags of ASCII letters and digits
+/// joined by hyphens, the empty tag standing for an unknown language
+fn is_valid_lang(lang: &str) -> bool {
+    lang.is_empty()
+        || lang.split('-').all(|subtag| {
+            (1..=8).contains(&subtag.len())
+                && subtag
+                    .chars()
+                    .all(|character| character.is_ascii_alphanumeric())
+        })
+}
+
+/// The name of `data-{key}` in the dataset of a DOM element, in camel case
+pub(crate) fn to_dataset_key(key: &str) -> String {
+    let mut dataset_key = String::with_capacity(key.len());
+    let mut characters = key.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match characters.peek() {
+            Some(next) if character == '-' && next.is_ascii_lowercase() => {
+                dataset_key.push(next.to_ascii_uppercase());
+                characters.next();
+            }
+            _ => dataset_key.push(character),
+        }
+    }
+
+    dataset_key
+}
+
+/// The [PuppetAttribute]s and `data-*` attributes of a node
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetAttributes {
+    attributes: Vec<PuppetAttribute>,
+    dataset: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl PuppetAttributes {
+    /// Replaces any previous value of the same attribute, keeps the attributes as
+    /// they were when `attribute` is not valid
+    pub(crate) fn set(&mut self, attribute: PuppetAttribute) {
+        if !attribute.is_valid() {
+            trace!(
+                "INVALID ATTRIBUTE {} {:?}",
+                attribute.to_html_name(),
+                attribute.to_html_value().resolve()
+            );
+
+            return;
+        }
+
+        match self
+            .attributes
+            .iter_mut()
+            .find(|previous| previous.to_html_name() == attribute.to_html_name())
+        {
+            Some(previous) => *previous = attribute,
+            None => self.attributes.push(attribute),
+        }
+    }
+
+    /// Keeps the dataset as it was when `key` is not valid, see [is_valid_data_key]
+    pub(crate) fn set_data(&mut self, key: Cow<'static, str>, value: Cow<'static, str>) {
+        if !is_valid_data_key(&key) {
+            trace!("INVALID DATA ATTRIBUTE {:?}", key);
+
+            return;
+        }
+
+        match self
+            .dataset
+            .iter_mut()
+            .find(|(previous, _)| *previous == key)
+        {
+            Some((_, previous)) => *previous = value,
+            None => self.dataset.push((key, value)),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PuppetAttribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.to_html_name() == name)
+    }
+
+    pub fn get_data(&self, key: &str) -> Option<&str> {
+        self.dataset
+            .iter()
+            .find(|(data_key, _)| data_key == key)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.get("hidden").is_some()
+    }
+
+    pub(crate) fn write_attributes(&self, element: &mut PuppetElement) {
+        for attribute in &self.attributes {
+            element.attribute(attribute.to_html_name(), attribute.to_html_value());
+        }
+
+        for (key, value) in &self.dataset {
+            element.data(key.clone(), value.clone());
+        }
+    }
+}
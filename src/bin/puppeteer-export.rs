@@ -0,0 +1,29 @@
+//! Write the demo app as a static site
+//!
+//! ```text
+//! puppeteer-export [OUT_DIR] [ASSET_ROOT]
+//! ```
+
+use puppeteer_builders::{demo_screen, PuppetSite};
+use std::{env, process::ExitCode};
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let out_dir = args.next().unwrap_or_else(|| "site".into());
+    let asset_root = args.next().unwrap_or_else(|| ".".into());
+
+    let mut site = PuppetSite::new("Puppeteer");
+    site.asset_root(asset_root)
+        .page("demo", "Demo", demo_screen());
+
+    match site.export(&out_dir) {
+        Ok(()) => {
+            println!("wrote {}", out_dir);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("could not export the site: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
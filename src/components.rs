@@ -1,10 +1,328 @@
+use crate::{
+    is_valid_id, ImageAlt, JsValueResult, PuppetAttribute, PuppetAttributes, PuppetElement,
+    PuppetEventHandler, PuppetId, PuppetImage, PuppetScopedStyle, PuppetStyle, PuppetStyleVariants,
+    PuppetText, PuppetVariant,
+};
+use core::{
+    fmt,
+    fmt::{Debug, Display},
+};
+use log::trace;
+use std::borrow::Cow;
+use web_sys::{Document, Element, Event};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct Component {
     component_type: ComponentType,
+    component_id: Option<&'static str>,
+    puppet_id: PuppetId,
+    /// Write the generated id when there is no `component_id`, set once another
+    /// component refers to this one
+    expose_id: bool,
+    /// Ids of the components naming this one, `aria-labelledby`
+    labelled_by: Vec<Cow<'static, str>>,
+    /// Ids of the components describing this one, `aria-describedby`
+    described_by: Vec<Cow<'static, str>>,
+    tab_index: Option<i32>,
+    attributes: PuppetAttributes,
     style: PuppetStyle,
-    // Fot images, text is used for information
-    text: &'static str,
+    /// Variants extending `style`
+    style_variants: Option<PuppetStyleVariants>,
+    variant: Option<PuppetVariant>,
+    scoped_style: Option<PuppetScopedStyle>,
+    /// The alternative text of a [ComponentType::Image]
+    text: PuppetText,
+    /// Trusted markup written in place of the text
+    raw_html: Option<Cow<'static, str>>,
+    /// Target of a [ComponentType::Link]
+    href: Option<Cow<'static, str>>,
+    /// Sources and loading of a [ComponentType::Image]
+    image: Option<PuppetImage>,
+    handlers: Vec<PuppetEventHandler>,
 }
 
+impl Component {
+    pub fn new<T>(component_type: ComponentType, text: T) -> Self
+    where
+        T: Into<PuppetText>,
+    {
+        Self {
+            component_type,
+            component_id: Option::default(),
+            puppet_id: PuppetId::generate(),
+            expose_id: false,
+            labelled_by: Vec::default(),
+            described_by: Vec::default(),
+            tab_index: Option::default(),
+            attributes: PuppetAttributes::default(),
+            style: PuppetStyle::default(),
+            style_variants: Option::default(),
+            variant: Option::default(),
+            scoped_style: Option::default(),
+            text: text.into(),
+            raw_html: Option::default(),
+            href: Option::default(),
+            image: Option::default(),
+            handlers: Vec::default(),
+        }
+    }
+
+    pub fn component_id(&mut self, id: &'static str) -> &mut Self {
+        if is_valid_id(id) {
+            self.component_id = Some(id);
+        } else {
+            if !id.is_empty() {
+                trace!("INVALID ID {:?}. IDS CANNOT CONTAIN WHITESPACE", id);
+            }
+            self.component_id = None;
+        }
+
+        self
+    }
+
+    /// The `component_id`, or else the generated id
+    pub fn id(&self) -> Cow<'static, str> {
+        match self.component_id {
+            Some(component_id) => Cow::Borrowed(component_id),
+            None => Cow::Owned(self.puppet_id.to_html_id()),
+        }
+    }
+
+    /// Write the generated id even though no component refers to it yet
+    pub fn expose_id(&mut self) -> &mut Self {
+        self.expose_id = true;
+
+        self
+    }
+
+    /// Name this component by the text of `label`, such as a heading naming a button
+    pub fn labelled_by(&mut self, label: &mut Component) -> &mut Self {
+        label.expose_id();
+        self.labelled_by.push(label.id());
+
+        self
+    }
+
+    /// Name this component by the element with the id `label`, written by hand
+    pub fn labelled_by_id(&mut self, label: &'static str) -> &mut Self {
+        self.labelled_by.push(Cow::Borrowed(label));
+
+        self
+    }
+
+    /// Describe this component by the text of `description`, such as a hint below
+    /// a button
+    pub fn described_by(&mut self, description: &mut Component) -> &mut Self {
+        description.expose_id();
+        self.described_by.push(description.id());
+
+        self
+    }
+
+    /// Describe this component by the element with the id `description`, written
+    /// by hand
+    pub fn described_by_id(&mut self, description: &'static str) -> &mut Self {
+        self.described_by.push(Cow::Borrowed(description));
+
+        self
+    }
+
+    /// Override where this component sits in the tab order: `0` for the document
+    /// order, `-1` to only focus it from code, positive values before everything
+    /// else, which is rarely what users expect
+    pub fn tab_index(&mut self, tab_index: i32) -> &mut Self {
+        self.tab_index = Some(tab_index);
+
+        self
+    }
+
+    /// Set an attribute, rejected when it is not valid
+    pub fn attribute(&mut self, attribute: PuppetAttribute) -> &mut Self {
+        self.attributes.set(attribute);
+
+        self
+    }
+
+    /// Set the `data-{key}` attribute, a hook for analytics and tests
+    pub fn data<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.attributes.set_data(key.into(), value.into());
+
+        self
+    }
+
+    pub fn attributes(&self) -> &PuppetAttributes {
+        &self.attributes
+    }
+
+    /// Write `html` as markup in place of the text, which is always escaped: only
+    /// for markup known to be safe, never for anything built from user input. Images
+    /// have no content and ignore it.
+    pub fn raw_html<T>(&mut self, html: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.raw_html = Some(html.into());
+
+        self
+    }
+
+    pub fn text<T>(&mut self, text: T) -> &mut Self
+    where
+        T: Into<PuppetText>,
+    {
+        self.text = text.into();
+
+        self
+    }
+
+    pub fn href<T>(&mut self, href: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.href = Some(href.into());
+
+        self
+    }
+
+    pub fn src<T>(&mut self, src: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.image.get_or_insert_with(PuppetImage::default).src(src);
+
+        self
+    }
+
+    /// Describe a [ComponentType::Image] by `image`, whose alternative text is
+    /// still [Component::text] unless it is decorative
+    pub fn image(&mut self, image: PuppetImage) -> &mut Self {
+        self.image = Some(image);
+
+        self
+    }
+
+    pub fn set_style(&mut self, style: &PuppetStyle) -> &mut Self {
+        self.style = *style;
+
+        self
+    }
+
+    /// Rules relative to this node for what its style cannot express, see
+    /// [PuppetScopedStyle]
+    pub fn scoped_style(&mut self, scoped_style: &PuppetScopedStyle) -> &mut Self {
+        self.scoped_style = Some(scoped_style.clone());
+
+        self
+    }
+
+    /// The variants extending the style of this component, see [PuppetStyleVariants]
+    pub fn style_variants(&mut self, style_variants: &PuppetStyleVariants) -> &mut Self {
+        self.style_variants = Some(style_variants.clone());
+
+        self
+    }
+
+    pub fn variant(&mut self, variant: PuppetVariant) -> &mut Self {
+        self.variant = Some(variant);
+
+        self
+    }
+
+    /// The style of the component extended by its variant, states aside
+    pub fn resolved_style(&self) -> PuppetStyle {
+        match &self.style_variants {
+            Some(style_variants) => style_variants.resolve(&self.style, self.variant),
+            None => self.style,
+        }
+    }
+
+    /// Listen for a DOM event such as `click` or `input` on this component
+    pub fn on<F>(&mut self, event: &'static str, handler: F) -> &mut Self
+    where
+        F: Fn(&Event) + 'static,
+    {
+        self.handlers.push(PuppetEventHandler::new(event, handler));
+
+        self
+    }
+
+    pub fn to_element(&self) -> PuppetElement {
+        let style = self.resolved_style();
+        let mut element = match (self.component_type, &self.image) {
+            (ComponentType::Image, image) => {
+                let image = image.clone().unwrap_or_default();
+                let alt = match image.is_decorative() {
+                    true => ImageAlt::Decorative,
+                    false => ImageAlt::Text(self.text.clone()),
+                };
+
+                image.to_element_with(&alt, &style)
+            }
+            (component_type, _) => {
+                let mut element = PuppetElement::new(component_type.to_html_tag());
+                match &self.raw_html {
+                    Some(raw_html) => element.raw_html(raw_html.clone()),
+                    None => element.add_child(self.text.clone()),
+                };
+                element.set_style(&style);
+
+                element
+            }
+        };
+
+        if let (ComponentType::Link, Some(href)) = (self.component_type, &self.href) {
+            element.attribute("href", href.clone());
+        }
+
+        if self.component_id.is_some() || self.expose_id {
+            element.attribute("id", self.id());
+        }
+
+        if !self.labelled_by.is_empty() {
+            element.attribute("aria-labelledby", self.labelled_by.join(" "));
+        }
+
+        if !self.described_by.is_empty() {
+            element.attribute("aria-describedby", self.described_by.join(" "));
+        }
+
+        if let Some(tab_index) = self.tab_index {
+            element.attribute("tabindex", tab_index.to_string());
+        }
+
+        self.attributes.write_attributes(&mut element);
+
+        if let Some(style_variants) = &self.style_variants {
+            for (state, patch) in style_variants.resolve_states(self.variant) {
+                element.state_style(state, patch);
+            }
+        }
+
+        if let Some(scoped_style) = &self.scoped_style {
+            element.scoped_style(scoped_style);
+        }
+
+        for handler in &self.handlers {
+            element.add_handler(handler.clone());
+        }
+
+        element
+    }
+
+    pub fn to_html(&self, document: &Document, parent_node: &Element) -> JsValueResult<()> {
+        self.to_element().to_html(document, parent_node)
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum ComponentType {
     Button,
     Paragraph,
@@ -12,3 +330,47 @@ pub enum ComponentType {
     Image,
     Link,
 }
+
+impl ComponentType {
+    pub fn to_html_tag(&self) -> &'static str {
+        match self {
+            Self::Button => "button",
+            Self::Paragraph => "p",
+            Self::Heading => "h1",
+            Self::Image => "img",
+            Self::Link => "a",
+        }
+    }
+}
+
+impl Debug for ComponentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}",
+            match self {
+                Self::Button => "ComponentType::Button",
+                Self::Paragraph => "ComponentType::Paragraph",
+                Self::Heading => "ComponentType::Heading",
+                Self::Image => "ComponentType::Image",
+                Self::Link => "ComponentType::Link",
+            }
+        )
+    }
+}
+
+impl Display for ComponentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}",
+            match self {
+                Self::Button => "Button",
+                Self::Paragraph => "Paragraph",
+                Self::Heading => "Heading",
+                Self::Image => "Image",
+                Self::Link => "Link",
+            }
+        )
+    }
+}
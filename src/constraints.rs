@@ -0,0 +1,205 @@
+use crate::{
+    layout::{Item, Solver, EPSILON},
+    PuppetChild, PuppetColumn, PuppetElement, PuppetLength, PuppetUnit, PuppetViewport,
+};
+use core::fmt::{self, Display};
+
+/// A size constraint that cannot be honored as written, found by
+/// [PuppetElement::validate_layout]. Sizes are in pixels of the viewport.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PuppetLayoutWarning {
+    /// `min-width` above `max-width`, the minimum winning as in CSS
+    MinWidthAboveMax { path: String, min: f32, max: f32 },
+    /// `min-height` above `max-height`, the minimum winning as in CSS
+    MinHeightAboveMax { path: String, min: f32, max: f32 },
+    /// A percentage of the height of a parent whose height depends on its content,
+    /// which browsers ignore
+    PercentageOfAutoHeight {
+        path: String,
+        property: &'static str,
+    },
+    /// A box whose width, minimum width, padding and margin cannot fit in the content
+    /// box of its parent
+    Overflow {
+        path: String,
+        width: f32,
+        available: f32,
+    },
+}
+
+impl PuppetLayoutWarning {
+    /// Where the constraint is, as tags and child indexes from the validated element
+    /// such as `div[0] > p[1]`
+    pub fn path(&self) -> &str {
+        match self {
+            Self::MinWidthAboveMax { path, .. }
+            | Self::MinHeightAboveMax { path, .. }
+            | Self::PercentageOfAutoHeight { path, .. }
+            | Self::Overflow { path, .. } => path,
+        }
+    }
+}
+
+impl Display for PuppetLayoutWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MinWidthAboveMax { path, min, max } => {
+                write!(f, "{}: min-width {}px above max-width {}px", path, min, max)
+            }
+            Self::MinHeightAboveMax { path, min, max } => write!(
+                f,
+                "{}: min-height {}px above max-height {}px",
+                path, min, max
+            ),
+            Self::PercentageOfAutoHeight { path, property } => write!(
+                f,
+                "{}: percentage {} of a parent with an auto height",
+                path, property
+            ),
+            Self::Overflow {
+                path,
+                width,
+                available,
+            } => write!(f, "{}: {}px wide in {}px available", path, width, available),
+        }
+    }
+}
+
+impl PuppetElement {
+    /// Every size constraint of the tree that cannot be honored on `viewport`, to be
+    /// checked before rendering.
+    ///
+    /// Sizes are resolved as [PuppetElement::compute_layout] does, so a percentage
+    /// height is only valid within a parent of definite height, such as
+    /// [PuppetLength::ViewPortHeight].
+    ///
+    /// ```
+    /// use puppeteer_builders::{puppet, PuppetLayoutWarning, PuppetUnit, PuppetViewport};
+    ///
+    /// let column = puppet! {
+    ///     column {
+    ///         style { min_width: PuppetUnit::Pixels(300), max_width: PuppetUnit::Pixels(200) }
+    ///     }
+    /// };
+    ///
+    /// let warnings = column.validate_layout(&PuppetViewport::new(1000.0, 600.0));
+    /// assert_eq!(
+    ///     warnings,
+    ///     [PuppetLayoutWarning::MinWidthAboveMax { path: "div[0]".into(), min: 300.0, max: 200.0 }]
+    /// );
+    /// ```
+    pub fn validate_layout(&self, viewport: &PuppetViewport) -> Vec<PuppetLayoutWarning> {
+        let mut warnings = Vec::default();
+        let solver = Solver { viewport };
+
+        validate(
+            &solver,
+            self,
+            format!("{}[0]", self.tag()),
+            viewport.width(),
+            Some(viewport.height()),
+            &mut warnings,
+        );
+
+        warnings
+    }
+}
+
+impl PuppetColumn {
+    pub fn validate_layout(&self, viewport: &PuppetViewport) -> Vec<PuppetLayoutWarning> {
+        self.to_element().validate_layout(viewport)
+    }
+}
+
+fn validate(
+    solver: &Solver,
+    element: &PuppetElement,
+    path: String,
+    containing_width: f32,
+    containing_height: Option<f32>,
+    warnings: &mut Vec<PuppetLayoutWarning>,
+) {
+    let resolved = solver.resolve(&Item::Element(element), containing_width, containing_height);
+
+    if resolved.min_width > resolved.max_width {
+        warnings.push(PuppetLayoutWarning::MinWidthAboveMax {
+            path: path.clone(),
+            min: resolved.min_width,
+            max: resolved.max_width,
+        });
+    }
+
+    if resolved.min_height > resolved.max_height {
+        warnings.push(PuppetLayoutWarning::MinHeightAboveMax {
+            path: path.clone(),
+            min: resolved.min_height,
+            max: resolved.max_height,
+        });
+    }
+
+    if let (Some(style), None) = (element.style(), containing_height) {
+        let percentages = [
+            ("height", style.height.is_some_and(is_percentage)),
+            (
+                "min-height",
+                style.min_height.is_some_and(is_nonzero_percentage),
+            ),
+            (
+                "max-height",
+                style.max_height.is_some_and(is_nonzero_percentage),
+            ),
+        ];
+
+        for (property, _) in percentages.iter().filter(|(_, percentage)| *percentage) {
+            warnings.push(PuppetLayoutWarning::PercentageOfAutoHeight {
+                path: path.clone(),
+                property,
+            });
+        }
+    }
+
+    // An auto width shrinks down to the minimum width, padding and margin
+    let width = resolved.clamp_width(resolved.width.unwrap_or(0.0)) + resolved.extra();
+    if width > containing_width + EPSILON {
+        warnings.push(PuppetLayoutWarning::Overflow {
+            path: path.clone(),
+            width,
+            available: containing_width,
+        });
+    }
+
+    let content_width = resolved.clamp_width(
+        resolved
+            .width
+            .unwrap_or((containing_width - resolved.extra()).max(0.0)),
+    );
+    let content_height = resolved.height.map(|height| resolved.clamp_height(height));
+
+    for (index, child) in element.children().iter().enumerate() {
+        if let PuppetChild::Element(child) = child {
+            validate(
+                solver,
+                child,
+                format!("{} > {}[{}]", path, child.tag(), index),
+                content_width,
+                content_height,
+                warnings,
+            );
+        }
+    }
+}
+
+fn is_percentage(length: PuppetLength) -> bool {
+    matches!(
+        length,
+        PuppetLength::Full
+            | PuppetLength::ThreeQuarter
+            | PuppetLength::Half
+            | PuppetLength::Quarter
+            | PuppetLength::Eighth
+    )
+}
+
+fn is_nonzero_percentage(unit: PuppetUnit) -> bool {
+    matches!(unit, PuppetUnit::Percentage(percentage) if percentage > 0)
+}
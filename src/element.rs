@@ -0,0 +1,583 @@
+use crate::{
+    add_class, bind_text, install_scoped_rules, install_state_rules, is_safe_attribute,
+    is_valid_data_key, prune_text_bindings, to_dataset_key, JsValueResult, PuppetEventHandler,
+    PuppetScopedStyle, PuppetStyle, PuppetStylePatch, PuppetText, StyleState,
+};
+use core::fmt::Write;
+use log::trace;
+use std::borrow::Cow;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Document, Element, HtmlElement};
+
+/// Elements that never have children nor a closing tag
+pub(crate) const VOID_TAGS: &[&str] = &["area", "br", "col", "hr", "img", "input", "link", "meta"];
+
+/// A backend independent description of one HTML element.
+///
+/// [crate::PuppetColumn], [crate::PuppetRow] and [crate::Component] are lowered to
+/// this tree, which is then written either to the DOM or to a string so that both
+/// backends always produce the same markup.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetElement {
+    tag: &'static str,
+    attributes: Vec<(&'static str, PuppetText)>,
+    /// `data-*` attributes by the name following `data-`, written through the
+    /// dataset of the DOM element
+    dataset: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    style: Option<PuppetStyle>,
+    /// Patches of the style while in a state, written as stylesheet rules
+    states: Vec<(StyleState, PuppetStylePatch)>,
+    /// Rules relative to the element, written as stylesheet rules
+    scoped_style: Option<PuppetScopedStyle>,
+    handlers: Vec<PuppetEventHandler>,
+    children: Vec<PuppetChild>,
+    /// Trusted markup written in place of the children, without escaping
+    raw_html: Option<Cow<'static, str>>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub enum PuppetChild {
+    Element(PuppetElement),
+    Text(PuppetText),
+}
+
+impl From<PuppetElement> for PuppetChild {
+    fn from(element: PuppetElement) -> Self {
+        Self::Element(element)
+    }
+}
+
+impl From<PuppetText> for PuppetChild {
+    fn from(text: PuppetText) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&'static str> for PuppetChild {
+    fn from(text: &'static str) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+impl From<String> for PuppetChild {
+    fn from(text: String) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+impl PuppetElement {
+    pub fn new(tag: &'static str) -> Self {
+        Self {
+            tag,
+            attributes: Vec::default(),
+            dataset: Vec::default(),
+            style: Option::default(),
+            states: Vec::default(),
+            scoped_style: Option::default(),
+            handlers: Vec::default(),
+            children: Vec::default(),
+            raw_html: Option::default(),
+        }
+    }
+
+    /// Set an attribute, replacing any previous value of the same name. URLs of
+    /// `href`, `src` and the like are rejected unless they are safe, see
+    /// [crate::sanitize_url], and checked again whenever a translated value is
+    /// written.
+    pub fn attribute<T>(&mut self, name: &'static str, value: T) -> &mut Self
+    where
+        T: Into<PuppetText>,
+    {
+        let value = value.into();
+
+        if !is_safe_attribute(name, &value.resolve()) {
+            return self;
+        }
+
+        match self
+            .attributes
+            .iter_mut()
+            .find(|(attribute, _)| *attribute == name)
+        {
+            Some((_, previous)) => *previous = value,
+            None => self.attributes.push((name, value)),
+        }
+
+        self
+    }
+
+    /// Set the `data-{key}` attribute, replacing any previous value. `key` is made
+    /// of lowercase ASCII letters, digits and hyphens, as in `data-test-id`.
+    pub fn data<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        let key = key.into();
+        if !is_valid_data_key(&key) {
+            trace!("INVALID DATA ATTRIBUTE {:?}", key);
+
+            return self;
+        }
+        let value = value.into();
+
+        match self
+            .dataset
+            .iter_mut()
+            .find(|(previous, _)| *previous == key)
+        {
+            Some((_, previous)) => *previous = value,
+            None => self.dataset.push((key, value)),
+        }
+
+        self
+    }
+
+    /// Write `html` as the content of the element in place of its children, as is.
+    ///
+    /// Text is always escaped otherwise: this is the only way to write markup, and it
+    /// must never be given markup built from user input.
+    pub fn raw_html<T>(&mut self, html: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.raw_html = Some(html.into());
+
+        self
+    }
+
+    /// Style the element, an empty style leaving it without `style` attribute
+    pub fn set_style(&mut self, style: &PuppetStyle) -> &mut Self {
+        self.style = Some(*style).filter(|style| !style.is_empty());
+
+        self
+    }
+
+    /// Patch the style while the element is in `state`, replacing any previous patch
+    /// of the state. Empty patches are left out.
+    pub fn state_style(&mut self, state: StyleState, patch: PuppetStylePatch) -> &mut Self {
+        self.states.retain(|(previous, _)| *previous != state);
+
+        if !patch.is_empty() {
+            self.states.push((state, patch));
+            self.states.sort_by_key(|(state, _)| *state);
+        }
+
+        self
+    }
+
+    /// Scope the rules of `scoped_style` to the element and its descendants
+    pub fn scoped_style(&mut self, scoped_style: &PuppetScopedStyle) -> &mut Self {
+        self.scoped_style = Some(scoped_style.clone()).filter(|scoped| !scoped.is_empty());
+
+        self
+    }
+
+    pub fn add_handler(&mut self, handler: PuppetEventHandler) -> &mut Self {
+        self.handlers.push(handler);
+
+        self
+    }
+
+    pub fn add_child<C>(&mut self, child: C) -> &mut Self
+    where
+        C: Into<PuppetChild>,
+    {
+        self.children.push(child.into());
+
+        self
+    }
+
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    pub fn attributes(&self) -> &[(&'static str, PuppetText)] {
+        &self.attributes
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Option<&PuppetText> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| *attribute == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn dataset(&self) -> &[(Cow<'static, str>, Cow<'static, str>)] {
+        &self.dataset
+    }
+
+    pub fn get_data(&self, key: &str) -> Option<&str> {
+        self.dataset
+            .iter()
+            .find(|(data_key, _)| data_key == key)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    pub fn style(&self) -> Option<&PuppetStyle> {
+        self.style.as_ref()
+    }
+
+    pub fn states(&self) -> &[(StyleState, PuppetStylePatch)] {
+        &self.states
+    }
+
+    pub fn get_scoped_style(&self) -> Option<&PuppetScopedStyle> {
+        self.scoped_style.as_ref()
+    }
+
+    /// The classes of the element in `stylesheet`: the class of its style unless it
+    /// is written inline, then the classes of its states and scope
+    pub(crate) fn class_names(
+        &self,
+        stylesheet: &mut PuppetStylesheet,
+        inline_style: bool,
+    ) -> Option<String> {
+        let style_class = match (&self.style, inline_style) {
+            (Some(style), false) => Some(stylesheet.class_name(style)),
+            _ => None,
+        };
+        let state_class = match self.states.is_empty() {
+            true => None,
+            false => Some(stylesheet.state_class_name(&self.states)),
+        };
+
+        let scope_class = self
+            .scoped_style
+            .as_ref()
+            .map(|scoped_style| stylesheet.scope_class_name(scoped_style));
+
+        let class_names = [style_class, state_class, scope_class]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<String>>();
+
+        match class_names.is_empty() {
+            true => None,
+            false => Some(class_names.join(" ")),
+        }
+    }
+
+    pub fn handlers(&self) -> &[PuppetEventHandler] {
+        &self.handlers
+    }
+
+    pub fn children(&self) -> &[PuppetChild] {
+        &self.children
+    }
+
+    pub fn get_raw_html(&self) -> Option<&str> {
+        self.raw_html.as_deref()
+    }
+
+    pub(crate) fn children_mut(&mut self) -> &mut [PuppetChild] {
+        &mut self.children
+    }
+
+    pub fn is_void(&self) -> bool {
+        VOID_TAGS.contains(&self.tag)
+    }
+
+    pub fn to_html(&self, document: &Document, parent_node: &Element) -> JsValueResult<()> {
+        let element = document.create_element(self.tag)?;
+
+        let element = element.dyn_ref::<HtmlElement>().ok_or(JsValue::from_str(
+            "Could not convert `Element` to `HtmlElement`",
+        ));
+        let element = element?;
+
+        for (name, value) in &self.attributes {
+            let resolved = value.resolve();
+            if !is_safe_attribute(name, &resolved) {
+                continue;
+            }
+
+            element.set_attribute(name, &resolved)?;
+            bind_text(element, Some(name), value);
+        }
+
+        for (key, value) in &self.dataset {
+            element.dataset().set(&to_dataset_key(key), value)?;
+        }
+
+        if let Some(style) = &self.style {
+            style.to_html(element)?;
+        }
+
+        if !self.states.is_empty() {
+            add_class(element, &install_state_rules(&self.states)?)?;
+        }
+
+        if let Some(scoped_style) = &self.scoped_style {
+            add_class(element, &install_scoped_rules(scoped_style)?)?;
+        }
+
+        for handler in &self.handlers {
+            handler.to_html(element)?;
+        }
+
+        if let Some(raw_html) = &self.raw_html {
+            element.set_inner_html(raw_html);
+        }
+
+        for child in self.children.iter().filter(|_| self.raw_html.is_none()) {
+            match child {
+                PuppetChild::Element(child) => child.to_html(document, element)?,
+                PuppetChild::Text(text) => {
+                    let text_node = document.create_text_node(&text.resolve());
+                    bind_text(&text_node, None, text);
+                    element.append_with_node_1(&text_node)?;
+                }
+            }
+        }
+
+        parent_node.append_with_node_1(element)?;
+
+        // Only the root of a render is attached to the document
+        if parent_node.is_connected() {
+            prune_text_bindings();
+        }
+
+        Ok(())
+    }
+
+    /// The element as HTML markup with inline styles
+    pub fn to_html_string(&self) -> String {
+        let mut html = String::default();
+        self.write_html(&mut html, None);
+
+        html
+    }
+
+    /// The element as HTML markup, its styles moved into `stylesheet` and referenced
+    /// through classes so that a stylesheet can be shared by several documents
+    pub fn to_html_string_with_stylesheet(&self, stylesheet: &mut PuppetStylesheet) -> String {
+        let mut html = String::default();
+        self.write_html(&mut html, Some(stylesheet));
+
+        html
+    }
+
+    fn write_html(&self, html: &mut String, mut stylesheet: Option<&mut PuppetStylesheet>) {
+        html.push('<');
+        html.push_str(self.tag);
+
+        let mut class_name = match stylesheet.as_deref_mut() {
+            Some(stylesheet) => self.class_names(stylesheet, false),
+            // Rules of states and scopes are only collected with a stylesheet, but
+            // the classes are written all the same so that the markup matches the DOM
+            None => self.class_names(&mut PuppetStylesheet::new(), true),
+        };
+
+        for (name, value) in &self.attributes {
+            let mut value = value.resolve().into_owned();
+            if !is_safe_attribute(name, &value) {
+                continue;
+            }
+
+            if *name == "class" {
+                if let Some(class_name) = class_name.take() {
+                    value.push(' ');
+                    value.push_str(&class_name);
+                }
+            }
+
+            // Writing to a String never fails
+            let _ = write!(html, r#" {}="{}""#, name, escape_html_attribute(&value));
+        }
+
+        for (key, value) in &self.dataset {
+            let _ = write!(html, r#" data-{}="{}""#, key, escape_html_attribute(value));
+        }
+
+        if let Some(class_name) = class_name {
+            let _ = write!(html, r#" class="{}""#, escape_html_attribute(&class_name));
+        }
+
+        if let (Some(style), None) = (&self.style, &stylesheet) {
+            let _ = write!(
+                html,
+                r#" style="{}""#,
+                escape_html_attribute(&style.to_css())
+            );
+        }
+
+        html.push('>');
+
+        if self.is_void() {
+            return;
+        }
+
+        if let Some(raw_html) = &self.raw_html {
+            html.push_str(raw_html);
+        }
+
+        for child in self.children.iter().filter(|_| self.raw_html.is_none()) {
+            match child {
+                PuppetChild::Element(child) => child.write_html(html, stylesheet.as_deref_mut()),
+                PuppetChild::Text(text) => html.push_str(&escape_html_text(&text.resolve())),
+            }
+        }
+
+        let _ = write!(html, "</{}>", self.tag);
+    }
+}
+
+/// Escape `text` to be written between tags, where it can never become markup
+pub fn escape_html_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            character => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+/// Escape `value` to be written within a double quoted attribute
+pub fn escape_html_attribute(value: &str) -> String {
+    escape_html_text(value).replace('"', "&quot;")
+}
+
+/// Styles collected from a tree into deduplicated class rules
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct PuppetStylesheet {
+    rules: Vec<(String, Declarations)>,
+    /// Selectors and declarations of [StyleState] rules, written after the other
+    /// rules
+    state_rules: Vec<(String, Declarations)>,
+    /// Scope classes with their [PuppetScopedStyle] rules, written last
+    scoped_rules: Vec<(String, Vec<(String, Declarations)>)>,
+}
+
+/// The properties and values of a rule, kept apart so that values are never split
+pub(crate) type Declarations = Vec<(&'static str, String)>;
+
+impl PuppetStylesheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The class holding `style`, derived from its declarations so that it stays
+    /// the same as long as the style does not change
+    pub fn class_name(&mut self, style: &PuppetStyle) -> String {
+        let class_name = format!("puppet-{:08x}", fnv1a(style.to_css().as_bytes()));
+
+        if !self.rules.iter().any(|(rule, _)| *rule == class_name) {
+            self.rules
+                .push((class_name.clone(), style.css_properties()));
+        }
+
+        class_name
+    }
+
+    /// The class whose rules patch the style of elements in `states`. Declarations
+    /// are `!important` so that they win over inline styles.
+    pub fn state_class_name(&mut self, states: &[(StyleState, PuppetStylePatch)]) -> String {
+        let rules = states
+            .iter()
+            .map(|(state, patch)| {
+                let declarations = patch
+                    .css_properties()
+                    .into_iter()
+                    .map(|(property, value)| (property, format!("{} !important", value)))
+                    .collect::<Declarations>();
+
+                (*state, declarations)
+            })
+            .collect::<Vec<_>>();
+
+        let key = rules
+            .iter()
+            .map(|(state, declarations)| format!("{}{{{}}}", state, to_css_text(declarations)))
+            .collect::<String>();
+        let class_name = format!("puppet-s{:08x}", fnv1a(key.as_bytes()));
+
+        let prefix = format!(".{}:", class_name);
+        if !self
+            .state_rules
+            .iter()
+            .any(|(selector, _)| selector.starts_with(&prefix))
+        {
+            for (state, declarations) in rules {
+                self.state_rules
+                    .push((state.to_html_selector(&class_name), declarations));
+            }
+        }
+
+        class_name
+    }
+
+    /// The class scoping the rules of `scoped_style`, added to the stylesheet once
+    pub fn scope_class_name(&mut self, scoped_style: &PuppetScopedStyle) -> String {
+        let class_name = scoped_style.class_name();
+
+        if !self
+            .scoped_rules
+            .iter()
+            .any(|(scope, _)| *scope == class_name)
+        {
+            self.scoped_rules
+                .push((class_name.clone(), scoped_style.scoped_rules(&class_name)));
+        }
+
+        class_name
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.state_rules.is_empty() && self.scoped_rules.is_empty()
+    }
+
+    /// One rule per class, one declaration per line
+    pub fn to_css(&self) -> String {
+        let mut css = String::default();
+
+        let rules = self
+            .rules
+            .iter()
+            .map(|(class_name, declarations)| (format!(".{}", class_name), declarations))
+            .chain(
+                self.state_rules
+                    .iter()
+                    .map(|(selector, declarations)| (selector.clone(), declarations)),
+            )
+            .chain(
+                self.scoped_rules
+                    .iter()
+                    .flat_map(|(_, rules)| rules)
+                    .map(|(selector, declarations)| (selector.clone(), declarations)),
+            );
+
+        for (selector, declarations) in rules {
+            let _ = writeln!(css, "{} {{", selector);
+            for (property, value) in declarations {
+                let _ = writeln!(css, "  {}: {};", property, value);
+            }
+            css.push_str("}\n");
+        }
+
+        css
+    }
+}
+
+/// `declarations` as written in a `style` attribute, `property: value;` separated by
+/// spaces
+pub(crate) fn to_css_text(declarations: &[(&'static str, String)]) -> String {
+    declarations
+        .iter()
+        .map(|(property, value)| format!("{}: {};", property, value))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// A hash that does not change between runs or compiler versions
+pub(crate) fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193)
+    })
+}
@@ -0,0 +1,79 @@
+use crate::JsValueResult;
+use core::{cmp::Ordering, fmt, fmt::Debug};
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Event, HtmlElement};
+
+/// A DOM event listener attached to a [crate::Component].
+///
+/// Handlers are compared by identity so that the nodes holding them can keep
+/// deriving `PartialEq` and `Ord`.
+#[derive(Clone)]
+pub struct PuppetEventHandler {
+    event: &'static str,
+    callback: Rc<dyn Fn(&Event)>,
+}
+
+impl PuppetEventHandler {
+    pub fn new<F>(event: &'static str, callback: F) -> Self
+    where
+        F: Fn(&Event) + 'static,
+    {
+        Self {
+            event,
+            callback: Rc::new(callback),
+        }
+    }
+
+    pub fn event(&self) -> &'static str {
+        self.event
+    }
+
+    pub fn call(&self, event: &Event) {
+        (self.callback)(event)
+    }
+
+    fn callback_address(&self) -> usize {
+        Rc::as_ptr(&self.callback) as *const () as usize
+    }
+
+    pub fn to_html(&self, target: &HtmlElement) -> JsValueResult<()> {
+        let callback = Rc::clone(&self.callback);
+        let closure =
+            Closure::wrap(Box::new(move |event: Event| callback(&event)) as Box<dyn FnMut(Event)>);
+
+        target.add_event_listener_with_callback(self.event, closure.as_ref().unchecked_ref())?;
+        // The listener lives as long as the element it is attached to
+        closure.forget();
+
+        Ok(())
+    }
+}
+
+impl PartialEq for PuppetEventHandler {
+    fn eq(&self, other: &Self) -> bool {
+        self.event == other.event && self.callback_address() == other.callback_address()
+    }
+}
+
+impl Eq for PuppetEventHandler {}
+
+impl PartialOrd for PuppetEventHandler {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PuppetEventHandler {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.event
+            .cmp(other.event)
+            .then_with(|| self.callback_address().cmp(&other.callback_address()))
+    }
+}
+
+impl Debug for PuppetEventHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PuppetEventHandler({:?})", self.event)
+    }
+}
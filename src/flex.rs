@@ -1,21 +1,46 @@
-use crate::PuppetUnit;
+use crate::{PuppetDirection, PuppetUnit};
 use core::{
     fmt,
     fmt::{Debug, Display},
     format_args,
 };
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
 pub enum FlexDirection {
+    #[default]
     Row,
     RowReverse,
     Column,
     ColumnReverse,
 }
 
-impl Default for FlexDirection {
-    fn default() -> Self {
-        FlexDirection::Row
+impl FlexDirection {
+    /// Swap [FlexDirection::Row] and [FlexDirection::RowReverse], columns are unaffected
+    pub fn mirrored(&self) -> Self {
+        match self {
+            FlexDirection::Row => FlexDirection::RowReverse,
+            FlexDirection::RowReverse => FlexDirection::Row,
+            FlexDirection::Column => FlexDirection::Column,
+            FlexDirection::ColumnReverse => FlexDirection::ColumnReverse,
+        }
+    }
+
+    /// The physical direction of a row, which starts from the right edge in a
+    /// right-to-left locale
+    pub fn for_direction(&self, direction: PuppetDirection) -> Self {
+        match direction {
+            PuppetDirection::Ltr => *self,
+            PuppetDirection::Rtl => self.mirrored(),
+        }
+    }
+
+    pub fn to_html_direction(&self) -> &'static str {
+        match self {
+            FlexDirection::Row => "row",
+            FlexDirection::RowReverse => "row-reverse",
+            FlexDirection::Column => "column",
+            FlexDirection::ColumnReverse => "column-reverse",
+        }
     }
 }
 
@@ -49,16 +74,21 @@ impl Display for FlexDirection {
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
 pub enum FlexWrap {
+    #[default]
     Wrap,
     NoWrap,
     WrapReverse,
 }
 
-impl Default for FlexWrap {
-    fn default() -> Self {
-        FlexWrap::Wrap
+impl FlexWrap {
+    pub fn to_html_wrap(&self) -> &'static str {
+        match self {
+            FlexWrap::Wrap => "wrap",
+            FlexWrap::NoWrap => "nowrap",
+            FlexWrap::WrapReverse => "wrap-reverse",
+        }
     }
 }
 
@@ -90,19 +120,27 @@ impl Display for FlexWrap {
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
 pub enum JustifyContent {
     FlexStart,
     FlexEnd,
+    #[default]
     Center,
     SpaceBetween,
     SpaceAround,
     SpaceEvenly,
 }
 
-impl Default for JustifyContent {
-    fn default() -> Self {
-        JustifyContent::Center
+impl JustifyContent {
+    pub fn to_html_justify(&self) -> &'static str {
+        match self {
+            JustifyContent::FlexStart => "flex-start",
+            JustifyContent::FlexEnd => "flex-end",
+            JustifyContent::Center => "center",
+            JustifyContent::SpaceBetween => "space-between",
+            JustifyContent::SpaceAround => "space-around",
+            JustifyContent::SpaceEvenly => "space-evenly",
+        }
     }
 }
 
@@ -140,19 +178,27 @@ impl Display for JustifyContent {
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
 pub enum AlignSelf {
     Auto,
     FlexStart,
     FlexEnd,
+    #[default]
     Center,
     Baseline,
     Stretch,
 }
 
-impl Default for AlignSelf {
-    fn default() -> Self {
-        AlignSelf::Center
+impl AlignSelf {
+    pub fn to_html_align(&self) -> &'static str {
+        match self {
+            AlignSelf::Auto => "auto",
+            AlignSelf::FlexStart => "flex-start",
+            AlignSelf::FlexEnd => "flex-end",
+            AlignSelf::Center => "center",
+            AlignSelf::Baseline => "baseline",
+            AlignSelf::Stretch => "stretch",
+        }
     }
 }
 
@@ -190,18 +236,25 @@ impl Display for AlignSelf {
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
 pub enum AlignItems {
     FlexStart,
     FlexEnd,
+    #[default]
     Center,
     Baseline,
     Stretch,
 }
 
-impl Default for AlignItems {
-    fn default() -> Self {
-        AlignItems::Center
+impl AlignItems {
+    pub fn to_html_align(&self) -> &'static str {
+        match self {
+            AlignItems::FlexStart => "flex-start",
+            AlignItems::FlexEnd => "flex-end",
+            AlignItems::Center => "center",
+            AlignItems::Baseline => "baseline",
+            AlignItems::Stretch => "stretch",
+        }
     }
 }
 
@@ -237,19 +290,27 @@ impl Display for AlignItems {
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
 pub enum AlignContent {
     FlexStart,
     FlexEnd,
+    #[default]
     Center,
     Stretch,
     SpaceBetween,
     SpaceAround,
 }
 
-impl Default for AlignContent {
-    fn default() -> Self {
-        AlignContent::Center
+impl AlignContent {
+    pub fn to_html_align(&self) -> &'static str {
+        match self {
+            AlignContent::FlexStart => "flex-start",
+            AlignContent::FlexEnd => "flex-end",
+            AlignContent::Center => "center",
+            AlignContent::Stretch => "stretch",
+            AlignContent::SpaceBetween => "space-between",
+            AlignContent::SpaceAround => "space-around",
+        }
     }
 }
 
@@ -293,6 +354,16 @@ pub enum FlexGap {
     Row(PuppetUnit),
 }
 
+impl FlexGap {
+    /// The gap as a `column-gap` or `row-gap` declaration
+    pub fn to_html_gap(&self) -> (&'static str, String) {
+        match self {
+            FlexGap::Column(gap) => ("column-gap", gap.to_html_units()),
+            FlexGap::Row(gap) => ("row-gap", gap.to_html_units()),
+        }
+    }
+}
+
 impl Default for FlexGap {
     fn default() -> Self {
         FlexGap::Row(PuppetUnit::Pixels(2_u16))
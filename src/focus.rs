@@ -0,0 +1,254 @@
+use crate::{
+    bind_text, get_document, JsValueResult, PuppetChild, PuppetColor, PuppetElement, PuppetStyle,
+    PuppetText,
+};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Document, Element, HtmlElement};
+
+/// Text of the link letting keyboard users jump over the navigation to the screen
+pub const SKIP_LINK_LABEL: &str = "Skip to content";
+
+/// Id given to the screen a skip link jumps to when it has none
+pub const SKIP_TARGET_ID: &str = "main-content";
+
+/// Elements the browser puts in the tab order without a `tabindex`
+const NATIVELY_FOCUSABLE: &[&str] = &["button", "input", "select", "textarea"];
+
+/// The outline drawn around the focused element when focus comes from the keyboard
+///
+/// ```
+/// use puppeteer_builders::{PuppetColor, PuppetStyle};
+///
+/// let mut style = PuppetStyle::default();
+/// style.background_color(PuppetColor::Hex("202124"));
+///
+/// let mut ring = style.focus_ring();
+/// ring.width(3);
+/// assert_eq!(ring.to_css(), "outline: 3px solid rgb(255,255,255); outline-offset: 2px;");
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct PuppetFocusRing {
+    color: PuppetColor,
+    /// In pixels
+    width: u8,
+    /// Gap between the element and the outline, in pixels
+    offset: u8,
+}
+
+impl Default for PuppetFocusRing {
+    fn default() -> Self {
+        PuppetStyle::default().focus_ring()
+    }
+}
+
+impl PuppetFocusRing {
+    pub fn new(color: PuppetColor) -> Self {
+        Self {
+            color,
+            width: 2,
+            offset: 2,
+        }
+    }
+
+    pub fn color(&mut self, color: PuppetColor) -> &mut Self {
+        self.color = color;
+
+        self
+    }
+
+    pub fn width(&mut self, width: u8) -> &mut Self {
+        self.width = width;
+
+        self
+    }
+
+    pub fn offset(&mut self, offset: u8) -> &mut Self {
+        self.offset = offset;
+
+        self
+    }
+
+    pub fn to_css(&self) -> String {
+        format!(
+            "outline: {}px solid {}; outline-offset: {}px;",
+            self.width,
+            self.color.to_html_color(),
+            self.offset
+        )
+    }
+
+    /// The rules drawing the ring around keyboard focus, and hiding skip links
+    /// until they are focused
+    pub fn to_stylesheet(&self) -> String {
+        format!(
+            ":focus-visible {{ {} }}\n\
+            [data-skip-link]:not(:focus) {{ position: absolute; width: 1px; height: 1px; \
+            overflow: hidden; clip-path: inset(50%); white-space: nowrap; }}\n",
+            self.to_css()
+        )
+    }
+}
+
+impl PuppetStyle {
+    /// A focus ring standing out from the background of this style
+    pub fn focus_ring(&self) -> PuppetFocusRing {
+        let (r, g, b) = self
+            .background_color
+            .and_then(|color| color.to_rgb())
+            .unwrap_or((255, 255, 255));
+        let luminance = 0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b);
+
+        PuppetFocusRing::new(if luminance > 127.5 {
+            PuppetColor::Rgb(0, 0, 0)
+        } else {
+            PuppetColor::Rgb(255, 255, 255)
+        })
+    }
+}
+
+impl PuppetElement {
+    /// Whether the browser lets users tab to this element
+    pub fn is_focusable(&self) -> bool {
+        if self.get_attribute("hidden").is_some() || self.get_attribute("disabled").is_some() {
+            return false;
+        }
+
+        match self.tab_index() {
+            Some(tab_index) => tab_index >= 0,
+            None => {
+                NATIVELY_FOCUSABLE.contains(&self.tag())
+                    || (self.tag() == "a" && self.get_attribute("href").is_some())
+            }
+        }
+    }
+
+    pub fn tab_index(&self) -> Option<i32> {
+        self.get_attribute("tabindex")
+            .and_then(|tab_index| tab_index.resolve().trim().parse().ok())
+    }
+
+    /// The elements of this tree in the order the Tab key moves through them:
+    /// positive `tabindex` first, lowest first, then the others as written
+    pub fn focus_order(&self) -> Vec<&PuppetElement> {
+        let mut focusable = Vec::default();
+        collect_focusable(self, &mut focusable);
+
+        // A stable sort keeps the document order among equal indexes
+        focusable.sort_by_key(|element| match element.tab_index() {
+            Some(tab_index) if tab_index > 0 => tab_index,
+            _ => i32::MAX,
+        });
+
+        focusable
+    }
+}
+
+fn collect_focusable<'e>(element: &'e PuppetElement, focusable: &mut Vec<&'e PuppetElement>) {
+    if element.get_attribute("hidden").is_some() {
+        return;
+    }
+
+    if element.is_focusable() {
+        focusable.push(element);
+    }
+
+    for child in element.children() {
+        if let PuppetChild::Element(child) = child {
+            collect_focusable(child, focusable);
+        }
+    }
+}
+
+/// Move the focus to the element with the id `id`, making it focusable from code
+/// when it is not already. Returns whether the element was found.
+pub fn focus_id(id: &str) -> JsValueResult<bool> {
+    match get_document().get_element_by_id(id) {
+        Some(element) => {
+            focus_element(&element)?;
+
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+pub(crate) fn focus_element(element: &Element) -> JsValueResult<()> {
+    let focusable = element.has_attribute("tabindex")
+        || NATIVELY_FOCUSABLE.contains(&element.tag_name().to_lowercase().as_str())
+        || (element.tag_name().eq_ignore_ascii_case("a") && element.has_attribute("href"));
+
+    if !focusable {
+        element.set_attribute("tabindex", "-1")?;
+    }
+
+    element
+        .dyn_ref::<HtmlElement>()
+        .ok_or(JsValue::from_str(
+            "Could not convert `Element` to `HtmlElement`",
+        ))?
+        .focus()
+}
+
+/// Draw `ring` around keyboard focus in the whole document, replacing the ring
+/// installed before
+pub fn install_focus_ring(document: &Document, ring: &PuppetFocusRing) -> JsValueResult<()> {
+    let style = match document.query_selector("style[data-puppet-focus]")? {
+        Some(style) => style,
+        None => {
+            let style = document.create_element("style")?;
+            style.set_attribute("data-puppet-focus", "")?;
+
+            match document.query_selector("head")? {
+                Some(head) => head.append_child(&style)?,
+                None => document
+                    .document_element()
+                    .ok_or(JsValue::from_str("The document has no root element"))?
+                    .append_child(&style)?,
+            };
+
+            style
+        }
+    };
+
+    style.set_text_content(Some(&ring.to_stylesheet()));
+
+    Ok(())
+}
+
+/// Put a skip link to the rendered screen first in `root`, giving the screen an id
+/// when it has none
+pub(crate) fn mount_skip_link(
+    document: &Document,
+    root: &Element,
+    label: &PuppetText,
+) -> JsValueResult<()> {
+    let Some(screen) = root.first_element_child() else {
+        return Ok(());
+    };
+
+    if screen.id().is_empty() {
+        screen.set_id(SKIP_TARGET_ID);
+    }
+    if !screen.has_attribute("tabindex") {
+        screen.set_attribute("tabindex", "-1")?;
+    }
+
+    let link = document.create_element("a")?;
+    link.set_attribute("href", &format!("#{}", screen.id()))?;
+    link.set_attribute("data-skip-link", "")?;
+    link.set_text_content(Some(&label.resolve()));
+    bind_text(&link, None, label);
+    root.insert_before(&link, Some(&screen))?;
+
+    Ok(())
+}
+
+/// The screen rendered into `root`, after its skip link
+pub(crate) fn screen_root(root: &Element) -> Option<Element> {
+    let first = root.first_element_child()?;
+
+    match first.has_attribute("data-skip-link") {
+        true => first.next_element_sibling(),
+        false => Some(first),
+    }
+}
@@ -0,0 +1,837 @@
+use crate::{
+    bind_text, install_scoped_rules, install_state_rules, is_safe_attribute, prune_text_bindings,
+    JsValueResult, NodeBackend, PuppetChild, PuppetColumn, PuppetElement, PuppetNode,
+    PuppetStylesheet, PuppetText, TEXT_TAG,
+};
+use core::fmt::{self, Display};
+use log::trace;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Document, Element, HtmlElement, Node, Text};
+
+/// A difference between server-rendered markup and the tree hydrating it.
+///
+/// Every mismatch is repaired in place so that the page still matches the tree, but
+/// it usually means that the server and the client rendered different data.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PuppetMismatch {
+    /// A node of another kind or tag, replaced by a freshly rendered one
+    Node {
+        path: String,
+        expected: String,
+        found: String,
+    },
+    Attribute {
+        path: String,
+        name: &'static str,
+        expected: String,
+        found: Option<String>,
+    },
+    /// A `data-{key}` attribute
+    Data {
+        path: String,
+        key: String,
+        expected: String,
+        found: Option<String>,
+    },
+    Style {
+        path: String,
+        expected: String,
+        found: Option<String>,
+    },
+    Text {
+        path: String,
+        expected: String,
+        found: String,
+    },
+    /// The content of an element given as [PuppetElement::raw_html]
+    RawHtml {
+        path: String,
+        expected: String,
+        found: String,
+    },
+    /// An attribute the tree does not have, removed
+    ExtraAttribute {
+        path: String,
+        name: String,
+        found: String,
+    },
+    /// A node missing from the markup, rendered and inserted
+    Missing { path: String, expected: String },
+    /// A node the tree does not have, removed
+    Extra { path: String, found: String },
+}
+
+impl PuppetMismatch {
+    /// Where the mismatch is, as tags and child indexes from the hydrated parent such
+    /// as `div[0] > p[1]`
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Node { path, .. }
+            | Self::Attribute { path, .. }
+            | Self::Data { path, .. }
+            | Self::Style { path, .. }
+            | Self::Text { path, .. }
+            | Self::RawHtml { path, .. }
+            | Self::ExtraAttribute { path, .. }
+            | Self::Missing { path, .. }
+            | Self::Extra { path, .. } => path,
+        }
+    }
+}
+
+impl Display for PuppetMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Node {
+                path,
+                expected,
+                found,
+            } => write!(f, "{}: expected {}, found {}", path, expected, found),
+            Self::Attribute {
+                path,
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: expected {}=\"{}\", found {:?}",
+                path, name, expected, found
+            ),
+            Self::Data {
+                path,
+                key,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: expected data-{}=\"{}\", found {:?}",
+                path, key, expected, found
+            ),
+            Self::Style {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: expected style \"{}\", found {:?}",
+                path, expected, found
+            ),
+            Self::Text {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: expected text {:?}, found {:?}",
+                path, expected, found
+            ),
+            Self::RawHtml {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}: expected markup {:?}, found {:?}",
+                path, expected, found
+            ),
+            Self::ExtraAttribute { path, name, found } => {
+                write!(f, "{}: unexpected {}=\"{}\"", path, name, found)
+            }
+            Self::Missing { path, expected } => write!(f, "{}: missing {}", path, expected),
+            Self::Extra { path, found } => write!(f, "{}: unexpected {}", path, found),
+        }
+    }
+}
+
+impl PuppetElement {
+    /// Take over the markup [PuppetElement::to_html_string] or
+    /// [PuppetElement::to_html_string_with_stylesheet] rendered into `parent_node`
+    /// instead of building it again: event handlers and translated text are attached
+    /// to the existing nodes.
+    ///
+    /// Whitespace-only text and comments between elements are ignored, and text the
+    /// parser merged is split again into the text children of the tree. Anything else
+    /// that differs from the tree, attributes it does not have included, is repaired
+    /// and returned.
+    pub fn hydrate(
+        &self,
+        document: &Document,
+        parent_node: &Element,
+    ) -> JsValueResult<Vec<PuppetMismatch>> {
+        let mut hydration = Hydration {
+            backend: DomBackend { document },
+            mismatches: Vec::default(),
+        };
+
+        hydration.children(
+            parent_node,
+            core::slice::from_ref(&PuppetChild::Element(self.clone())),
+            "",
+        )?;
+
+        for mismatch in &hydration.mismatches {
+            trace!("HYDRATION MISMATCH {}", mismatch);
+        }
+
+        if parent_node.is_connected() {
+            prune_text_bindings();
+        }
+
+        Ok(hydration.mismatches)
+    }
+
+    /// Check and repair markup held in memory like [PuppetElement::hydrate] does with
+    /// the DOM, such as the output of a server
+    pub fn hydrate_node(&self, parent_node: &PuppetNode) -> Vec<PuppetMismatch> {
+        let mut hydration = Hydration {
+            backend: NodeBackend,
+            mismatches: Vec::default(),
+        };
+
+        // Nodes held in memory never fail to be updated
+        if let Err(error) = hydration.children(
+            parent_node,
+            core::slice::from_ref(&PuppetChild::Element(self.clone())),
+            "",
+        ) {
+            trace!("{:?}", error);
+        }
+
+        hydration.mismatches
+    }
+}
+
+impl PuppetColumn {
+    pub fn hydrate(
+        &self,
+        document: &Document,
+        app_node: &Element,
+    ) -> JsValueResult<Vec<PuppetMismatch>> {
+        self.to_element().hydrate(document, app_node)
+    }
+
+    pub fn hydrate_node(&self, app_node: &PuppetNode) -> Vec<PuppetMismatch> {
+        self.to_element().hydrate_node(app_node)
+    }
+}
+
+/// What hydration tells apart in a node
+pub(crate) enum NodeKind {
+    /// An element and its tag in lower case
+    Element(String),
+    Text(String),
+    Comment,
+    /// Any other node and its text
+    Other(String),
+}
+
+/// The nodes hydration takes over, so that the same matching runs against the DOM
+/// and against [PuppetNode]s
+pub(crate) trait HydrationBackend {
+    type Node: Clone;
+
+    fn kind(&self, node: &Self::Node) -> NodeKind;
+
+    fn children(&self, parent: &Self::Node) -> Vec<Self::Node>;
+
+    fn attribute(&self, element: &Self::Node, name: &str) -> Option<String>;
+
+    fn attribute_names(&self, element: &Self::Node) -> Vec<String>;
+
+    fn set_attribute(&mut self, element: &Self::Node, name: &str, value: &str)
+        -> JsValueResult<()>;
+
+    fn remove_attribute(&mut self, element: &Self::Node, name: &str) -> JsValueResult<()>;
+
+    fn set_text(&mut self, node: &Self::Node, text: &str);
+
+    /// Keep `head` in the text `node` and move the rest of its text into a new node
+    /// right after it
+    fn split_text(
+        &mut self,
+        parent: &Self::Node,
+        node: &Self::Node,
+        head: &str,
+    ) -> JsValueResult<Self::Node>;
+
+    /// Render `child` before `before`, or at the end of `parent`
+    fn insert(
+        &mut self,
+        parent: &Self::Node,
+        child: &PuppetChild,
+        before: Option<&Self::Node>,
+    ) -> JsValueResult<()>;
+
+    fn remove(&mut self, parent: &Self::Node, node: &Self::Node) -> JsValueResult<()>;
+
+    /// The classes of the states and scope of `expected`, with their rules installed
+    fn rule_classes(&mut self, expected: &PuppetElement) -> JsValueResult<Vec<String>>;
+
+    /// The markup `expected` gives once written into `element`, and the markup found
+    /// in `element`
+    fn raw_html(&mut self, element: &Self::Node, expected: &str)
+        -> JsValueResult<(String, String)>;
+
+    fn set_raw_html(&mut self, element: &Self::Node, raw_html: &str);
+
+    /// Keep `node` in sync with the locale, see [PuppetText]
+    fn bind_text(
+        &mut self,
+        _node: &Self::Node,
+        _attribute: Option<&'static str>,
+        _text: &PuppetText,
+    ) {
+    }
+
+    fn attach_handlers(
+        &mut self,
+        _element: &Self::Node,
+        _expected: &PuppetElement,
+    ) -> JsValueResult<()> {
+        Ok(())
+    }
+}
+
+struct DomBackend<'d> {
+    document: &'d Document,
+}
+
+fn as_element(node: &Node) -> JsValueResult<&Element> {
+    node.dyn_ref::<Element>()
+        .ok_or(JsValue::from_str("Could not convert `Node` to `Element`"))
+}
+
+impl HydrationBackend for DomBackend<'_> {
+    type Node = Node;
+
+    fn kind(&self, node: &Node) -> NodeKind {
+        match node.node_type() {
+            Node::ELEMENT_NODE => NodeKind::Element(
+                node.dyn_ref::<Element>()
+                    .map(|element| element.tag_name().to_lowercase())
+                    .unwrap_or_default(),
+            ),
+            Node::TEXT_NODE => NodeKind::Text(node.text_content().unwrap_or_default()),
+            Node::COMMENT_NODE => NodeKind::Comment,
+            _ => NodeKind::Other(node.text_content().unwrap_or_default()),
+        }
+    }
+
+    fn children(&self, parent: &Node) -> Vec<Node> {
+        let nodes = parent.child_nodes();
+
+        (0..nodes.length())
+            .filter_map(|index| nodes.get(index))
+            .collect()
+    }
+
+    fn attribute(&self, element: &Node, name: &str) -> Option<String> {
+        element.dyn_ref::<Element>()?.get_attribute(name)
+    }
+
+    fn attribute_names(&self, element: &Node) -> Vec<String> {
+        let Some(attributes) = element.dyn_ref::<Element>().map(Element::attributes) else {
+            return Vec::default();
+        };
+
+        (0..attributes.length())
+            .filter_map(|index| attributes.item(index))
+            .map(|attribute| attribute.name())
+            .collect()
+    }
+
+    fn set_attribute(&mut self, element: &Node, name: &str, value: &str) -> JsValueResult<()> {
+        as_element(element)?.set_attribute(name, value)
+    }
+
+    fn remove_attribute(&mut self, element: &Node, name: &str) -> JsValueResult<()> {
+        as_element(element)?.remove_attribute(name)
+    }
+
+    fn set_text(&mut self, node: &Node, text: &str) {
+        node.set_text_content(Some(text));
+    }
+
+    fn split_text(&mut self, _parent: &Node, node: &Node, head: &str) -> JsValueResult<Node> {
+        let text = node
+            .dyn_ref::<Text>()
+            .ok_or(JsValue::from_str("Could not convert `Node` to `Text`"))?;
+
+        // Offsets of the DOM count UTF-16 code units
+        Ok(text.split_text(head.encode_utf16().count() as u32)?.into())
+    }
+
+    fn insert(
+        &mut self,
+        parent: &Node,
+        child: &PuppetChild,
+        before: Option<&Node>,
+    ) -> JsValueResult<()> {
+        let container = self.document.create_element("div")?;
+
+        match child {
+            PuppetChild::Element(element) => element.to_html(self.document, &container)?,
+            PuppetChild::Text(text) => {
+                let text_node = self.document.create_text_node(&text.resolve());
+                bind_text(&text_node, None, text);
+                container.append_with_node_1(&text_node)?;
+            }
+        }
+
+        if let Some(rendered) = container.first_child() {
+            parent.insert_before(&rendered, before)?;
+        }
+
+        Ok(())
+    }
+
+    fn remove(&mut self, parent: &Node, node: &Node) -> JsValueResult<()> {
+        parent.remove_child(node)?;
+
+        Ok(())
+    }
+
+    fn rule_classes(&mut self, expected: &PuppetElement) -> JsValueResult<Vec<String>> {
+        let state_class = match expected.states().is_empty() {
+            true => None,
+            false => Some(install_state_rules(expected.states())?),
+        };
+        let scope_class = match expected.get_scoped_style() {
+            Some(scoped_style) => Some(install_scoped_rules(scoped_style)?),
+            None => None,
+        };
+
+        Ok([state_class, scope_class].into_iter().flatten().collect())
+    }
+
+    /// Markup is compared once parsed, as browsers serialize it their own way
+    fn raw_html(&mut self, element: &Node, expected: &str) -> JsValueResult<(String, String)> {
+        let parsed = self.document.create_element("div")?;
+        parsed.set_inner_html(expected);
+
+        Ok((parsed.inner_html(), as_element(element)?.inner_html()))
+    }
+
+    fn set_raw_html(&mut self, element: &Node, raw_html: &str) {
+        if let Some(element) = element.dyn_ref::<Element>() {
+            element.set_inner_html(raw_html);
+        }
+    }
+
+    fn bind_text(&mut self, node: &Node, attribute: Option<&'static str>, text: &PuppetText) {
+        bind_text(node, attribute, text);
+    }
+
+    fn attach_handlers(&mut self, element: &Node, expected: &PuppetElement) -> JsValueResult<()> {
+        let html_element = element.dyn_ref::<HtmlElement>().ok_or(JsValue::from_str(
+            "Could not convert `Element` to `HtmlElement`",
+        ))?;
+
+        for handler in expected.handlers() {
+            handler.to_html(html_element)?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Hydration<B> {
+    backend: B,
+    mismatches: Vec<PuppetMismatch>,
+}
+
+impl<B: HydrationBackend> Hydration<B> {
+    fn element(
+        &mut self,
+        expected: &PuppetElement,
+        found: &B::Node,
+        path: &str,
+    ) -> JsValueResult<()> {
+        for (name, value) in expected.attributes() {
+            let expected_value = value.resolve();
+            let found_value = self.backend.attribute(found, name);
+
+            // Unsafe URLs are written nowhere
+            if !is_safe_attribute(name, &expected_value) {
+                if found_value.is_some() {
+                    self.backend.remove_attribute(found, name)?;
+                }
+                continue;
+            }
+
+            // Classes of the style, states and scope follow those of the attribute
+            let matches = match *name {
+                "class" => found_value.as_deref().is_some_and(|classes| {
+                    expected_value
+                        .split_whitespace()
+                        .all(|class_name| has_class(classes, class_name))
+                }),
+                _ => found_value.as_deref() == Some(&expected_value),
+            };
+
+            if !matches {
+                self.mismatches.push(PuppetMismatch::Attribute {
+                    path: path.into(),
+                    name,
+                    expected: expected_value.clone().into_owned(),
+                    found: found_value,
+                });
+                self.backend.set_attribute(found, name, &expected_value)?;
+            }
+
+            self.backend.bind_text(found, Some(name), value);
+        }
+
+        for (key, value) in expected.dataset() {
+            let name = format!("data-{}", key);
+            let found_value = self.backend.attribute(found, &name);
+
+            if found_value.as_deref() != Some(value) {
+                self.mismatches.push(PuppetMismatch::Data {
+                    path: path.into(),
+                    key: key.clone().into_owned(),
+                    expected: value.clone().into_owned(),
+                    found: found_value,
+                });
+                self.backend.set_attribute(found, &name, value)?;
+            }
+        }
+
+        // Markup written with a stylesheet has the class of the style instead
+        let classes = self.backend.attribute(found, "class").unwrap_or_default();
+        let inline_style = expected
+            .style()
+            .filter(|style| !has_class(&classes, &PuppetStylesheet::new().class_name(style)));
+
+        if let Some(style) = inline_style {
+            let expected_style = style.to_css();
+            let found_style = self.backend.attribute(found, "style");
+
+            if found_style.as_deref() != Some(&expected_style) {
+                self.mismatches.push(PuppetMismatch::Style {
+                    path: path.into(),
+                    expected: expected_style.clone(),
+                    found: found_style,
+                });
+                self.backend
+                    .set_attribute(found, "style", &expected_style)?;
+            }
+        }
+
+        // Markup written without a stylesheet has the classes of the states and scope
+        // but not their rules
+        let rule_classes = self.backend.rule_classes(expected)?;
+
+        for class_name in &rule_classes {
+            let classes = self.backend.attribute(found, "class");
+
+            if !classes
+                .as_deref()
+                .is_some_and(|classes| has_class(classes, class_name))
+            {
+                self.mismatches.push(PuppetMismatch::Attribute {
+                    path: path.into(),
+                    name: "class",
+                    expected: class_name.clone(),
+                    found: classes.clone(),
+                });
+
+                let classes = match classes.filter(|classes| !classes.is_empty()) {
+                    Some(classes) => format!("{} {}", classes, class_name),
+                    None => class_name.clone(),
+                };
+                self.backend.set_attribute(found, "class", &classes)?;
+            }
+        }
+
+        let has_classes = expected.get_attribute("class").is_some()
+            || (expected.style().is_some() && inline_style.is_none())
+            || !rule_classes.is_empty();
+
+        for name in self.backend.attribute_names(found) {
+            let expected_name = match name.as_str() {
+                "class" => has_classes,
+                "style" => inline_style.is_some(),
+                name => {
+                    expected.get_attribute(name).is_some()
+                        || name
+                            .strip_prefix("data-")
+                            .is_some_and(|key| expected.get_data(key).is_some())
+                }
+            };
+
+            if !expected_name {
+                let found_value = self.backend.attribute(found, &name).unwrap_or_default();
+                self.backend.remove_attribute(found, &name)?;
+                self.mismatches.push(PuppetMismatch::ExtraAttribute {
+                    path: path.into(),
+                    name,
+                    found: found_value,
+                });
+            }
+        }
+
+        self.backend.attach_handlers(found, expected)?;
+
+        match expected.get_raw_html() {
+            _ if expected.is_void() => (),
+            Some(raw_html) => self.raw_html(raw_html, found, path)?,
+            None => self.children(found, expected.children(), path)?,
+        }
+
+        Ok(())
+    }
+
+    fn children(
+        &mut self,
+        parent: &B::Node,
+        expected: &[PuppetChild],
+        parent_path: &str,
+    ) -> JsValueResult<()> {
+        let found = self.backend.children(parent);
+        let mut next = 0;
+        let mut index = 0;
+
+        while index < expected.len() {
+            let child = &expected[index];
+
+            let element = match child {
+                PuppetChild::Element(element) => element,
+                PuppetChild::Text(_) => {
+                    // The parser merges adjacent text into a single node
+                    let run = expected[index..]
+                        .iter()
+                        .take_while(|child| matches!(child, PuppetChild::Text(_)))
+                        .count();
+                    self.texts(
+                        parent,
+                        &expected[index..index + run],
+                        index,
+                        parent_path,
+                        &found,
+                        &mut next,
+                    )?;
+                    index += run;
+                    continue;
+                }
+            };
+            let path = child_path(parent_path, child, index);
+            index += 1;
+
+            while found
+                .get(next)
+                .is_some_and(|node| is_ignorable(&self.backend.kind(node)))
+            {
+                next += 1;
+            }
+
+            let Some(current) = found.get(next) else {
+                self.mismatches.push(PuppetMismatch::Missing {
+                    path,
+                    expected: describe_child(child),
+                });
+                self.backend.insert(parent, child, None)?;
+                continue;
+            };
+            next += 1;
+
+            match self.backend.kind(current) {
+                NodeKind::Element(tag) if tag.eq_ignore_ascii_case(element.tag()) => {
+                    self.element(element, current, &path)?;
+                }
+                kind => {
+                    self.mismatches.push(PuppetMismatch::Node {
+                        path,
+                        expected: describe_child(child),
+                        found: describe_node(&kind),
+                    });
+                    self.backend.insert(parent, child, Some(current))?;
+                    self.backend.remove(parent, current)?;
+                }
+            }
+        }
+
+        for current in &found[next..] {
+            let kind = self.backend.kind(current);
+
+            if !is_ignorable(&kind) {
+                let found = describe_node(&kind);
+                self.mismatches.push(PuppetMismatch::Extra {
+                    path: match parent_path {
+                        "" => found.clone(),
+                        parent_path => parent_path.into(),
+                    },
+                    found,
+                });
+                self.backend.remove(parent, current)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Hydrate the adjacent `texts` of the tree, starting at child `first`, with the
+    /// text nodes found next, splitting them again where the tree has separate text
+    fn texts(
+        &mut self,
+        parent: &B::Node,
+        texts: &[PuppetChild],
+        first: usize,
+        parent_path: &str,
+        found: &[B::Node],
+        next: &mut usize,
+    ) -> JsValueResult<()> {
+        let texts = texts
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, child)| match child {
+                PuppetChild::Text(text) => Some((first + offset, child, text, text.resolve())),
+                PuppetChild::Element(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let expected_text = texts
+            .iter()
+            .map(|(.., resolved)| resolved.as_ref())
+            .collect::<String>();
+
+        // Comments are left where they are, before the text
+        while found
+            .get(*next)
+            .is_some_and(|node| matches!(self.backend.kind(node), NodeKind::Comment))
+        {
+            *next += 1;
+        }
+
+        let start = *next;
+        while let Some(NodeKind::Text(_)) = found.get(*next).map(|node| self.backend.kind(node)) {
+            *next += 1;
+        }
+        // Empty text is not written into markup at all
+        if expected_text.is_empty() {
+            *next = start;
+        }
+        let nodes = &found[start..*next];
+        let after = found.get(*next);
+
+        // Text missing from the markup is inserted where it belongs
+        let Some(node) = nodes.first() else {
+            for (index, child, _, resolved) in &texts {
+                if !resolved.is_empty() {
+                    self.mismatches.push(PuppetMismatch::Missing {
+                        path: child_path(parent_path, child, *index),
+                        expected: describe_child(child),
+                    });
+                }
+                self.backend.insert(parent, child, after)?;
+            }
+            return Ok(());
+        };
+
+        let mut found_text = String::default();
+        for (position, text_node) in nodes.iter().enumerate() {
+            if let NodeKind::Text(text) = self.backend.kind(text_node) {
+                found_text.push_str(&text);
+            }
+            if position > 0 {
+                self.backend.remove(parent, text_node)?;
+            }
+        }
+
+        if found_text != expected_text {
+            let (index, child, ..) = texts
+                .iter()
+                .find(|(.., resolved)| !resolved.is_empty())
+                .unwrap_or(&texts[0]);
+            self.mismatches.push(PuppetMismatch::Text {
+                path: child_path(parent_path, child, *index),
+                expected: expected_text.clone(),
+                found: found_text,
+            });
+        }
+        self.backend.set_text(node, &expected_text);
+
+        let last = texts
+            .iter()
+            .rposition(|(.., resolved)| !resolved.is_empty())
+            .unwrap_or_default();
+        let mut node = node.clone();
+        for (position, (_, child, text, resolved)) in texts.iter().enumerate() {
+            if resolved.is_empty() {
+                let before = match position < last {
+                    true => Some(&node),
+                    false => after,
+                };
+                self.backend.insert(parent, child, before)?;
+                continue;
+            }
+
+            let rest = match position < last {
+                true => Some(self.backend.split_text(parent, &node, resolved)?),
+                false => None,
+            };
+            self.backend.bind_text(&node, None, text);
+
+            if let Some(rest) = rest {
+                node = rest;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn raw_html(&mut self, expected: &str, found: &B::Node, path: &str) -> JsValueResult<()> {
+        let (expected_html, found_html) = self.backend.raw_html(found, expected)?;
+
+        if found_html != expected_html {
+            self.mismatches.push(PuppetMismatch::RawHtml {
+                path: path.into(),
+                expected: expected_html,
+                found: found_html,
+            });
+            self.backend.set_raw_html(found, expected);
+        }
+
+        Ok(())
+    }
+}
+
+fn has_class(classes: &str, class_name: &str) -> bool {
+    classes.split_whitespace().any(|class| class == class_name)
+}
+
+/// Whitespace between tags and comments, which never come from a tree
+fn is_ignorable(kind: &NodeKind) -> bool {
+    match kind {
+        NodeKind::Comment => true,
+        NodeKind::Text(text) => text.trim().is_empty(),
+        _ => false,
+    }
+}
+
+fn child_path(parent_path: &str, child: &PuppetChild, index: usize) -> String {
+    let name = match child {
+        PuppetChild::Element(element) => element.tag(),
+        PuppetChild::Text(_) => TEXT_TAG,
+    };
+
+    if parent_path.is_empty() {
+        format!("{}[{}]", name, index)
+    } else {
+        format!("{} > {}[{}]", parent_path, name, index)
+    }
+}
+
+fn describe_child(child: &PuppetChild) -> String {
+    match child {
+        PuppetChild::Element(element) => format!("<{}>", element.tag()),
+        PuppetChild::Text(text) => format!("text {:?}", text.resolve()),
+    }
+}
+
+fn describe_node(kind: &NodeKind) -> String {
+    match kind {
+        NodeKind::Element(tag) => format!("<{}>", tag),
+        NodeKind::Comment => "comment".into(),
+        NodeKind::Text(text) | NodeKind::Other(text) => format!("text {:?}", text),
+    }
+}
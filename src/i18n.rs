@@ -0,0 +1,500 @@
+use crate::{is_safe_attribute, JsValueResult};
+use core::{
+    cell::RefCell,
+    fmt,
+    fmt::{Debug, Display},
+};
+use log::trace;
+use std::{borrow::Cow, collections::HashMap};
+use wasm_bindgen::JsCast;
+use web_sys::{Element, Node};
+
+thread_local! {
+    static I18N: RefCell<PuppetI18n> = RefCell::default();
+}
+
+/// The text of a [crate::Component], either shown as is or looked up in the current locale
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub enum PuppetText {
+    Raw(Cow<'static, str>),
+    Message(PuppetMessage),
+}
+
+impl PuppetText {
+    /// The text as it should be shown in the current locale
+    pub fn resolve(&self) -> Cow<'_, str> {
+        match self {
+            Self::Raw(text) => Cow::Borrowed(text),
+            Self::Message(message) => Cow::Owned(message.resolve()),
+        }
+    }
+
+    pub fn is_message(&self) -> bool {
+        matches!(self, Self::Message(_))
+    }
+}
+
+impl Default for PuppetText {
+    fn default() -> Self {
+        Self::Raw(Cow::Borrowed(""))
+    }
+}
+
+impl From<&'static str> for PuppetText {
+    fn from(text: &'static str) -> Self {
+        Self::Raw(Cow::Borrowed(text))
+    }
+}
+
+impl From<String> for PuppetText {
+    fn from(text: String) -> Self {
+        Self::Raw(Cow::Owned(text))
+    }
+}
+
+impl From<Cow<'static, str>> for PuppetText {
+    fn from(text: Cow<'static, str>) -> Self {
+        Self::Raw(text)
+    }
+}
+
+impl From<PuppetMessage> for PuppetText {
+    fn from(message: PuppetMessage) -> Self {
+        Self::Message(message)
+    }
+}
+
+impl Display for PuppetText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.resolve())
+    }
+}
+
+/// A message key with the values interpolated into its `{placeholders}`
+///
+/// ```
+/// use puppeteer_builders::{register_locale, PluralCategory, PuppetLocale, PuppetMessage};
+///
+/// let mut english = PuppetLocale::new("en");
+/// english.message("greeting", "Hello, {name}!");
+/// english.plural(
+///     "inbox",
+///     &[
+///         (PluralCategory::One, "You have one message"),
+///         (PluralCategory::Other, "You have {count} messages"),
+///     ],
+/// );
+/// register_locale(english);
+///
+/// assert_eq!(PuppetMessage::new("greeting").arg("name", "Ada").resolve(), "Hello, Ada!");
+/// assert_eq!(PuppetMessage::new("inbox").count(1).resolve(), "You have one message");
+/// assert_eq!(PuppetMessage::new("inbox").count(3).resolve(), "You have 3 messages");
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetMessage {
+    key: &'static str,
+    count: Option<i64>,
+    args: Vec<(&'static str, Cow<'static, str>)>,
+}
+
+impl PuppetMessage {
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            count: Option::default(),
+            args: Vec::default(),
+        }
+    }
+
+    /// Select the plural form and fill the `{count}` placeholder
+    pub fn count(mut self, count: i64) -> Self {
+        self.count = Some(count);
+
+        self
+    }
+
+    pub fn arg<T>(mut self, name: &'static str, value: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.args.push((name, value.into()));
+
+        self
+    }
+
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Look the message up in the current locale, then in the fallback locale.
+    /// The key itself is returned when neither knows the message.
+    pub fn resolve(&self) -> String {
+        I18N.with(|i18n| {
+            let i18n = i18n.borrow();
+
+            let template = [i18n.current(), i18n.fallback()]
+                .into_iter()
+                .flatten()
+                .find_map(|locale| locale.template(self.key, self.count));
+
+            match template {
+                Some(template) => self.interpolate(template),
+                None => {
+                    trace!("MISSING MESSAGE: {}", self.key);
+                    self.key.into()
+                }
+            }
+        })
+    }
+
+    fn interpolate(&self, template: &str) -> String {
+        let mut resolved = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            resolved.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let end = match rest.find('}') {
+                Some(end) => end,
+                None => break,
+            };
+            let name = &rest[1..end];
+
+            match (name, self.count) {
+                ("count", Some(count)) => resolved.push_str(&count.to_string()),
+                _ => match self.args.iter().find(|(arg, _)| *arg == name) {
+                    Some((_, value)) => resolved.push_str(value),
+                    None => resolved.push_str(&rest[..=end]),
+                },
+            }
+
+            rest = &rest[end + 1..];
+        }
+        resolved.push_str(rest);
+
+        resolved
+    }
+}
+
+/// Plural forms as named by the Unicode CLDR
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// Writing direction of a locale
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum PuppetDirection {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+impl PuppetDirection {
+    pub fn to_html_dir(&self) -> &'static str {
+        match self {
+            Self::Ltr => "ltr",
+            Self::Rtl => "rtl",
+        }
+    }
+}
+
+/// A bundle of messages for one language tag such as `en` or `ar-EG`
+#[derive(Clone)]
+pub struct PuppetLocale {
+    tag: &'static str,
+    direction: PuppetDirection,
+    plural_rule: fn(i64) -> PluralCategory,
+    messages: HashMap<Cow<'static, str>, Vec<(PluralCategory, Cow<'static, str>)>>,
+}
+
+impl PuppetLocale {
+    /// The direction and plural rule are picked from the language of `tag`
+    pub fn new(tag: &'static str) -> Self {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+
+        let direction = match language {
+            "ar" | "dv" | "fa" | "he" | "ps" | "ur" | "yi" => PuppetDirection::Rtl,
+            _ => PuppetDirection::Ltr,
+        };
+
+        let plural_rule: fn(i64) -> PluralCategory = match language {
+            "ja" | "ko" | "th" | "vi" | "zh" => |_| PluralCategory::Other,
+            "fr" | "pt" => |count| match count.abs() {
+                0 | 1 => PluralCategory::One,
+                _ => PluralCategory::Other,
+            },
+            "ar" => |count| match count.abs() {
+                0 => PluralCategory::Zero,
+                1 => PluralCategory::One,
+                2 => PluralCategory::Two,
+                count if (3..=10).contains(&(count % 100)) => PluralCategory::Few,
+                count if (11..=99).contains(&(count % 100)) => PluralCategory::Many,
+                _ => PluralCategory::Other,
+            },
+            "pl" => |count| {
+                let count = count.abs();
+                match (count, count % 10, count % 100) {
+                    (1, _, _) => PluralCategory::One,
+                    (_, 2..=4, tens) if !(12..=14).contains(&tens) => PluralCategory::Few,
+                    _ => PluralCategory::Many,
+                }
+            },
+            "ru" | "uk" => |count| {
+                let count = count.abs();
+                match (count % 10, count % 100) {
+                    (1, tens) if tens != 11 => PluralCategory::One,
+                    (2..=4, tens) if !(12..=14).contains(&tens) => PluralCategory::Few,
+                    _ => PluralCategory::Many,
+                }
+            },
+            _ => |count| match count.abs() {
+                1 => PluralCategory::One,
+                _ => PluralCategory::Other,
+            },
+        };
+
+        Self {
+            tag,
+            direction,
+            plural_rule,
+            messages: HashMap::default(),
+        }
+    }
+
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    pub fn direction(&mut self, direction: PuppetDirection) -> &mut Self {
+        self.direction = direction;
+
+        self
+    }
+
+    pub fn text_direction(&self) -> PuppetDirection {
+        self.direction
+    }
+
+    pub fn plural_rule(&mut self, plural_rule: fn(i64) -> PluralCategory) -> &mut Self {
+        self.plural_rule = plural_rule;
+
+        self
+    }
+
+    pub fn message<K, T>(&mut self, key: K, template: T) -> &mut Self
+    where
+        K: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
+        self.messages
+            .insert(key.into(), vec![(PluralCategory::Other, template.into())]);
+
+        self
+    }
+
+    /// A message with one template per plural form, [PluralCategory::Other] is used
+    /// for any form that is missing
+    pub fn plural<K>(&mut self, key: K, forms: &[(PluralCategory, &'static str)]) -> &mut Self
+    where
+        K: Into<Cow<'static, str>>,
+    {
+        self.messages.insert(
+            key.into(),
+            forms
+                .iter()
+                .map(|(category, template)| (*category, Cow::Borrowed(*template)))
+                .collect(),
+        );
+
+        self
+    }
+
+    fn template(&self, key: &str, count: Option<i64>) -> Option<&str> {
+        let forms = self.messages.get(key)?;
+        let category = count.map_or(PluralCategory::Other, self.plural_rule);
+
+        forms
+            .iter()
+            .find(|(form, _)| *form == category)
+            .or_else(|| {
+                forms
+                    .iter()
+                    .find(|(form, _)| *form == PluralCategory::Other)
+            })
+            .map(|(_, template)| template.as_ref())
+    }
+}
+
+impl Debug for PuppetLocale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetLocale")
+            .field("tag", &self.tag)
+            .field("direction", &self.direction)
+            .field("messages", &self.messages.len())
+            .finish()
+    }
+}
+
+/// Registered locales plus the DOM nodes that follow the current one
+#[derive(Debug, Default)]
+struct PuppetI18n {
+    locales: Vec<PuppetLocale>,
+    current: Option<usize>,
+    roots: Vec<Element>,
+    bindings: Vec<TextBinding>,
+}
+
+#[derive(Debug)]
+struct TextBinding {
+    node: Node,
+    attribute: Option<&'static str>,
+    text: PuppetText,
+}
+
+impl PuppetI18n {
+    fn current(&self) -> Option<&PuppetLocale> {
+        self.current.and_then(|index| self.locales.get(index))
+    }
+
+    /// The first registered locale
+    fn fallback(&self) -> Option<&PuppetLocale> {
+        self.locales.first()
+    }
+
+    /// Forget the roots and nodes removed from the document
+    fn prune(&mut self) {
+        self.roots.retain(|root| root.is_connected());
+        self.bindings.retain(|binding| binding.node.is_connected());
+    }
+
+    fn set_root_attributes(&self, root: &Element) -> JsValueResult<()> {
+        if let Some(locale) = self.current() {
+            root.set_attribute("lang", locale.tag)?;
+            root.set_attribute("dir", locale.direction.to_html_dir())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Add or replace the bundle for `locale.tag()`. The first registered locale becomes
+/// the current one and the fallback for missing messages.
+pub fn register_locale(locale: PuppetLocale) {
+    I18N.with(|i18n| {
+        let mut i18n = i18n.borrow_mut();
+
+        match i18n
+            .locales
+            .iter()
+            .position(|registered| registered.tag == locale.tag)
+        {
+            Some(index) => i18n.locales[index] = locale,
+            None => i18n.locales.push(locale),
+        }
+
+        if i18n.current.is_none() {
+            i18n.current = Some(0);
+        }
+    })
+}
+
+/// Switch to a registered locale, updating the `lang`/`dir` of every mount root and
+/// the text of every rendered message in place
+pub fn set_locale(tag: &str) -> JsValueResult<()> {
+    let bindings = I18N.with(|i18n| -> JsValueResult<Vec<_>> {
+        let mut i18n = i18n.borrow_mut();
+
+        match i18n.locales.iter().position(|locale| locale.tag == tag) {
+            Some(index) => i18n.current = Some(index),
+            None => {
+                trace!("UNKNOWN LOCALE: {}", tag);
+                return Ok(Vec::default());
+            }
+        }
+
+        i18n.prune();
+
+        for root in &i18n.roots {
+            i18n.set_root_attributes(root)?;
+        }
+
+        Ok(i18n
+            .bindings
+            .iter()
+            .map(|binding| {
+                (
+                    binding.node.clone(),
+                    binding.attribute,
+                    binding.text.clone(),
+                )
+            })
+            .collect())
+    })?;
+
+    // Messages are resolved outside of the borrow above since they borrow the locales too
+    for (node, attribute, text) in bindings {
+        match (attribute, node.dyn_ref::<Element>()) {
+            (Some(attribute), Some(element)) => {
+                let resolved = text.resolve();
+                match is_safe_attribute(attribute, &resolved) {
+                    true => element.set_attribute(attribute, &resolved)?,
+                    false => element.remove_attribute(attribute)?,
+                }
+            }
+            _ => node.set_text_content(Some(&text.resolve())),
+        }
+    }
+
+    Ok(())
+}
+
+/// The tag of the current locale, if any locale was registered
+pub fn current_locale() -> Option<&'static str> {
+    I18N.with(|i18n| i18n.borrow().current().map(PuppetLocale::tag))
+}
+
+pub fn current_direction() -> PuppetDirection {
+    I18N.with(|i18n| {
+        i18n.borrow()
+            .current()
+            .map(PuppetLocale::text_direction)
+            .unwrap_or_default()
+    })
+}
+
+/// Set `lang` and `dir` on `root` now and whenever the locale changes
+pub fn mount_locale_root(root: &Element) -> JsValueResult<()> {
+    I18N.with(|i18n| {
+        let mut i18n = i18n.borrow_mut();
+        i18n.set_root_attributes(root)?;
+        i18n.roots.push(root.clone());
+
+        Ok(())
+    })
+}
+
+/// Keep the text content, or `attribute` when given, of `node` in sync with the locale
+pub(crate) fn bind_text(node: &Node, attribute: Option<&'static str>, text: &PuppetText) {
+    if text.is_message() {
+        I18N.with(|i18n| {
+            i18n.borrow_mut().bindings.push(TextBinding {
+                node: node.clone(),
+                attribute,
+                text: text.clone(),
+            })
+        })
+    }
+}
+
+/// Forget the bound nodes removed from the document, once a render attached its new
+/// nodes so that they are not mistaken for removed ones
+pub(crate) fn prune_text_bindings() {
+    I18N.with(|i18n| i18n.borrow_mut().prune())
+}
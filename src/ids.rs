@@ -0,0 +1,175 @@
+use crate::PuppetElement;
+use core::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt,
+    fmt::{Debug, Display},
+};
+use log::trace;
+use nanorand::{Rng, WyRand};
+use std::collections::HashSet;
+
+thread_local! {
+    static ID_GENERATOR: RefCell<Option<IdGenerator>> = RefCell::default();
+}
+
+/// Start of every generated id, so that they never clash with ids written by hand
+pub const PUPPET_ID_PREFIX: &str = "p";
+
+/// Generate the same ids on every run from now on.
+///
+/// Tests get stable markup this way, and a server rendering markup to be hydrated
+/// should be seeded like the client so that both build the same ids.
+pub fn seed_ids(seed: u64) {
+    ID_GENERATOR.with(|generator| *generator.borrow_mut() = Some(IdGenerator::new(seed)));
+}
+
+struct IdGenerator {
+    /// Random per run, so that ids differ between apps sharing a page
+    session: u32,
+    /// Ids are never reused within a run, which keeps them free of collisions
+    next: u64,
+}
+
+impl IdGenerator {
+    fn new(seed: u64) -> Self {
+        Self {
+            session: WyRand::new_seed(seed).generate(),
+            next: 0,
+        }
+    }
+
+    fn from_entropy() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        let seed = crate::get_window()
+            .performance()
+            .map(|performance| (performance.time_origin() + performance.now()).to_bits())
+            .unwrap_or_default();
+        #[cfg(not(target_arch = "wasm32"))]
+        let seed = WyRand::new().generate();
+
+        Self::new(seed)
+    }
+}
+
+/// The id generated for every [crate::PuppetColumn], [crate::PuppetRow] and
+/// [crate::Component], written as their `id` when they have none of their own and
+/// another node refers to them.
+///
+/// Generated ids take no part in comparisons, so that trees built alike stay equal.
+/// A clone keeps the id, so that labels and descriptions referring to the original
+/// still find it. Rendering a node next to its clone writes the id twice, which
+/// [PuppetElement::duplicate_ids] reports.
+#[derive(Clone, Copy)]
+pub struct PuppetId {
+    session: u32,
+    index: u64,
+}
+
+impl PuppetId {
+    pub fn generate() -> Self {
+        ID_GENERATOR.with(|generator| {
+            let mut generator = generator.borrow_mut();
+            let generator = generator.get_or_insert_with(IdGenerator::from_entropy);
+
+            let id = Self {
+                session: generator.session,
+                index: generator.next,
+            };
+            generator.next += 1;
+
+            id
+        })
+    }
+
+    pub fn to_html_id(&self) -> String {
+        format!("{}{:08x}-{}", PUPPET_ID_PREFIX, self.session, self.index)
+    }
+}
+
+impl Default for PuppetId {
+    fn default() -> Self {
+        Self::generate()
+    }
+}
+
+impl PartialEq for PuppetId {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for PuppetId {}
+
+impl PartialOrd for PuppetId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PuppetId {
+    fn cmp(&self, _other: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Debug for PuppetId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PuppetId({})", self.to_html_id())
+    }
+}
+
+impl Display for PuppetId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_html_id())
+    }
+}
+
+/// Whether `id` can be written as an HTML `id`: not empty and without whitespace
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && !id.chars().any(char::is_whitespace)
+}
+
+impl PuppetElement {
+    /// Ids used by more than one element of this tree, in the order they are found
+    /// again
+    pub fn duplicate_ids(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut duplicates = Vec::default();
+        collect_duplicate_ids(self, &mut seen, &mut duplicates);
+
+        duplicates
+    }
+
+    /// Log and return every id of this tree that is not unique, ids being looked up
+    /// by labels and descriptions
+    pub(crate) fn warn_duplicate_ids(&self) -> Vec<String> {
+        let duplicates = self.duplicate_ids();
+
+        for id in &duplicates {
+            trace!("DUPLICATE ID {}", id);
+        }
+
+        duplicates
+    }
+}
+
+fn collect_duplicate_ids(
+    element: &PuppetElement,
+    seen: &mut HashSet<String>,
+    duplicates: &mut Vec<String>,
+) {
+    if let Some(id) = element.get_attribute("id") {
+        let id = id.resolve().into_owned();
+
+        if !seen.insert(id.clone()) && !duplicates.contains(&id) {
+            duplicates.push(id);
+        }
+    }
+
+    for child in element.children() {
+        if let crate::PuppetChild::Element(child) = child {
+            collect_duplicate_ids(child, seen, duplicates);
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{
+    Component, ComponentType, JsValueResult, ObjectFit, PuppetColor, PuppetElement,
+    PuppetEventHandler, PuppetStyle, PuppetText,
+};
+use core::{
+    fmt,
+    fmt::{Debug, Display},
+};
+use log::trace;
+use std::borrow::Cow;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Element, HtmlElement};
+
+/// What an image shows, for people who cannot see it
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub enum ImageAlt {
+    Text(PuppetText),
+    /// The image only decorates the page and is skipped by screen readers
+    Decorative,
+}
+
+impl Default for ImageAlt {
+    fn default() -> Self {
+        Self::Text(PuppetText::default())
+    }
+}
+
+/// When the browser fetches an image
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum ImageLoading {
+    #[default]
+    Eager,
+    /// Once the image gets close to the viewport
+    Lazy,
+}
+
+impl ImageLoading {
+    pub fn to_html_loading(&self) -> &'static str {
+        match self {
+            Self::Eager => "eager",
+            Self::Lazy => "lazy",
+        }
+    }
+}
+
+impl Debug for ImageLoading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}",
+            match self {
+                Self::Eager => "ImageLoading::Eager",
+                Self::Lazy => "ImageLoading::Lazy",
+            }
+        )
+    }
+}
+
+impl Display for ImageLoading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Eager => "Eager",
+                Self::Lazy => "Lazy",
+            }
+        )
+    }
+}
+
+/// When the browser picks a candidate of a `srcset`
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum ImageDescriptor {
+    /// The intrinsic width of the candidate in pixels, chosen through `sizes`
+    Width(u32),
+    /// The pixel density the candidate is meant for
+    Density(u8),
+}
+
+impl ImageDescriptor {
+    pub fn to_html_descriptor(&self) -> String {
+        match self {
+            Self::Width(width) => format!("{}w", width),
+            Self::Density(density) => format!("{}x", density),
+        }
+    }
+}
+
+impl Debug for ImageDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Width(width) => write!(f, "ImageDescriptor::Width({})", width),
+            Self::Density(density) => write!(f, "ImageDescriptor::Density({})", density),
+        }
+    }
+}
+
+impl Display for ImageDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_html_descriptor())
+    }
+}
+
+/// An image with its responsive sources, alternative text and loading behaviour
+///
+/// ```
+/// use puppeteer_builders::{ImageDescriptor, ImageLoading, ObjectFit, PuppetColor, PuppetImage};
+///
+/// let mut hero = PuppetImage::new("hero-800.jpg", "A puppet on stage");
+/// hero.candidate("hero-1600.jpg", ImageDescriptor::Width(1600))
+///     .candidate("hero-800.jpg", ImageDescriptor::Width(800))
+///     .sizes("(max-width: 800px) 100vw, 800px")
+///     .dimensions(800, 450)
+///     .object_fit(ObjectFit::Cover)
+///     .loading(ImageLoading::Lazy)
+///     .placeholder(PuppetColor::Hex("DDDDDD"));
+///
+/// let html = hero.to_html_string();
+/// assert!(html.starts_with(concat!(
+///     r#"<img alt="A puppet on stage" src="hero-800.jpg" "#,
+///     r#"srcset="hero-1600.jpg 1600w, hero-800.jpg 800w" sizes="(max-width: 800px) 100vw, 800px" "#,
+///     r#"width="800" height="450" loading="lazy""#,
+/// )));
+/// assert!(html.contains("background-color: #DDDDDD; object-fit: cover;"));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetImage {
+    src: Cow<'static, str>,
+    srcset: Vec<(Cow<'static, str>, ImageDescriptor)>,
+    sizes: Option<Cow<'static, str>>,
+    alt: ImageAlt,
+    width: Option<u32>,
+    height: Option<u32>,
+    object_fit: Option<ObjectFit>,
+    loading: ImageLoading,
+    placeholder: Option<PuppetColor>,
+    style: PuppetStyle,
+}
+
+impl PuppetImage {
+    pub fn new<S, A>(src: S, alt: A) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+        A: Into<PuppetText>,
+    {
+        Self {
+            src: src.into(),
+            alt: ImageAlt::Text(alt.into()),
+            ..Self::default()
+        }
+    }
+
+    /// An image without meaning, written with an empty `alt`
+    pub fn decorative<S>(src: S) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+    {
+        Self {
+            src: src.into(),
+            alt: ImageAlt::Decorative,
+            ..Self::default()
+        }
+    }
+
+    pub fn src<T>(&mut self, src: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.src = src.into();
+
+        self
+    }
+
+    /// Add a source to the `srcset` the browser picks from
+    pub fn candidate<T>(&mut self, src: T, descriptor: ImageDescriptor) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.srcset.push((src.into(), descriptor));
+
+        self
+    }
+
+    /// The width the image is shown at, as a CSS media condition list such as
+    /// `(max-width: 600px) 100vw, 50vw`
+    pub fn sizes<T>(&mut self, sizes: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.sizes = Some(sizes.into());
+
+        self
+    }
+
+    pub fn alt(&mut self, alt: ImageAlt) -> &mut Self {
+        self.alt = alt;
+
+        self
+    }
+
+    /// The intrinsic size in pixels, letting the browser reserve the space of the
+    /// image before it is loaded
+    pub fn dimensions(&mut self, width: u32, height: u32) -> &mut Self {
+        self.width = Some(width);
+        self.height = Some(height);
+
+        self
+    }
+
+    pub fn object_fit(&mut self, object_fit: ObjectFit) -> &mut Self {
+        self.object_fit = Some(object_fit);
+
+        self
+    }
+
+    pub fn loading(&mut self, loading: ImageLoading) -> &mut Self {
+        self.loading = loading;
+
+        self
+    }
+
+    /// The background shown while the image loads, cleared once it has by the DOM
+    /// backend
+    pub fn placeholder(&mut self, placeholder: PuppetColor) -> &mut Self {
+        self.placeholder = Some(placeholder);
+
+        self
+    }
+
+    pub fn set_style(&mut self, style: &PuppetStyle) -> &mut Self {
+        self.style = *style;
+
+        self
+    }
+
+    pub fn get_alt(&self) -> &ImageAlt {
+        &self.alt
+    }
+
+    pub fn is_decorative(&self) -> bool {
+        self.alt == ImageAlt::Decorative
+    }
+
+    pub fn get_src(&self) -> &str {
+        &self.src
+    }
+
+    pub fn to_html_srcset(&self) -> Option<String> {
+        if self.srcset.is_empty() {
+            return None;
+        }
+
+        Some(
+            self.srcset
+                .iter()
+                .map(|(src, descriptor)| format!("{} {}", src, descriptor.to_html_descriptor()))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+
+    pub fn to_component(&self) -> Component {
+        Component::from(self.clone())
+    }
+
+    pub fn to_element(&self) -> PuppetElement {
+        self.to_element_with(&self.alt, &self.style)
+    }
+
+    /// The `img` element, described by `alt` and laid out by `style` as components
+    /// keep both outside of their image
+    pub(crate) fn to_element_with(&self, alt: &ImageAlt, style: &PuppetStyle) -> PuppetElement {
+        let mut element = PuppetElement::new(ComponentType::Image.to_html_tag());
+
+        element.attribute(
+            "alt",
+            match alt {
+                ImageAlt::Text(text) => text.clone(),
+                ImageAlt::Decorative => PuppetText::default(),
+            },
+        );
+
+        if !self.src.is_empty() {
+            element.attribute("src", self.src.clone());
+        }
+
+        if let Some(srcset) = self.to_html_srcset() {
+            element.attribute("srcset", srcset);
+        }
+
+        if let Some(sizes) = &self.sizes {
+            element.attribute("sizes", sizes.clone());
+        }
+
+        if let Some(width) = self.width {
+            element.attribute("width", width.to_string());
+        }
+
+        if let Some(height) = self.height {
+            element.attribute("height", height.to_string());
+        }
+
+        if self.loading == ImageLoading::Lazy {
+            element.attribute("loading", self.loading.to_html_loading());
+        }
+
+        let mut style = *style;
+        if let Some(object_fit) = self.object_fit {
+            style.object_fit(object_fit);
+        }
+        if let Some(placeholder) = self.placeholder {
+            style.background_color(placeholder);
+            element.add_handler(PuppetEventHandler::new("load", clear_placeholder));
+        }
+        element.set_style(&style);
+
+        element
+    }
+
+    pub fn to_html(&self, document: &Document, parent_node: &Element) -> JsValueResult<()> {
+        self.to_element().to_html(document, parent_node)
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+}
+
+impl From<PuppetImage> for Component {
+    fn from(image: PuppetImage) -> Self {
+        let text = match image.get_alt() {
+            ImageAlt::Text(text) => text.clone(),
+            ImageAlt::Decorative => PuppetText::default(),
+        };
+
+        let mut component = Component::new(ComponentType::Image, text);
+        component.set_style(&image.style).image(image);
+
+        component
+    }
+}
+
+/// Transparent images would otherwise show their placeholder once loaded
+fn clear_placeholder(event: &web_sys::Event) {
+    if let Some(image) = event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+    {
+        if let Err(error) = image
+            .style()
+            .set_property("background-color", "transparent")
+        {
+            trace!("{:?}", error);
+        }
+    }
+}
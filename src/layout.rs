@@ -0,0 +1,944 @@
+use crate::{
+    strip_tags, AlignContent, AlignItems, AlignSelf, FlexDirection, FlexGap, FlexWrap,
+    JustifyContent, PuppetAlignment, PuppetChild, PuppetDirection, PuppetElement, PuppetLength,
+    PuppetStyle, PuppetUnit,
+};
+use core::mem;
+
+/// The tag of the anonymous boxes holding the text of an element
+pub const TEXT_TAG: &str = "#text";
+
+/// Rounding slack when turning a width back into a number of characters
+pub(crate) const EPSILON: f32 = 0.01;
+
+/// A rectangle in viewport units, `x` and `y` being its top left corner
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone, Copy)]
+pub struct PuppetRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PuppetRect {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+}
+
+/// The screen a tree is laid out on, and how text is measured on it
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PuppetViewport {
+    width: f32,
+    height: f32,
+    direction: PuppetDirection,
+    char_width: f32,
+    line_height: f32,
+}
+
+impl PuppetViewport {
+    /// A left-to-right viewport measuring text in 8x16 pixel characters
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            direction: PuppetDirection::default(),
+            char_width: 8.0,
+            line_height: 16.0,
+        }
+    }
+
+    pub fn direction(&mut self, direction: PuppetDirection) -> &mut Self {
+        self.direction = direction;
+
+        self
+    }
+
+    /// Every character of text is `char_width` wide and every line `line_height` tall
+    pub fn text_metrics(&mut self, char_width: f32, line_height: f32) -> &mut Self {
+        self.char_width = char_width;
+        self.line_height = line_height;
+
+        self
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+}
+
+/// The computed geometry of one [PuppetElement], or of its text when tagged [TEXT_TAG]
+#[derive(Debug, PartialEq, Clone)]
+pub struct PuppetBox {
+    tag: &'static str,
+    id: Option<String>,
+    /// Border box, padding included and margin excluded
+    rect: PuppetRect,
+    style: Option<PuppetStyle>,
+    /// Wrapped lines of a text box
+    lines: Vec<String>,
+    children: Vec<PuppetBox>,
+}
+
+impl PuppetBox {
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn rect(&self) -> PuppetRect {
+        self.rect
+    }
+
+    pub fn style(&self) -> Option<&PuppetStyle> {
+        self.style.as_ref()
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn children(&self) -> &[PuppetBox] {
+        &self.children
+    }
+
+    pub fn is_text(&self) -> bool {
+        self.tag == TEXT_TAG
+    }
+
+    /// The first box, depth first, laid out for the element with this `id`
+    pub fn find(&self, id: &str) -> Option<&PuppetBox> {
+        if self.id.as_deref() == Some(id) {
+            return Some(self);
+        }
+
+        self.children.iter().find_map(|child| child.find(id))
+    }
+
+    fn translate(&mut self, x: f32, y: f32) {
+        self.rect.x += x;
+        self.rect.y += y;
+
+        for child in &mut self.children {
+            child.translate(x, y);
+        }
+    }
+}
+
+impl PuppetElement {
+    /// Lay the tree out on `viewport` the way a browser would: an element with a
+    /// [PuppetAlignment] is a flex container, and one without stacks its children as
+    /// blocks as wide as itself.
+    ///
+    /// Sizes use `box-sizing: content-box` and unset sizes are `auto`.
+    /// [PuppetAlignment::Grid] stacks its children from the top. See
+    /// [PuppetElement::validate_layout] for constraints that cannot be honored.
+    pub fn compute_layout(&self, viewport: &PuppetViewport) -> PuppetBox {
+        let solver = Solver { viewport };
+        let item = Item::Element(self);
+        let resolved = solver.resolve(&item, viewport.width, Some(viewport.height));
+
+        let mut root = solver.layout(&item, viewport.width, Some(viewport.height), None, None);
+        root.translate(resolved.margin, resolved.margin);
+
+        root
+    }
+}
+
+/// Word wrap `text` into lines of at most `columns` characters, breaking words that
+/// are longer than a line
+pub(crate) fn wrap_text(text: &str, columns: usize) -> Vec<String> {
+    let columns = columns.max(1);
+    let mut lines = Vec::default();
+    let mut line = String::default();
+
+    for mut word in text.split_whitespace() {
+        loop {
+            let word_length = word.chars().count();
+            let line_length = line.chars().count();
+
+            if line_length == 0 && word_length <= columns {
+                line.push_str(word);
+                break;
+            }
+
+            if line_length > 0 && line_length + 1 + word_length <= columns {
+                line.push(' ');
+                line.push_str(word);
+                break;
+            }
+
+            if line_length > 0 {
+                lines.push(mem::take(&mut line));
+                continue;
+            }
+
+            let split = word
+                .char_indices()
+                .nth(columns)
+                .map_or(word.len(), |(index, _)| index);
+            lines.push(word[..split].into());
+            word = &word[split..];
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+pub(crate) enum Item<'e> {
+    Element(&'e PuppetElement),
+    Text(String),
+}
+
+impl<'e> Item<'e> {
+    pub(crate) fn children(element: &'e PuppetElement) -> Vec<Item<'e>> {
+        if let Some(raw_html) = element.get_raw_html() {
+            return vec![Item::Text(strip_tags(raw_html))];
+        }
+
+        element
+            .children()
+            .iter()
+            .map(|child| match child {
+                PuppetChild::Element(child) => Item::Element(child),
+                PuppetChild::Text(text) => Item::Text(text.resolve().into_owned()),
+            })
+            .collect()
+    }
+
+    fn style(&self) -> Option<&PuppetStyle> {
+        match self {
+            Item::Element(element) => element.style(),
+            Item::Text(_) => None,
+        }
+    }
+}
+
+/// A [PuppetStyle] resolved against its containing block
+pub(crate) struct Resolved {
+    pub(crate) margin: f32,
+    pub(crate) padding: f32,
+    pub(crate) width: Option<f32>,
+    pub(crate) height: Option<f32>,
+    pub(crate) min_width: f32,
+    pub(crate) max_width: f32,
+    pub(crate) min_height: f32,
+    pub(crate) max_height: f32,
+    /// `None` when the children flow as blocks, as they do without `display: flex`
+    alignment: Option<PuppetAlignment>,
+}
+
+impl Resolved {
+    /// Padding and margin on both sides
+    pub(crate) fn extra(&self) -> f32 {
+        2.0 * (self.padding + self.margin)
+    }
+
+    /// Like CSS, the minimum wins over the maximum
+    pub(crate) fn clamp_width(&self, width: f32) -> f32 {
+        width.min(self.max_width).max(self.min_width)
+    }
+
+    pub(crate) fn clamp_height(&self, height: f32) -> f32 {
+        height.min(self.max_height).max(self.min_height)
+    }
+
+    fn align_self(&self) -> AlignSelf {
+        match self.alignment {
+            Some(PuppetAlignment::Flex { align_self, .. }) => align_self,
+            Some(PuppetAlignment::Grid) | None => AlignSelf::Auto,
+        }
+    }
+}
+
+/// Flex container settings with gaps resolved to viewport units
+struct Flex {
+    direction: FlexDirection,
+    wrap: FlexWrap,
+    justify_content: JustifyContent,
+    align_items: AlignItems,
+    align_content: AlignContent,
+    column_gap: f32,
+    row_gap: f32,
+    /// Whether the container is a flex container, `align-self` being ignored by block
+    /// and grid containers which stretch their children across
+    is_flex: bool,
+}
+
+impl Flex {
+    fn is_row(&self) -> bool {
+        matches!(
+            self.direction,
+            FlexDirection::Row | FlexDirection::RowReverse
+        )
+    }
+
+    fn is_reverse(&self) -> bool {
+        matches!(
+            self.direction,
+            FlexDirection::RowReverse | FlexDirection::ColumnReverse
+        )
+    }
+
+    fn main_gap(&self) -> f32 {
+        if self.is_row() {
+            self.column_gap
+        } else {
+            self.row_gap
+        }
+    }
+
+    fn cross_gap(&self) -> f32 {
+        if self.is_row() {
+            self.row_gap
+        } else {
+            self.column_gap
+        }
+    }
+
+    /// How an item is placed on the cross axis of its line
+    fn align(&self, align_self: AlignSelf) -> AlignSelf {
+        match align_self {
+            AlignSelf::Auto => match self.align_items {
+                AlignItems::FlexStart => AlignSelf::FlexStart,
+                AlignItems::FlexEnd => AlignSelf::FlexEnd,
+                AlignItems::Center => AlignSelf::Center,
+                AlignItems::Baseline => AlignSelf::Baseline,
+                AlignItems::Stretch => AlignSelf::Stretch,
+            },
+            align_self if self.is_flex => align_self,
+            _ => AlignSelf::Stretch,
+        }
+    }
+}
+
+/// An item of a flex line with its sizes along the main axis
+struct FlexItem<'e> {
+    item: Item<'e>,
+    resolved: Resolved,
+    /// Hypothetical main size of the content box
+    base: f32,
+    min: f32,
+    max: f32,
+    /// Used main size of the content box
+    size: f32,
+    /// Content width of items laid out in a column
+    cross: Option<f32>,
+    /// The item laid out at its content height when measured in a column
+    measured: Option<PuppetBox>,
+    frozen: bool,
+}
+
+impl<'e> FlexItem<'e> {
+    fn outer_main(&self) -> f32 {
+        self.size + self.resolved.extra()
+    }
+}
+
+pub(crate) struct Solver<'v> {
+    pub(crate) viewport: &'v PuppetViewport,
+}
+
+impl<'v> Solver<'v> {
+    fn resolve_unit(&self, unit: Option<PuppetUnit>, basis: Option<f32>) -> Option<f32> {
+        match unit? {
+            PuppetUnit::Pixels(pixels) => Some(f32::from(pixels)),
+            PuppetUnit::Percentage(percentage) => {
+                basis.map(|basis| basis * f32::from(percentage) / 100.0)
+            }
+        }
+    }
+
+    fn resolve_length(&self, length: Option<PuppetLength>, basis: Option<f32>) -> Option<f32> {
+        let fraction = match length? {
+            PuppetLength::ViewPortWidth => return Some(self.viewport.width),
+            PuppetLength::ViewPortHeight => return Some(self.viewport.height),
+            PuppetLength::Normal => return None,
+            PuppetLength::Full => 1.0,
+            PuppetLength::ThreeQuarter => 0.75,
+            PuppetLength::Half => 0.5,
+            PuppetLength::Quarter => 0.25,
+            PuppetLength::Eighth => 0.125,
+        };
+
+        basis.map(|basis| basis * fraction)
+    }
+
+    pub(crate) fn resolve(&self, item: &Item, width: f32, height: Option<f32>) -> Resolved {
+        match item.style() {
+            Some(style) => Resolved {
+                // Percentages of padding and margin are based on the width, as in CSS
+                margin: self.resolve_unit(style.margin, Some(width)).unwrap_or(0.0),
+                padding: self.resolve_unit(style.padding, Some(width)).unwrap_or(0.0),
+                width: self.resolve_length(style.width, Some(width)),
+                height: self.resolve_length(style.height, height),
+                min_width: self
+                    .resolve_unit(style.min_width, Some(width))
+                    .unwrap_or(0.0),
+                max_width: self
+                    .resolve_unit(style.max_width, Some(width))
+                    .unwrap_or(f32::INFINITY),
+                min_height: self.resolve_unit(style.min_height, height).unwrap_or(0.0),
+                max_height: self
+                    .resolve_unit(style.max_height, height)
+                    .unwrap_or(f32::INFINITY),
+                alignment: style
+                    .alignment
+                    .map(|alignment| alignment.for_direction(self.viewport.direction)),
+            },
+            None => Resolved {
+                margin: 0.0,
+                padding: 0.0,
+                width: None,
+                height: None,
+                min_width: 0.0,
+                max_width: f32::INFINITY,
+                min_height: 0.0,
+                max_height: f32::INFINITY,
+                alignment: None,
+            },
+        }
+    }
+
+    fn flex(&self, alignment: Option<PuppetAlignment>, width: f32, height: Option<f32>) -> Flex {
+        match alignment {
+            Some(PuppetAlignment::Flex {
+                flex_direction,
+                flex_wrap,
+                justify_content,
+                align_items,
+                align_content,
+                gap,
+                ..
+            }) => {
+                let (column_gap, row_gap) = match gap {
+                    FlexGap::Column(gap) => (self.resolve_unit(Some(gap), Some(width)), None),
+                    FlexGap::Row(gap) => (None, self.resolve_unit(Some(gap), height)),
+                };
+
+                Flex {
+                    direction: flex_direction,
+                    wrap: flex_wrap,
+                    justify_content,
+                    align_items,
+                    align_content,
+                    column_gap: column_gap.unwrap_or(0.0),
+                    row_gap: row_gap.unwrap_or(0.0),
+                    is_flex: true,
+                }
+            }
+            // Blocks stack and stretch to the width of their container
+            Some(PuppetAlignment::Grid) | None => Flex {
+                direction: FlexDirection::Column,
+                wrap: FlexWrap::NoWrap,
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Stretch,
+                align_content: AlignContent::FlexStart,
+                column_gap: 0.0,
+                row_gap: 0.0,
+                is_flex: false,
+            },
+        }
+    }
+
+    fn text_width(&self, text: &str) -> f32 {
+        text.chars().count() as f32 * self.viewport.char_width
+    }
+
+    fn min_text_width(&self, text: &str) -> f32 {
+        text.split_whitespace()
+            .map(|word| self.text_width(word))
+            .fold(0.0, f32::max)
+    }
+
+    /// Outer width of `item` when laid out on a single line (`max`) or as narrow as
+    /// its content allows
+    fn intrinsic_width(&self, item: &Item, width: f32, height: Option<f32>, max: bool) -> f32 {
+        let element = match item {
+            Item::Element(element) => element,
+            Item::Text(text) if max => return self.text_width(text),
+            Item::Text(text) => return self.min_text_width(text),
+        };
+
+        let resolved = self.resolve(item, width, height);
+
+        let content = match resolved.width {
+            Some(width) => width,
+            None => {
+                let flex = self.flex(resolved.alignment, width, height);
+                let children = Item::children(element)
+                    .iter()
+                    .map(|child| self.intrinsic_width(child, width, height, max))
+                    .collect::<Vec<f32>>();
+
+                if flex.is_row() && (max || flex.wrap == FlexWrap::NoWrap) {
+                    let gaps = children.len().saturating_sub(1) as f32 * flex.main_gap();
+                    children.iter().sum::<f32>() + gaps
+                } else {
+                    children.iter().copied().fold(0.0, f32::max)
+                }
+            }
+        };
+
+        resolved.clamp_width(content) + resolved.extra()
+    }
+
+    /// Lay `item` out at the origin. `width` and `height` force the size of its content
+    /// box, otherwise it is taken from its style, or from its content when auto.
+    fn layout(
+        &self,
+        item: &Item,
+        containing_width: f32,
+        containing_height: Option<f32>,
+        width: Option<f32>,
+        height: Option<f32>,
+    ) -> PuppetBox {
+        let element = match item {
+            Item::Element(element) => element,
+            Item::Text(text) => {
+                let width = width.unwrap_or_else(|| self.text_width(text));
+                let columns = ((width + EPSILON) / self.viewport.char_width).floor() as usize;
+                let lines = wrap_text(text, columns);
+
+                return PuppetBox {
+                    tag: TEXT_TAG,
+                    id: None,
+                    rect: PuppetRect::new(
+                        0.0,
+                        0.0,
+                        width,
+                        lines.len() as f32 * self.viewport.line_height,
+                    ),
+                    style: None,
+                    lines,
+                    children: Vec::default(),
+                };
+            }
+        };
+
+        let resolved = self.resolve(item, containing_width, containing_height);
+
+        let content_width = width.unwrap_or_else(|| {
+            resolved.clamp_width(
+                resolved
+                    .width
+                    .unwrap_or((containing_width - resolved.extra()).max(0.0)),
+            )
+        });
+        let definite_height = height.or_else(|| resolved.height.map(|h| resolved.clamp_height(h)));
+
+        let (mut children, used_height) =
+            self.layout_children(element, &resolved, content_width, definite_height);
+        let content_height = definite_height.unwrap_or_else(|| resolved.clamp_height(used_height));
+
+        for child in &mut children {
+            child.translate(resolved.padding, resolved.padding);
+        }
+
+        PuppetBox {
+            tag: element.tag(),
+            id: element
+                .get_attribute("id")
+                .map(|id| id.resolve().into_owned()),
+            rect: PuppetRect::new(
+                0.0,
+                0.0,
+                content_width + 2.0 * resolved.padding,
+                content_height + 2.0 * resolved.padding,
+            ),
+            style: element.style().copied(),
+            lines: Vec::default(),
+            children,
+        }
+    }
+
+    /// Place the children of `element` inside its content box, returning their boxes
+    /// relative to the content box and the height they use
+    fn layout_children(
+        &self,
+        element: &PuppetElement,
+        resolved: &Resolved,
+        width: f32,
+        height: Option<f32>,
+    ) -> (Vec<PuppetBox>, f32) {
+        let flex = self.flex(resolved.alignment, width, height);
+        let is_row = flex.is_row();
+        let main_size = if is_row { Some(width) } else { height };
+        let cross_size = if is_row { height } else { Some(width) };
+
+        let mut items = Item::children(element)
+            .into_iter()
+            .map(|item| self.flex_item(item, &flex, width, height))
+            .collect::<Vec<FlexItem>>();
+
+        let lines = self.break_lines(&items, &flex, main_size);
+
+        for line in &lines {
+            self.resolve_main_sizes(&mut items[line.clone()], &flex, main_size);
+        }
+
+        // Lay every item out with its used main size to learn its cross size. Items
+        // measured in a column of auto height keep their content height, so they are
+        // not laid out again.
+        let mut boxes = items
+            .iter_mut()
+            .map(|flex_item| {
+                if let (None, Some(measured)) = (main_size, flex_item.measured.take()) {
+                    return measured;
+                }
+
+                let (item_width, item_height) = if is_row {
+                    (Some(flex_item.size), None)
+                } else {
+                    (flex_item.cross, Some(flex_item.size))
+                };
+
+                self.layout(&flex_item.item, width, height, item_width, item_height)
+            })
+            .collect::<Vec<PuppetBox>>();
+
+        let outer_cross = |index: usize, boxes: &[PuppetBox]| {
+            let rect = boxes[index].rect;
+            let cross = if is_row { rect.height } else { rect.width };
+
+            cross + 2.0 * items[index].resolved.margin
+        };
+
+        let mut line_crosses = lines
+            .iter()
+            .map(|line| {
+                line.clone()
+                    .map(|index| outer_cross(index, &boxes))
+                    .fold(0.0, f32::max)
+            })
+            .collect::<Vec<f32>>();
+
+        if let (FlexWrap::NoWrap, Some(cross_size), [line_cross]) =
+            (flex.wrap, cross_size, line_crosses.as_mut_slice())
+        {
+            *line_cross = cross_size;
+        }
+
+        let cross_gaps = lines.len().saturating_sub(1) as f32 * flex.cross_gap();
+        let mut used_cross = line_crosses.iter().sum::<f32>() + cross_gaps;
+
+        let (mut line_offset, line_spacing) = match (flex.wrap, cross_size) {
+            (FlexWrap::NoWrap, _) | (_, None) => (0.0, 0.0),
+            (_, Some(cross_size)) => {
+                let free = cross_size - used_cross;
+                let count = lines.len() as f32;
+
+                match flex.align_content {
+                    AlignContent::FlexStart => (0.0, 0.0),
+                    AlignContent::FlexEnd => (free, 0.0),
+                    AlignContent::Center => (free / 2.0, 0.0),
+                    AlignContent::SpaceBetween if free > 0.0 && lines.len() > 1 => {
+                        (0.0, free / (count - 1.0))
+                    }
+                    AlignContent::SpaceBetween => (0.0, 0.0),
+                    AlignContent::SpaceAround if free > 0.0 => (free / count / 2.0, free / count),
+                    AlignContent::SpaceAround => (free / 2.0, 0.0),
+                    AlignContent::Stretch => {
+                        if free > 0.0 {
+                            line_crosses
+                                .iter_mut()
+                                .for_each(|line_cross| *line_cross += free / count);
+                            used_cross = cross_size;
+                        }
+                        (0.0, 0.0)
+                    }
+                }
+            }
+        };
+
+        let used_main = lines
+            .iter()
+            .map(|line| {
+                let gaps = line.len().saturating_sub(1) as f32 * flex.main_gap();
+                items[line.clone()]
+                    .iter()
+                    .map(FlexItem::outer_main)
+                    .sum::<f32>()
+                    + gaps
+            })
+            .fold(0.0, f32::max);
+        let container_main = main_size.unwrap_or(used_main);
+        let container_cross = cross_size.unwrap_or(used_cross);
+
+        for (line, line_cross) in lines.iter().zip(&line_crosses) {
+            let line_main = items[line.clone()]
+                .iter()
+                .map(FlexItem::outer_main)
+                .sum::<f32>()
+                + line.len().saturating_sub(1) as f32 * flex.main_gap();
+            let (mut position, spacing) =
+                justify(flex.justify_content, container_main - line_main, line.len());
+
+            for index in line.clone() {
+                let flex_item = &items[index];
+                let margin = flex_item.resolved.margin;
+                let align = flex.align(flex_item.resolved.align_self());
+
+                let auto_cross = if is_row {
+                    flex_item.resolved.height.is_none()
+                } else {
+                    flex_item.resolved.width.is_none()
+                };
+                let stretched = line_cross - flex_item.resolved.extra();
+                let stretched_cross = if is_row {
+                    flex_item.resolved.clamp_height(stretched.max(0.0))
+                } else {
+                    flex_item.resolved.clamp_width(stretched.max(0.0))
+                };
+                // Items of a column already as wide as their line, such as blocks, are
+                // not laid out again
+                let is_stretched = !is_row
+                    && (boxes[index].rect.width
+                        - 2.0 * flex_item.resolved.padding
+                        - stretched_cross)
+                        .abs()
+                        <= EPSILON;
+                if align == AlignSelf::Stretch && auto_cross && !is_stretched {
+                    boxes[index] = if is_row {
+                        self.layout(
+                            &flex_item.item,
+                            width,
+                            height,
+                            Some(flex_item.size),
+                            Some(stretched_cross),
+                        )
+                    } else {
+                        self.layout(
+                            &flex_item.item,
+                            width,
+                            height,
+                            Some(stretched_cross),
+                            Some(flex_item.size),
+                        )
+                    };
+                }
+
+                let item_cross = outer_cross(index, &boxes);
+                let cross_position = line_offset
+                    + margin
+                    + match align {
+                        AlignSelf::FlexEnd => line_cross - item_cross,
+                        AlignSelf::Center => (line_cross - item_cross) / 2.0,
+                        _ => 0.0,
+                    };
+                let main_position = position + margin;
+
+                let rect = boxes[index].rect;
+                let (box_main, box_cross) = if is_row {
+                    (rect.width, rect.height)
+                } else {
+                    (rect.height, rect.width)
+                };
+
+                let main_position = if flex.is_reverse() {
+                    container_main - main_position - box_main
+                } else {
+                    main_position
+                };
+                let cross_position = if flex.wrap == FlexWrap::WrapReverse {
+                    container_cross - cross_position - box_cross
+                } else {
+                    cross_position
+                };
+
+                if is_row {
+                    boxes[index].translate(main_position, cross_position);
+                } else {
+                    boxes[index].translate(cross_position, main_position);
+                }
+
+                position += flex_item.outer_main() + flex.main_gap() + spacing;
+            }
+
+            line_offset += line_cross + flex.cross_gap() + line_spacing;
+        }
+
+        let used_height = if is_row { used_cross } else { used_main };
+
+        (boxes, used_height)
+    }
+
+    /// The hypothetical main size of `item` and the limits it can shrink or grow to
+    fn flex_item<'e>(
+        &self,
+        item: Item<'e>,
+        flex: &Flex,
+        width: f32,
+        height: Option<f32>,
+    ) -> FlexItem<'e> {
+        let resolved = self.resolve(&item, width, height);
+        let extra = resolved.extra();
+
+        let (base, min, max, cross, measured) = if flex.is_row() {
+            let content = |max| self.intrinsic_width(&item, width, height, max) - extra;
+            let base = resolved.clamp_width(resolved.width.unwrap_or_else(|| content(true)));
+            let min = if resolved.min_width > 0.0 || resolved.width.is_some() {
+                resolved.min_width
+            } else {
+                content(false).min(base)
+            };
+
+            (base, min, resolved.max_width, None, None)
+        } else {
+            let available = (width - extra).max(0.0);
+            let cross = resolved.clamp_width(match resolved.width {
+                Some(width) => width,
+                None if flex.align(resolved.align_self()) == AlignSelf::Stretch => available,
+                None => (self.intrinsic_width(&item, width, height, true) - extra).min(available),
+            });
+            let measured = match resolved.height {
+                Some(_) => None,
+                None => Some(self.layout(&item, width, height, Some(cross), None)),
+            };
+            let content = measured
+                .as_ref()
+                .map_or(0.0, |measured| measured.rect.height)
+                - 2.0 * resolved.padding;
+            let base = resolved.clamp_height(resolved.height.unwrap_or(content));
+            // Like `min-height: auto`, items do not shrink below their content
+            let min = if resolved.min_height > 0.0 || resolved.height.is_some() {
+                resolved.min_height
+            } else {
+                content.min(base)
+            };
+
+            (base, min, resolved.max_height, Some(cross), measured)
+        };
+
+        FlexItem {
+            item,
+            resolved,
+            base,
+            min,
+            max,
+            size: base,
+            cross,
+            measured,
+            frozen: false,
+        }
+    }
+
+    fn break_lines(
+        &self,
+        items: &[FlexItem],
+        flex: &Flex,
+        main_size: Option<f32>,
+    ) -> Vec<core::ops::Range<usize>> {
+        let main_size = match (flex.wrap, main_size) {
+            (FlexWrap::NoWrap, _) | (_, None) => return core::iter::once(0..items.len()).collect(),
+            (_, Some(main_size)) => main_size,
+        };
+
+        let mut lines = Vec::default();
+        let mut start = 0;
+        let mut used = 0.0;
+
+        for (index, item) in items.iter().enumerate() {
+            let outer = item.outer_main();
+            let gap = if index > start { flex.main_gap() } else { 0.0 };
+
+            if index > start && used + gap + outer > main_size + EPSILON {
+                lines.push(start..index);
+                start = index;
+                used = outer;
+            } else {
+                used += gap + outer;
+            }
+        }
+        lines.push(start..items.len());
+
+        lines
+    }
+
+    /// Shrink the items of an overflowing line in proportion to their size, never
+    /// below their minimum size
+    fn resolve_main_sizes(&self, line: &mut [FlexItem], flex: &Flex, main_size: Option<f32>) {
+        for item in line.iter_mut() {
+            item.size = item.base.min(item.max).max(item.min);
+        }
+
+        let main_size = match main_size {
+            Some(main_size) => main_size,
+            None => return,
+        };
+
+        let gaps = line.len().saturating_sub(1) as f32 * flex.main_gap();
+
+        loop {
+            let used = line.iter().map(FlexItem::outer_main).sum::<f32>() + gaps;
+            let overflow = used - main_size;
+            let weight = line
+                .iter()
+                .filter(|item| !item.frozen)
+                .map(|item| item.size)
+                .sum::<f32>();
+
+            if overflow <= EPSILON || weight <= 0.0 {
+                return;
+            }
+
+            let mut froze = false;
+            for item in line.iter_mut().filter(|item| !item.frozen) {
+                let shrunk = item.size - overflow * item.size / weight;
+
+                if shrunk <= item.min {
+                    item.size = item.min;
+                    item.frozen = true;
+                    froze = true;
+                }
+            }
+
+            if !froze {
+                for item in line.iter_mut().filter(|item| !item.frozen) {
+                    item.size -= overflow * item.size / weight;
+                }
+                return;
+            }
+        }
+    }
+}
+
+/// Offset of the first item and extra space between items for `free` space on a line
+fn justify(justify_content: JustifyContent, free: f32, count: usize) -> (f32, f32) {
+    let count = count as f32;
+
+    match justify_content {
+        JustifyContent::FlexStart => (0.0, 0.0),
+        JustifyContent::FlexEnd => (free, 0.0),
+        JustifyContent::Center => (free / 2.0, 0.0),
+        JustifyContent::SpaceBetween if free > 0.0 && count > 1.0 => (0.0, free / (count - 1.0)),
+        JustifyContent::SpaceBetween => (0.0, 0.0),
+        JustifyContent::SpaceAround if free > 0.0 => (free / count / 2.0, free / count),
+        JustifyContent::SpaceEvenly if free > 0.0 => (free / (count + 1.0), free / (count + 1.0)),
+        JustifyContent::SpaceAround | JustifyContent::SpaceEvenly => (free / 2.0, 0.0),
+    }
+}
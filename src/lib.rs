@@ -1,8 +1,8 @@
-use core::{fmt::Debug, format_args};
+use core::fmt::Debug;
 use log::{trace, Level};
-use std::panic;
-use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::{Document, Element, HtmlElement, Window};
+use std::{borrow::Cow, panic};
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, Element, Window};
 
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
@@ -13,6 +13,58 @@ mod global;
 pub use global::*;
 mod flex;
 pub use flex::*;
+mod components;
+pub use components::*;
+mod events;
+pub use events::*;
+mod macros;
+mod router;
+pub use router::*;
+mod i18n;
+pub use i18n::*;
+mod element;
+pub use element::*;
+mod layout;
+pub mod snapshot;
+pub use layout::*;
+mod constraints;
+pub use constraints::*;
+mod terminal;
+pub use terminal::*;
+mod site;
+pub use site::*;
+mod hydrate;
+pub use hydrate::*;
+mod node;
+pub use node::*;
+mod overlay;
+pub use overlay::*;
+mod table;
+pub use table::*;
+mod virtual_list;
+pub use virtual_list::*;
+mod navigation;
+pub use navigation::*;
+mod toast;
+pub use toast::*;
+mod ids;
+pub use ids::*;
+mod focus;
+pub use focus::*;
+mod aria;
+pub use aria::*;
+mod attributes;
+pub use attributes::*;
+mod sanitize;
+pub use sanitize::*;
+mod variants;
+pub use variants::*;
+mod scoped;
+pub use scoped::*;
+mod image;
+pub use image::*;
+mod async_data;
+pub use async_data::*;
 
 #[wasm_bindgen(start)]
 pub fn main() -> Result<(), JsValue> {
@@ -22,23 +74,29 @@ pub fn main() -> Result<(), JsValue> {
         Err(e) => trace!("{:?}", e),
     }
 
-    let mut column_style = PuppetStyle::default();
-    column_style.width(PuppetLength::Half);
-    column_style.height(PuppetLength::ViewPortHeight);
-    column_style.min_width(PuppetUnit::Pixels(50));
-    column_style.min_height(PuppetUnit::Pixels(50));
-    column_style.background_color(PuppetColor::Rgb(0, 0, 0));
-
-    let mut column = PuppetColumn::new();
-    column.column_id("foo");
-    column.style = column_style.clone();
-    let document = get_document();
-
-    column.to_html(&document, &html_app_node())?;
+    let mut router = PuppetRouter::new(BrowserHistory);
+    router.route(PuppetRoute::new("/", |_params| demo_screen()));
+    router.mount(&html_app_node())?;
 
     Ok(())
 }
 
+/// The screen of the demo app, also written by the `puppeteer-export` binary
+pub fn demo_screen() -> PuppetColumn {
+    puppet! {
+        column {
+            id("foo")
+            style {
+                width: PuppetLength::Half,
+                height: PuppetLength::ViewPortHeight,
+                min_width: PuppetUnit::Pixels(50),
+                min_height: PuppetUnit::Pixels(50),
+                background_color: PuppetColor::Rgb(0, 0, 0),
+            }
+        }
+    }
+}
+
 pub(crate) fn get_window() -> Window {
     match web_sys::window() {
         Some(window) => window,
@@ -81,7 +139,13 @@ pub(crate) fn html_app_node() -> Element {
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct PuppetColumn {
     column_id: Option<&'static str>,
+    puppet_id: PuppetId,
+    /// Write the generated id when there is no `column_id`, for other nodes to refer to
+    expose_id: bool,
+    semantics: PuppetSemantics,
+    attributes: PuppetAttributes,
     style: PuppetStyle,
+    scoped_style: Option<PuppetScopedStyle>,
     rows: Vec<PuppetRow>,
 }
 
@@ -89,109 +153,360 @@ impl PuppetColumn {
     pub fn new() -> Self {
         Self {
             column_id: Option::default(),
+            puppet_id: PuppetId::generate(),
+            expose_id: false,
+            semantics: PuppetSemantics::default(),
+            attributes: PuppetAttributes::default(),
             style: PuppetStyle::default(),
+            scoped_style: Option::default(),
             rows: Vec::default(),
         }
     }
     pub fn column_id(&mut self, id: &'static str) -> &mut Self {
-        if !id.is_empty() {
+        if is_valid_id(id) {
             self.column_id = Some(id);
 
             self
         } else {
+            if !id.is_empty() {
+                trace!("INVALID ID {:?}. IDS CANNOT CONTAIN WHITESPACE", id);
+            }
             self.column_id = None;
 
             self
         }
     }
 
+    /// The `column_id`, or else the generated id
+    pub fn id(&self) -> Cow<'static, str> {
+        match self.column_id {
+            Some(column_id) => Cow::Borrowed(column_id),
+            None => Cow::Owned(self.puppet_id.to_html_id()),
+        }
+    }
+
+    /// Write the generated id even though nothing refers to it, see [PuppetColumn::id]
+    pub fn expose_id(&mut self) -> &mut Self {
+        self.expose_id = true;
+
+        self
+    }
+
+    /// The element the column is written as, rejected when it does not allow the
+    /// role or ARIA attributes already set
+    pub fn tag(&mut self, tag: LayoutTag) -> &mut Self {
+        if tag == LayoutTag::ListItem {
+            trace!("INVALID TAG {} FOR A COLUMN", tag);
+        } else {
+            self.semantics.set_tag(tag);
+        }
+
+        self
+    }
+
+    /// Rejected when the tag or ARIA attributes already set do not allow it
+    pub fn role(&mut self, role: PuppetRole) -> &mut Self {
+        self.semantics.set_role(role);
+
+        self
+    }
+
+    /// Set an ARIA attribute, rejected when the role does not allow it
+    pub fn aria(&mut self, attribute: AriaAttribute) -> &mut Self {
+        self.semantics.set_attribute(attribute);
+
+        self
+    }
+
+    pub fn semantics(&self) -> &PuppetSemantics {
+        &self.semantics
+    }
+
+    /// Set an attribute, rejected when it is not valid
+    pub fn attribute(&mut self, attribute: PuppetAttribute) -> &mut Self {
+        self.attributes.set(attribute);
+
+        self
+    }
+
+    /// Set the `data-{key}` attribute, a hook for analytics and tests
+    pub fn data<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.attributes.set_data(key.into(), value.into());
+
+        self
+    }
+
+    pub fn attributes(&self) -> &PuppetAttributes {
+        &self.attributes
+    }
+
     pub fn set_style(&mut self, style: &PuppetStyle) -> &mut Self {
         self.style = *style;
 
         self
     }
 
-    pub fn to_html(&self, document: &Document, app_node: &Element) -> JsValueResult<()> {
-        //-> HtmlDivElement {
-        let div = document.create_element("div")?;
+    /// Rules relative to this node for what its style cannot express, see
+    /// [PuppetScopedStyle]
+    pub fn scoped_style(&mut self, scoped_style: &PuppetScopedStyle) -> &mut Self {
+        self.scoped_style = Some(scoped_style.clone());
 
-        let div = div.dyn_ref::<HtmlElement>().ok_or(JsValue::from_str(
-            "Could not convert `Element` to `HtmlElement`",
-        ));
-        let div = div?;
+        self
+    }
 
-        match self.column_id {
-            Some(column_id) => div.set_id(column_id),
-            None => (),
+    pub fn add_row(&mut self, row: PuppetRow) -> &mut Self {
+        self.rows.push(row);
+
+        self
+    }
+
+    pub fn into_rows(self) -> Vec<PuppetRow> {
+        self.rows
+    }
+
+    pub fn extend_rows<I>(&mut self, rows: I) -> &mut Self
+    where
+        I: IntoIterator<Item = PuppetRow>,
+    {
+        self.rows.extend(rows);
+
+        self
+    }
+
+    pub fn to_element(&self) -> PuppetElement {
+        let tag = self.semantics.get_tag();
+        let mut element = PuppetElement::new(tag.to_html_tag());
+
+        if self.column_id.is_some() || self.expose_id {
+            element.attribute("id", self.id());
+        }
+
+        self.semantics.write_attributes(tag, &mut element);
+        self.attributes.write_attributes(&mut element);
+        element.set_style(&self.style);
+
+        if let Some(scoped_style) = &self.scoped_style {
+            element.scoped_style(scoped_style);
+        }
+
+        for puppet_row in &self.rows {
+            element.add_child(puppet_row.to_element_within(tag));
         }
 
-        self.style.to_html(&div)?;
+        element
+    }
+
+    /// Render the column into `app_node`, returning the ids written more than once,
+    /// see [PuppetElement::duplicate_ids]
+    pub fn to_html(&self, document: &Document, app_node: &Element) -> JsValueResult<Vec<String>> {
+        let element = self.to_element();
+        let duplicates = element.warn_duplicate_ids();
+
+        element.to_html(document, app_node)?;
+
+        Ok(duplicates)
+    }
+
+    /// The column as HTML markup, logging the ids written more than once
+    pub fn to_html_string(&self) -> String {
+        let element = self.to_element();
+        element.warn_duplicate_ids();
 
-        self.rows.iter().for_each(|puppet_row| {
-            div.append_with_str_1(&puppet_row.to_html());
-        });
+        element.to_html_string()
+    }
 
-        app_node.append_with_node_1(div)?;
+    pub fn compute_layout(&self, viewport: &PuppetViewport) -> PuppetBox {
+        self.to_element().compute_layout(viewport)
+    }
+}
 
-        Ok(())
+impl Default for PuppetColumn {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct PuppetRow {
     column_id: Option<&'static str>,
+    puppet_id: PuppetId,
+    /// Write the generated id when there is no `column_id`, for other nodes to refer to
+    expose_id: bool,
+    semantics: PuppetSemantics,
+    attributes: PuppetAttributes,
     style: PuppetStyle,
+    scoped_style: Option<PuppetScopedStyle>,
     components: Vec<Component>,
 }
 
-
 impl PuppetRow {
     pub fn new() -> Self {
         Self {
             column_id: Option::default(),
+            puppet_id: PuppetId::generate(),
+            expose_id: false,
+            semantics: PuppetSemantics::default(),
+            attributes: PuppetAttributes::default(),
             style: PuppetStyle::default(),
+            scoped_style: Option::default(),
             components: Vec::default(),
         }
     }
     pub fn column_id(&mut self, id: &'static str) -> &mut Self {
-        if !id.is_empty() {
+        if is_valid_id(id) {
             self.column_id = Some(id);
 
             self
         } else {
+            if !id.is_empty() {
+                trace!("INVALID ID {:?}. IDS CANNOT CONTAIN WHITESPACE", id);
+            }
             self.column_id = None;
 
             self
         }
     }
 
+    /// The `column_id`, or else the generated id
+    pub fn id(&self) -> Cow<'static, str> {
+        match self.column_id {
+            Some(column_id) => Cow::Borrowed(column_id),
+            None => Cow::Owned(self.puppet_id.to_html_id()),
+        }
+    }
+
+    /// Write the generated id even though nothing refers to it, see [PuppetRow::id]
+    pub fn expose_id(&mut self) -> &mut Self {
+        self.expose_id = true;
+
+        self
+    }
+
+    /// The element the row is written as, rejected when it does not allow the
+    /// role or ARIA attributes already set. Rows of a [LayoutTag::List] column are
+    /// always list items
+    pub fn tag(&mut self, tag: LayoutTag) -> &mut Self {
+        if tag == LayoutTag::List {
+            trace!("INVALID TAG {} FOR A ROW", tag);
+        } else {
+            self.semantics.set_tag(tag);
+        }
+
+        self
+    }
+
+    /// Rejected when the tag or ARIA attributes already set do not allow it
+    pub fn role(&mut self, role: PuppetRole) -> &mut Self {
+        self.semantics.set_role(role);
+
+        self
+    }
+
+    /// Set an ARIA attribute, rejected when the role does not allow it
+    pub fn aria(&mut self, attribute: AriaAttribute) -> &mut Self {
+        self.semantics.set_attribute(attribute);
+
+        self
+    }
+
+    pub fn semantics(&self) -> &PuppetSemantics {
+        &self.semantics
+    }
+
+    /// Set an attribute, rejected when it is not valid
+    pub fn attribute(&mut self, attribute: PuppetAttribute) -> &mut Self {
+        self.attributes.set(attribute);
+
+        self
+    }
+
+    /// Set the `data-{key}` attribute, a hook for analytics and tests
+    pub fn data<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.attributes.set_data(key.into(), value.into());
+
+        self
+    }
+
+    pub fn attributes(&self) -> &PuppetAttributes {
+        &self.attributes
+    }
+
     pub fn set_style(&mut self, style: &PuppetStyle) -> &mut Self {
         self.style = *style;
 
         self
     }
 
-    pub fn to_html(&self, document: &Document) -> JsValueResult<()> {
-        //-> HtmlDivElement {
-        let div = document.create_element("div")?;
+    /// Rules relative to this node for what its style cannot express, see
+    /// [PuppetScopedStyle]
+    pub fn scoped_style(&mut self, scoped_style: &PuppetScopedStyle) -> &mut Self {
+        self.scoped_style = Some(scoped_style.clone());
+
+        self
+    }
+
+    pub fn add_component(&mut self, component: Component) -> &mut Self {
+        self.components.push(component);
+
+        self
+    }
 
-        let div = div.dyn_ref::<HtmlElement>().ok_or(JsValue::from_str(
-            "Could not convert `Element` to `HtmlElement`",
-        ));
-        let div = div?;
+    pub fn extend_components<I>(&mut self, components: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Component>,
+    {
+        self.components.extend(components);
 
-        match self.column_id {
-            Some(column_id) => div.set_id(column_id),
-            None => (),
+        self
+    }
+
+    pub fn to_element(&self) -> PuppetElement {
+        self.to_element_within(LayoutTag::Div)
+    }
+
+    /// The element of the row within a column of the tag `parent`
+    pub(crate) fn to_element_within(&self, parent: LayoutTag) -> PuppetElement {
+        let tag = self.semantics.get_tag().within(parent);
+        let mut element = PuppetElement::new(tag.to_html_tag());
+
+        if self.column_id.is_some() || self.expose_id {
+            element.attribute("id", self.id());
+        }
+
+        self.semantics.write_attributes(tag, &mut element);
+        self.attributes.write_attributes(&mut element);
+        element.set_style(&self.style);
+
+        if let Some(scoped_style) = &self.scoped_style {
+            element.scoped_style(scoped_style);
         }
 
-        self.style.to_html(&div)?;
+        for component in &self.components {
+            element.add_child(component.to_element());
+        }
+
+        element
+    }
 
-        self.components.iter().for_each(|puppet_row| {
-            div.append_with_str_1(&puppet_row.to_html());
-        });
+    pub fn to_html(&self, document: &Document, parent_node: &Element) -> JsValueResult<()> {
+        self.to_element().to_html(document, parent_node)
+    }
 
-        app_node.append_with_node_1(div)?;
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+}
 
-        Ok(())
-    }
\ No newline at end of file
+impl Default for PuppetRow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,255 @@
+/// Declare a [crate::PuppetColumn], [crate::PuppetRow] or [crate::Component] tree
+///
+/// Every node is written as `kind { body }`. A body is a list of items, optionally
+/// separated by commas:
+///
+/// - `id(expr)` sets the id of a `column`, `row` or component
+/// - `tag(expr)`, `role(expr)` and `aria(expr)` give a `column` or `row` its
+///   [crate::LayoutTag], [crate::PuppetRole] and ARIA attributes
+/// - `attribute(expr)` sets a [crate::PuppetAttribute] and `data(key, value)` a
+///   `data-*` attribute on any node
+/// - `variants(expr)` and `variant(expr)` extend the style of a component with a
+///   [crate::PuppetStyleVariants] and pick one of its [crate::PuppetVariant]
+/// - `style(expr)` uses an existing [crate::PuppetStyle]
+/// - `scoped(expr)` adds the rules of a [crate::PuppetScopedStyle] to any node
+/// - `style { property: expr, .. }` builds a style through its setters, see [puppet_style]
+/// - `row { .. }` adds a row to a `column`
+/// - `button(expr)`, `paragraph(expr)`, `heading(expr)`, `image(expr)` and `link(expr)`
+///   add a component to a `row`, optionally followed by a `{ body }`
+/// - `extend(expr)` appends any iterator of rows to a `column` or of components to a `row`
+/// - `href(expr)` sets the target of a `link`
+/// - `tab_index(expr)` overrides where a component sits in the tab order
+/// - `src(expr)` sets the source of an `image`
+/// - `raw_html(expr)` writes trusted markup in place of the text of a component
+/// - `model(expr)` describes an `image` by a [crate::PuppetImage], with its
+///   responsive sources, size and loading
+/// - `on event => expr` listens for a DOM event on a component
+///
+/// ```
+/// use puppeteer_builders::{puppet, PuppetColor, PuppetLength};
+///
+/// let greeting = "Hello";
+///
+/// let column = puppet! {
+///     column {
+///         id("foo")
+///         style { width: PuppetLength::Half, background_color: PuppetColor::Rgb(0, 0, 0) }
+///         row {
+///             heading("Puppeteer")
+///             paragraph(greeting)
+///             button("Click me") {
+///                 on click => |_event| log::trace!("clicked"),
+///             }
+///         }
+///     }
+/// };
+/// ```
+#[macro_export]
+macro_rules! puppet {
+    (column { $($body:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut column = $crate::PuppetColumn::new();
+        $crate::puppet!(@column column $($body)*);
+        column
+    }};
+    (row { $($body:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut row = $crate::PuppetRow::new();
+        $crate::puppet!(@row row $($body)*);
+        row
+    }};
+    ($kind:ident ( $text:expr ) $({ $($body:tt)* })?) => {{
+        #[allow(unused_mut)]
+        let mut component = $crate::Component::new($crate::puppet!(@type $kind), $text);
+        $crate::puppet!(@component component $($($body)*)?);
+        component
+    }};
+
+    (@type button) => { $crate::ComponentType::Button };
+    (@type paragraph) => { $crate::ComponentType::Paragraph };
+    (@type heading) => { $crate::ComponentType::Heading };
+    (@type image) => { $crate::ComponentType::Image };
+    (@type link) => { $crate::ComponentType::Link };
+
+    (@column $node:ident) => {};
+    (@column $node:ident , $($rest:tt)*) => {
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident id ( $id:expr ) $($rest:tt)*) => {
+        $node.column_id($id);
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident tag ( $tag:expr ) $($rest:tt)*) => {
+        $node.tag($tag);
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident role ( $role:expr ) $($rest:tt)*) => {
+        $node.role($role);
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident aria ( $attribute:expr ) $($rest:tt)*) => {
+        $node.aria($attribute);
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident attribute ( $attribute:expr ) $($rest:tt)*) => {
+        $node.attribute($attribute);
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident data ( $key:expr , $value:expr ) $($rest:tt)*) => {
+        $node.data($key, $value);
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident scoped ( $scoped_style:expr ) $($rest:tt)*) => {
+        $node.scoped_style(&$scoped_style);
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident style $style:tt $($rest:tt)*) => {
+        $node.set_style(&$crate::puppet!(@style $style));
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident row { $($body:tt)* } $($rest:tt)*) => {
+        $node.add_row($crate::puppet!(row { $($body)* }));
+        $crate::puppet!(@column $node $($rest)*);
+    };
+    (@column $node:ident extend ( $rows:expr ) $($rest:tt)*) => {
+        $node.extend_rows($rows);
+        $crate::puppet!(@column $node $($rest)*);
+    };
+
+    (@row $node:ident) => {};
+    (@row $node:ident , $($rest:tt)*) => {
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident id ( $id:expr ) $($rest:tt)*) => {
+        $node.column_id($id);
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident tag ( $tag:expr ) $($rest:tt)*) => {
+        $node.tag($tag);
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident role ( $role:expr ) $($rest:tt)*) => {
+        $node.role($role);
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident aria ( $attribute:expr ) $($rest:tt)*) => {
+        $node.aria($attribute);
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident attribute ( $attribute:expr ) $($rest:tt)*) => {
+        $node.attribute($attribute);
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident data ( $key:expr , $value:expr ) $($rest:tt)*) => {
+        $node.data($key, $value);
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident scoped ( $scoped_style:expr ) $($rest:tt)*) => {
+        $node.scoped_style(&$scoped_style);
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident style $style:tt $($rest:tt)*) => {
+        $node.set_style(&$crate::puppet!(@style $style));
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident extend ( $components:expr ) $($rest:tt)*) => {
+        $node.extend_components($components);
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident $kind:ident ( $text:expr ) { $($body:tt)* } $($rest:tt)*) => {
+        $node.add_component($crate::puppet!($kind ( $text ) { $($body)* }));
+        $crate::puppet!(@row $node $($rest)*);
+    };
+    (@row $node:ident $kind:ident ( $text:expr ) $($rest:tt)*) => {
+        $node.add_component($crate::puppet!($kind ( $text )));
+        $crate::puppet!(@row $node $($rest)*);
+    };
+
+    (@component $node:ident) => {};
+    (@component $node:ident , $($rest:tt)*) => {
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident attribute ( $attribute:expr ) $($rest:tt)*) => {
+        $node.attribute($attribute);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident data ( $key:expr , $value:expr ) $($rest:tt)*) => {
+        $node.data($key, $value);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident scoped ( $scoped_style:expr ) $($rest:tt)*) => {
+        $node.scoped_style(&$scoped_style);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident style $style:tt $($rest:tt)*) => {
+        $node.set_style(&$crate::puppet!(@style $style));
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident id ( $id:expr ) $($rest:tt)*) => {
+        $node.component_id($id);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident tab_index ( $tab_index:expr ) $($rest:tt)*) => {
+        $node.tab_index($tab_index);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident href ( $href:expr ) $($rest:tt)*) => {
+        $node.href($href);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident variants ( $variants:expr ) $($rest:tt)*) => {
+        $node.style_variants(&$variants);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident variant ( $variant:expr ) $($rest:tt)*) => {
+        $node.variant($variant);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident raw_html ( $html:expr ) $($rest:tt)*) => {
+        $node.raw_html($html);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident src ( $src:expr ) $($rest:tt)*) => {
+        $node.src($src);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident model ( $image:expr ) $($rest:tt)*) => {
+        $node.image($image);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+    (@component $node:ident on $event:ident => $handler:expr) => {
+        $node.on(stringify!($event), $handler);
+    };
+    (@component $node:ident on $event:ident => $handler:expr , $($rest:tt)*) => {
+        $node.on(stringify!($event), $handler);
+        $crate::puppet!(@component $node $($rest)*);
+    };
+
+    (@style ( $style:expr )) => { $style };
+    (@style { $($properties:tt)* }) => { $crate::puppet_style! { $($properties)* } };
+}
+
+/// Build a [crate::PuppetStyle] by calling one setter per `property: expr` pair,
+/// starting either from the default style or from `..base`
+///
+/// ```
+/// use puppeteer_builders::{puppet_style, PuppetLength, PuppetUnit};
+///
+/// let base = puppet_style! { width: PuppetLength::Half };
+/// let style = puppet_style! { ..base, min_width: PuppetUnit::Pixels(50) };
+/// ```
+#[macro_export]
+macro_rules! puppet_style {
+    (.. $base:expr $(, $property:ident : $value:expr)* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut style: $crate::PuppetStyle = $base;
+        $(style.$property($value);)*
+        style
+    }};
+    ($($property:ident : $value:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut style = $crate::PuppetStyle::default();
+        $(style.$property($value);)*
+        style
+    }};
+}
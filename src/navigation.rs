@@ -0,0 +1,759 @@
+use crate::{
+    current_direction, get_window, Component, ComponentType, JsValueResult, PuppetChild,
+    PuppetColumn, PuppetDirection, PuppetElement, PuppetRow, PuppetStyle, PuppetText,
+};
+use core::fmt::Debug;
+use log::trace;
+use std::borrow::Cow;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Document, Element, Event, HtmlElement, KeyboardEvent, Node};
+
+/// Width in pixels under which a [PuppetNavbar] collapses its links by default
+pub const NAVBAR_COLLAPSE_WIDTH: u32 = 640;
+
+const NAVBAR_TOGGLE_LABEL: &str = "Menu";
+const BREADCRUMB_LABEL: &str = "Breadcrumb";
+
+/// A link of a navigation component
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetNavLink {
+    label: PuppetText,
+    href: Cow<'static, str>,
+    current: bool,
+}
+
+impl PuppetNavLink {
+    pub fn new<L, H>(label: L, href: H) -> Self
+    where
+        L: Into<PuppetText>,
+        H: Into<Cow<'static, str>>,
+    {
+        Self {
+            label: label.into(),
+            href: href.into(),
+            current: false,
+        }
+    }
+
+    /// Mark the link as the page being shown, `aria-current="page"`
+    pub fn current(&mut self, current: bool) -> &mut Self {
+        self.current = current;
+
+        self
+    }
+
+    pub fn is_current(&self) -> bool {
+        self.current
+    }
+
+    pub fn to_component(&self) -> Component {
+        let mut link = Component::new(ComponentType::Link, self.label.clone());
+        link.href(self.href.clone());
+
+        link
+    }
+
+    pub fn to_element(&self) -> PuppetElement {
+        let mut element = self.to_component().to_element();
+
+        if self.current {
+            element.attribute("aria-current", "page");
+        }
+
+        element
+    }
+}
+
+/// A top navigation bar whose links collapse behind a menu button on narrow screens
+///
+/// ```
+/// use puppeteer_builders::{PuppetNavLink, PuppetNavbar};
+///
+/// let mut home = PuppetNavLink::new("Home", "/");
+/// home.current(true);
+///
+/// let mut navbar = PuppetNavbar::new("main-nav");
+/// navbar.brand("Puppeteer").add_link(home).add_link(PuppetNavLink::new("Docs", "/docs"));
+///
+/// assert!(navbar.to_html_string().contains(r#"<a href="/" aria-current="page""#));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetNavbar {
+    navbar_id: &'static str,
+    label: PuppetText,
+    brand: Option<PuppetNavLink>,
+    links: Vec<PuppetNavLink>,
+    style: PuppetStyle,
+    collapse_width: u32,
+}
+
+impl PuppetNavbar {
+    pub fn new(navbar_id: &'static str) -> Self {
+        Self {
+            navbar_id,
+            label: "Main".into(),
+            brand: Option::default(),
+            links: Vec::default(),
+            style: PuppetStyle::default(),
+            collapse_width: NAVBAR_COLLAPSE_WIDTH,
+        }
+    }
+
+    /// The accessible name of the navigation landmark, `Main` by default
+    pub fn label<T>(&mut self, label: T) -> &mut Self
+    where
+        T: Into<PuppetText>,
+    {
+        self.label = label.into();
+
+        self
+    }
+
+    /// The name of the app, linking to `/`
+    pub fn brand<T>(&mut self, brand: T) -> &mut Self
+    where
+        T: Into<PuppetText>,
+    {
+        self.brand = Some(PuppetNavLink::new(brand, "/"));
+
+        self
+    }
+
+    pub fn add_link(&mut self, link: PuppetNavLink) -> &mut Self {
+        self.links.push(link);
+
+        self
+    }
+
+    pub fn set_style(&mut self, style: &PuppetStyle) -> &mut Self {
+        self.style = *style;
+
+        self
+    }
+
+    /// Collapse the links behind the menu button when the window is narrower
+    pub fn collapse_width(&mut self, collapse_width: u32) -> &mut Self {
+        self.collapse_width = collapse_width;
+
+        self
+    }
+
+    fn links_id(&self) -> String {
+        format!("{}-links", self.navbar_id)
+    }
+
+    /// The expanded navbar, the menu button being hidden until the DOM backend
+    /// collapses it
+    pub fn to_element(&self) -> PuppetElement {
+        let mut links = PuppetRow::new();
+        links.extend_components(self.links.iter().map(PuppetNavLink::to_component));
+        let mut links = links.to_element();
+        links.attribute("id", self.links_id());
+        mark_current(&mut links, &self.links);
+
+        let mut toggle = Component::new(ComponentType::Button, NAVBAR_TOGGLE_LABEL).to_element();
+        toggle
+            .attribute("type", "button")
+            .attribute("aria-controls", self.links_id())
+            .attribute("aria-expanded", "true")
+            .attribute("hidden", "");
+
+        let mut bar = PuppetRow::new();
+        bar.set_style(&self.style);
+        let mut bar = bar.to_element();
+        if let Some(brand) = &self.brand {
+            bar.add_child(brand.to_element());
+        }
+        bar.add_child(toggle).add_child(links);
+
+        let mut navbar = PuppetElement::new("nav");
+        navbar
+            .attribute("id", self.navbar_id)
+            .attribute("aria-label", self.label.clone())
+            .add_child(bar);
+
+        navbar
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+
+    /// Render the navbar and collapse it whenever the window is narrower than
+    /// [PuppetNavbar::collapse_width]
+    pub fn to_html(&self, document: &Document, parent_node: &Element) -> JsValueResult<()> {
+        self.to_element().to_html(document, parent_node)?;
+
+        let navbar = last_element(parent_node)?;
+        let toggle = navbar
+            .query_selector("button[aria-controls]")?
+            .ok_or(JsValue::from_str("Could not find the navbar toggle"))?;
+        let links = navbar
+            .query_selector(&format!("#{}", self.links_id()))?
+            .ok_or(JsValue::from_str("Could not find the navbar links"))?;
+
+        let collapse_width = f64::from(self.collapse_width);
+        let resize_toggle = toggle.clone();
+        let resize_links = links.clone();
+        let collapse = move || -> JsValueResult<()> {
+            let width = get_window().inner_width()?.as_f64().unwrap_or(f64::MAX);
+            let collapsed = width < collapse_width;
+
+            set_hidden(&resize_toggle, !collapsed)?;
+            resize_toggle.set_attribute("aria-expanded", &(!collapsed).to_string())?;
+            set_hidden(&resize_links, collapsed)
+        };
+        collapse()?;
+
+        listen(&get_window(), "resize", move |_event| collapse())?;
+
+        let click_toggle = toggle.clone();
+        listen(&toggle, "click", move |_event| {
+            let expanded = click_toggle.get_attribute("aria-expanded").as_deref() == Some("true");
+
+            click_toggle.set_attribute("aria-expanded", &(!expanded).to_string())?;
+            set_hidden(&links, expanded)
+        })
+    }
+}
+
+/// A panel of a [PuppetTabs]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetTab {
+    label: PuppetText,
+    panel: PuppetColumn,
+}
+
+impl PuppetTab {
+    pub fn new<T>(label: T, panel: PuppetColumn) -> Self
+    where
+        T: Into<PuppetText>,
+    {
+        Self {
+            label: label.into(),
+            panel,
+        }
+    }
+}
+
+/// A tab strip showing one panel at a time, moved through with the arrow keys
+///
+/// ```
+/// use puppeteer_builders::{puppet, PuppetTab, PuppetTabs};
+///
+/// let mut tabs = PuppetTabs::new("settings");
+/// tabs.add_tab(PuppetTab::new("Profile", puppet! { column {} }))
+///     .add_tab(PuppetTab::new("Security", puppet! { column {} }))
+///     .select(1);
+///
+/// let html = tabs.to_html_string();
+/// assert!(html.contains(r#"id="settings-tab-1" aria-selected="true""#));
+/// assert!(html.contains(r#"id="settings-panel-0" aria-labelledby="settings-tab-0" tabindex="0" hidden="""#));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetTabs {
+    tabs_id: &'static str,
+    label: Option<PuppetText>,
+    tabs: Vec<PuppetTab>,
+    selected: usize,
+}
+
+impl PuppetTabs {
+    pub fn new(tabs_id: &'static str) -> Self {
+        Self {
+            tabs_id,
+            label: Option::default(),
+            tabs: Vec::default(),
+            selected: 0,
+        }
+    }
+
+    /// The accessible name of the tab list
+    pub fn label<T>(&mut self, label: T) -> &mut Self
+    where
+        T: Into<PuppetText>,
+    {
+        self.label = Some(label.into());
+
+        self
+    }
+
+    pub fn add_tab(&mut self, tab: PuppetTab) -> &mut Self {
+        self.tabs.push(tab);
+
+        self
+    }
+
+    /// Show the panel at `index`, clamped to the last tab
+    pub fn select(&mut self, index: usize) -> &mut Self {
+        self.selected = index.min(self.tabs.len().saturating_sub(1));
+
+        self
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    fn tab_id(&self, index: usize) -> String {
+        format!("{}-tab-{}", self.tabs_id, index)
+    }
+
+    fn panel_id(&self, index: usize) -> String {
+        format!("{}-panel-{}", self.tabs_id, index)
+    }
+
+    pub fn to_element(&self) -> PuppetElement {
+        let mut tab_list = PuppetRow::new();
+        tab_list.extend_components(
+            self.tabs
+                .iter()
+                .map(|tab| Component::new(ComponentType::Button, tab.label.clone())),
+        );
+        let mut tab_list = tab_list.to_element();
+        tab_list.attribute("role", "tablist");
+        if let Some(label) = &self.label {
+            tab_list.attribute("aria-label", label.clone());
+        }
+
+        for (index, child) in tab_list.children_mut().iter_mut().enumerate() {
+            if let PuppetChild::Element(tab) = child {
+                let selected = index == self.selected;
+
+                tab.attribute("type", "button")
+                    .attribute("role", "tab")
+                    .attribute("id", self.tab_id(index))
+                    .attribute("aria-selected", selected.to_string())
+                    .attribute("aria-controls", self.panel_id(index))
+                    // Only the selected tab is in the tab order, arrows move between tabs
+                    .attribute("tabindex", if selected { "0" } else { "-1" });
+            }
+        }
+
+        let mut tabs = PuppetElement::new("div");
+        tabs.attribute("id", self.tabs_id).add_child(tab_list);
+
+        for (index, tab) in self.tabs.iter().enumerate() {
+            let mut panel = PuppetElement::new("div");
+            panel
+                .attribute("role", "tabpanel")
+                .attribute("id", self.panel_id(index))
+                .attribute("aria-labelledby", self.tab_id(index))
+                .attribute("tabindex", "0");
+            if index != self.selected {
+                panel.attribute("hidden", "");
+            }
+            panel.add_child(tab.panel.to_element());
+
+            tabs.add_child(panel);
+        }
+
+        tabs
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+
+    /// Render the tabs and switch panels on clicks and on the arrow, Home and End
+    /// keys, arrows following the text direction
+    pub fn to_html(&self, document: &Document, parent_node: &Element) -> JsValueResult<()> {
+        self.to_element().to_html(document, parent_node)?;
+
+        let tabs = last_element(parent_node)?;
+
+        let click_tabs = tabs.clone();
+        listen(&tabs, "click", move |event| {
+            match event_target(&event)?.closest(r#"[role="tab"]"#)? {
+                Some(tab) => select_tab(&click_tabs, &tab, false),
+                None => Ok(()),
+            }
+        })?;
+
+        let key_tabs = tabs.clone();
+        listen(&tabs, "keydown", move |event| {
+            let Some(keyboard_event) = event.dyn_ref::<KeyboardEvent>() else {
+                return Ok(());
+            };
+            let Some(current) = event_target(&event)?.closest(r#"[role="tab"]"#)? else {
+                return Ok(());
+            };
+
+            let all_tabs = elements(&key_tabs, r#"[role="tab"]"#)?;
+            let Some(position) = all_tabs
+                .iter()
+                .position(|tab| tab.is_same_node(Some(&current)))
+            else {
+                return Ok(());
+            };
+
+            let (previous_key, next_key) = match current_direction() {
+                PuppetDirection::Ltr => ("ArrowLeft", "ArrowRight"),
+                PuppetDirection::Rtl => ("ArrowRight", "ArrowLeft"),
+            };
+            let last = all_tabs.len() - 1;
+            let target = match keyboard_event.key().as_str() {
+                key if key == previous_key => position.checked_sub(1).unwrap_or(last),
+                key if key == next_key => (position + 1) % all_tabs.len(),
+                "Home" => 0,
+                "End" => last,
+                _ => return Ok(()),
+            };
+
+            event.prevent_default();
+            select_tab(&key_tabs, &all_tabs[target], true)
+        })
+    }
+}
+
+fn select_tab(tabs: &Element, selected: &Element, focus: bool) -> JsValueResult<()> {
+    for tab in elements(tabs, r#"[role="tab"]"#)? {
+        let is_selected = tab.is_same_node(Some(selected));
+
+        tab.set_attribute("aria-selected", &is_selected.to_string())?;
+        tab.set_attribute("tabindex", if is_selected { "0" } else { "-1" })?;
+
+        if let Some(panel_id) = tab.get_attribute("aria-controls") {
+            if let Some(panel) = tabs.query_selector(&format!("#{}", panel_id))? {
+                set_hidden(&panel, !is_selected)?;
+            }
+        }
+
+        if is_selected && focus {
+            if let Some(tab) = tab.dyn_ref::<HtmlElement>() {
+                tab.focus()?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The trail of pages leading to the current one, the last being current
+///
+/// ```
+/// use puppeteer_builders::{PuppetBreadcrumbs, PuppetNavLink};
+///
+/// let mut breadcrumbs = PuppetBreadcrumbs::new();
+/// breadcrumbs
+///     .add_link(PuppetNavLink::new("Home", "/"))
+///     .add_link(PuppetNavLink::new("Users", "/users"));
+///
+/// let html = breadcrumbs.to_html_string();
+/// assert!(html.contains(r#"<a href="/users" aria-current="page""#));
+/// assert!(html.ends_with("Users</a></li></ol></nav>"));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetBreadcrumbs {
+    links: Vec<PuppetNavLink>,
+}
+
+impl PuppetBreadcrumbs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_link(&mut self, link: PuppetNavLink) -> &mut Self {
+        self.links.push(link);
+
+        self
+    }
+
+    pub fn to_element(&self) -> PuppetElement {
+        let mut list = PuppetElement::new("ol");
+
+        for (index, link) in self.links.iter().enumerate() {
+            let mut link = link.clone();
+            link.current(index + 1 == self.links.len());
+
+            let mut item = PuppetElement::new("li");
+            item.add_child(link.to_element());
+            list.add_child(item);
+        }
+
+        let mut breadcrumbs = PuppetElement::new("nav");
+        breadcrumbs
+            .attribute("aria-label", BREADCRUMB_LABEL)
+            .add_child(list);
+
+        breadcrumbs
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+
+    pub fn to_html(&self, document: &Document, parent_node: &Element) -> JsValueResult<()> {
+        self.to_element().to_html(document, parent_node)
+    }
+}
+
+/// A button opening a list of links, moved through with the arrow keys
+///
+/// ```
+/// use puppeteer_builders::{PuppetMenu, PuppetNavLink};
+///
+/// let mut menu = PuppetMenu::new("account", "Account");
+/// menu.add_link(PuppetNavLink::new("Profile", "/profile"))
+///     .add_link(PuppetNavLink::new("Sign out", "/sign-out"));
+///
+/// let html = menu.to_html_string();
+/// assert!(html.contains(r#"aria-haspopup="menu" aria-expanded="false" aria-controls="account-menu""#));
+/// assert!(html.contains(r#"<a href="/profile" role="menuitem" tabindex="-1""#));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetMenu {
+    menu_id: &'static str,
+    label: PuppetText,
+    links: Vec<PuppetNavLink>,
+}
+
+impl PuppetMenu {
+    pub fn new<T>(menu_id: &'static str, label: T) -> Self
+    where
+        T: Into<PuppetText>,
+    {
+        Self {
+            menu_id,
+            label: label.into(),
+            links: Vec::default(),
+        }
+    }
+
+    pub fn add_link(&mut self, link: PuppetNavLink) -> &mut Self {
+        self.links.push(link);
+
+        self
+    }
+
+    fn list_id(&self) -> String {
+        format!("{}-menu", self.menu_id)
+    }
+
+    /// The closed menu
+    pub fn to_element(&self) -> PuppetElement {
+        let mut button = Component::new(ComponentType::Button, self.label.clone()).to_element();
+        button
+            .attribute("type", "button")
+            .attribute("id", format!("{}-button", self.menu_id))
+            .attribute("aria-haspopup", "menu")
+            .attribute("aria-expanded", "false")
+            .attribute("aria-controls", self.list_id());
+
+        let mut list = PuppetColumn::new();
+        for link in &self.links {
+            let mut row = PuppetRow::new();
+            row.add_component(link.to_component());
+            list.add_row(row);
+        }
+        let mut list = list.to_element();
+        list.attribute("id", self.list_id())
+            .attribute("role", "menu")
+            .attribute("aria-labelledby", format!("{}-button", self.menu_id))
+            .attribute("hidden", "");
+
+        for (row, link) in list.children_mut().iter_mut().zip(&self.links) {
+            if let PuppetChild::Element(row) = row {
+                row.attribute("role", "none");
+
+                if let Some(PuppetChild::Element(item)) = row.children_mut().first_mut() {
+                    item.attribute("role", "menuitem")
+                        .attribute("tabindex", "-1");
+                    if link.current {
+                        item.attribute("aria-current", "page");
+                    }
+                }
+            }
+        }
+
+        let mut menu = PuppetElement::new("div");
+        menu.attribute("id", self.menu_id)
+            .add_child(button)
+            .add_child(list);
+
+        menu
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+
+    /// Render the menu, opened by its button or the arrow keys and closed by Escape,
+    /// a click outside of it or choosing a link
+    pub fn to_html(&self, document: &Document, parent_node: &Element) -> JsValueResult<()> {
+        self.to_element().to_html(document, parent_node)?;
+
+        let menu = last_element(parent_node)?;
+
+        let click_menu = menu.clone();
+        listen(&menu, "click", move |event| {
+            let target = event_target(&event)?;
+
+            if target.closest("[aria-haspopup]")?.is_some() {
+                let expanded = is_expanded(&click_menu)?;
+                return toggle_menu(&click_menu, !expanded, false);
+            }
+
+            if target.closest(r#"[role="menuitem"]"#)?.is_some() {
+                return toggle_menu(&click_menu, false, false);
+            }
+
+            Ok(())
+        })?;
+
+        let key_menu = menu.clone();
+        listen(&menu, "keydown", move |event| {
+            let Some(keyboard_event) = event.dyn_ref::<KeyboardEvent>() else {
+                return Ok(());
+            };
+
+            let items = elements(&key_menu, r#"[role="menuitem"]"#)?;
+            if items.is_empty() {
+                return Ok(());
+            }
+            let target = event_target(&event)?;
+            let position = items
+                .iter()
+                .position(|item| item.is_same_node(Some(&target)));
+
+            let focused = match (keyboard_event.key().as_str(), position) {
+                ("Escape", _) => {
+                    event.prevent_default();
+                    return toggle_menu(&key_menu, false, true);
+                }
+                ("Tab", _) => return toggle_menu(&key_menu, false, false),
+                ("ArrowDown" | "Enter" | " ", None) => {
+                    event.prevent_default();
+                    return toggle_menu(&key_menu, true, true);
+                }
+                ("ArrowUp", None) => {
+                    toggle_menu(&key_menu, true, false)?;
+                    items.len() - 1
+                }
+                ("ArrowDown", Some(position)) => (position + 1) % items.len(),
+                ("ArrowUp", Some(position)) => position.checked_sub(1).unwrap_or(items.len() - 1),
+                ("Home", Some(_)) => 0,
+                ("End", Some(_)) => items.len() - 1,
+                _ => return Ok(()),
+            };
+
+            event.prevent_default();
+            match items[focused].dyn_ref::<HtmlElement>() {
+                Some(item) => item.focus(),
+                None => Ok(()),
+            }
+        })?;
+
+        let outside_menu = menu.clone();
+        listen(document, "click", move |event| {
+            let inside = event
+                .target()
+                .and_then(|target| target.dyn_into::<Node>().ok())
+                .is_some_and(|target| outside_menu.contains(Some(&target)));
+
+            match inside {
+                true => Ok(()),
+                false => toggle_menu(&outside_menu, false, false),
+            }
+        })
+    }
+}
+
+fn is_expanded(menu: &Element) -> JsValueResult<bool> {
+    Ok(menu
+        .query_selector("[aria-haspopup]")?
+        .and_then(|button| button.get_attribute("aria-expanded"))
+        .as_deref()
+        == Some("true"))
+}
+
+/// Open or close `menu`, focusing its first item when opened or its button when
+/// closed if `focus` is set
+fn toggle_menu(menu: &Element, open: bool, focus: bool) -> JsValueResult<()> {
+    let Some(button) = menu.query_selector("[aria-haspopup]")? else {
+        return Ok(());
+    };
+    if !open && !is_expanded(menu)? {
+        return Ok(());
+    }
+
+    button.set_attribute("aria-expanded", &open.to_string())?;
+    if let Some(list) = menu.query_selector(r#"[role="menu"]"#)? {
+        set_hidden(&list, !open)?;
+    }
+
+    if !focus {
+        return Ok(());
+    }
+
+    let focused = match open {
+        true => menu.query_selector(r#"[role="menuitem"]"#)?,
+        false => Some(button),
+    };
+    match focused
+        .as_ref()
+        .and_then(|element| element.dyn_ref::<HtmlElement>())
+    {
+        Some(element) => element.focus(),
+        None => Ok(()),
+    }
+}
+
+/// Set `aria-current` on the links of a row lowered from `links`
+fn mark_current(row: &mut PuppetElement, links: &[PuppetNavLink]) {
+    for (child, link) in row.children_mut().iter_mut().zip(links) {
+        if let (PuppetChild::Element(element), true) = (child, link.current) {
+            element.attribute("aria-current", "page");
+        }
+    }
+}
+
+fn set_hidden(element: &Element, hidden: bool) -> JsValueResult<()> {
+    match hidden {
+        true => element.set_attribute("hidden", ""),
+        false => element.remove_attribute("hidden"),
+    }
+}
+
+fn last_element(parent_node: &Element) -> JsValueResult<Element> {
+    parent_node
+        .last_element_child()
+        .ok_or(JsValue::from_str("Could not find the rendered element"))
+}
+
+fn event_target(event: &Event) -> JsValueResult<Element> {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .ok_or(JsValue::from_str("The event has no target element"))
+}
+
+fn elements(root: &Element, selector: &str) -> JsValueResult<Vec<Element>> {
+    let nodes = root.query_selector_all(selector)?;
+
+    Ok((0..nodes.length())
+        .filter_map(|index| nodes.get(index))
+        .filter_map(|node| node.dyn_into::<Element>().ok())
+        .collect())
+}
+
+/// Attach a listener for the lifetime of the page, logging its errors
+fn listen<T, F>(target: &T, event: &str, listener: F) -> JsValueResult<()>
+where
+    T: AsRef<web_sys::EventTarget> + Debug,
+    F: Fn(Event) -> JsValueResult<()> + 'static,
+{
+    let closure = Closure::wrap(Box::new(move |event: Event| {
+        if let Err(error) = listener(event) {
+            trace!("{:?}", error);
+        }
+    }) as Box<dyn FnMut(Event)>);
+
+    target
+        .as_ref()
+        .add_event_listener_with_callback(event, closure.as_ref().unchecked_ref())?;
+    // The listener lives as long as the element it is attached to
+    closure.forget();
+
+    Ok(())
+}
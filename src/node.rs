@@ -0,0 +1,374 @@
+use crate::{
+    escape_html_attribute, escape_html_text, is_safe_attribute, HydrationBackend, JsValueResult,
+    NodeKind, PuppetChild, PuppetElement, PuppetStylesheet, VOID_TAGS,
+};
+use core::{
+    cell::RefCell,
+    fmt,
+    fmt::{Debug, Write},
+};
+use std::rc::Rc;
+
+/// A node of parsed markup held in memory, standing in for the DOM so that markup
+/// can be hydrated without a browser with [PuppetElement::hydrate_node].
+///
+/// Like a DOM node, a clone refers to the same node.
+#[derive(Clone)]
+pub struct PuppetNode(Rc<RefCell<NodeData>>);
+
+enum NodeData {
+    Element {
+        tag: String,
+        attributes: Vec<(String, String)>,
+        children: Vec<PuppetNode>,
+    },
+    Text(String),
+    Comment(String),
+    /// Markup given as [PuppetElement::raw_html], left unparsed
+    Raw(String),
+}
+
+impl PuppetNode {
+    pub fn element(tag: &str) -> Self {
+        Self::new(NodeData::Element {
+            tag: tag.to_lowercase(),
+            attributes: Vec::default(),
+            children: Vec::default(),
+        })
+    }
+
+    pub fn text<T>(text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::new(NodeData::Text(text.into()))
+    }
+
+    pub fn comment<T>(comment: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::new(NodeData::Comment(comment.into()))
+    }
+
+    fn new(data: NodeData) -> Self {
+        Self(Rc::new(RefCell::new(data)))
+    }
+
+    /// `element` as a browser parses [PuppetElement::to_html_string]: adjacent text
+    /// is merged into one node and empty text is left out
+    pub fn from_element(element: &PuppetElement) -> Self {
+        let mut node = Self::element(element.tag());
+        let mut class_name = element.class_names(&mut PuppetStylesheet::new(), true);
+
+        for (name, value) in element.attributes() {
+            let mut value = value.resolve().into_owned();
+            if !is_safe_attribute(name, &value) {
+                continue;
+            }
+
+            if *name == "class" {
+                if let Some(class_name) = class_name.take() {
+                    value.push(' ');
+                    value.push_str(&class_name);
+                }
+            }
+
+            node.attribute(name, &value);
+        }
+
+        for (key, value) in element.dataset() {
+            node.attribute(&format!("data-{}", key), value);
+        }
+
+        if let Some(class_name) = class_name {
+            node.attribute("class", &class_name);
+        }
+
+        if let Some(style) = element.style() {
+            node.attribute("style", &style.to_css());
+        }
+
+        match element.get_raw_html() {
+            _ if element.is_void() => (),
+            Some(raw_html) => {
+                node.add_child(Self::new(NodeData::Raw(raw_html.into())));
+            }
+            None => {
+                for child in element.children() {
+                    node.add_parsed_child(child);
+                }
+            }
+        }
+
+        node
+    }
+
+    fn add_parsed_child(&mut self, child: &PuppetChild) {
+        let text = match child {
+            PuppetChild::Element(element) => {
+                self.add_child(Self::from_element(element));
+                return;
+            }
+            PuppetChild::Text(text) => text.resolve(),
+        };
+
+        if text.is_empty() {
+            return;
+        }
+
+        if let Some(last) = self.children().last() {
+            if let NodeData::Text(last) = &mut *last.0.borrow_mut() {
+                last.push_str(&text);
+                return;
+            }
+        }
+
+        self.add_child(Self::text(text));
+    }
+
+    /// Set `name` to `value`, in place when the node already has it
+    pub fn attribute(&mut self, name: &str, value: &str) -> &mut Self {
+        if let NodeData::Element { attributes, .. } = &mut *self.0.borrow_mut() {
+            match attributes
+                .iter_mut()
+                .find(|(attribute, _)| attribute == name)
+            {
+                Some((_, found)) => *found = value.into(),
+                None => attributes.push((name.into(), value.into())),
+            }
+        }
+
+        self
+    }
+
+    pub fn add_child(&mut self, child: PuppetNode) -> &mut Self {
+        if let NodeData::Element { children, .. } = &mut *self.0.borrow_mut() {
+            children.push(child);
+        }
+
+        self
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Option<String> {
+        match &*self.0.borrow() {
+            NodeData::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(attribute, _)| attribute == name)
+                .map(|(_, value)| value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn children(&self) -> Vec<PuppetNode> {
+        match &*self.0.borrow() {
+            NodeData::Element { children, .. } => children.clone(),
+            _ => Vec::default(),
+        }
+    }
+
+    /// Whether both refer to the same node
+    pub fn is_same_node(&self, other: &PuppetNode) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// The node as markup, escaped like [PuppetElement::to_html_string]
+    pub fn to_html_string(&self) -> String {
+        let mut html = String::default();
+        self.write_html(&mut html);
+
+        html
+    }
+
+    /// The markup of the children of the node
+    pub fn inner_html(&self) -> String {
+        let mut html = String::default();
+        for child in self.children() {
+            child.write_html(&mut html);
+        }
+
+        html
+    }
+
+    fn write_html(&self, html: &mut String) {
+        match &*self.0.borrow() {
+            NodeData::Element {
+                tag,
+                attributes,
+                children,
+            } => {
+                let _ = write!(html, "<{}", tag);
+                for (name, value) in attributes {
+                    // Writing to a String never fails
+                    let _ = write!(html, r#" {}="{}""#, name, escape_html_attribute(value));
+                }
+                html.push('>');
+
+                if VOID_TAGS.contains(&tag.as_str()) {
+                    return;
+                }
+
+                for child in children {
+                    child.write_html(html);
+                }
+                let _ = write!(html, "</{}>", tag);
+            }
+            NodeData::Text(text) => html.push_str(&escape_html_text(text)),
+            NodeData::Comment(comment) => {
+                let _ = write!(html, "<!--{}-->", comment);
+            }
+            NodeData::Raw(raw_html) => html.push_str(raw_html),
+        }
+    }
+
+    fn with_children<F, R>(&self, callback: F) -> R
+    where
+        F: FnOnce(&mut Vec<PuppetNode>) -> R,
+        R: Default,
+    {
+        match &mut *self.0.borrow_mut() {
+            NodeData::Element { children, .. } => callback(children),
+            _ => R::default(),
+        }
+    }
+
+    fn position_of(&self, child: &PuppetNode) -> Option<usize> {
+        self.with_children(|children| children.iter().position(|node| node.is_same_node(child)))
+    }
+}
+
+impl Debug for PuppetNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PuppetNode({})", self.to_html_string())
+    }
+}
+
+/// Hydrates [PuppetNode]s, where nothing is bound or listened to
+pub(crate) struct NodeBackend;
+
+impl HydrationBackend for NodeBackend {
+    type Node = PuppetNode;
+
+    fn kind(&self, node: &PuppetNode) -> NodeKind {
+        match &*node.0.borrow() {
+            NodeData::Element { tag, .. } => NodeKind::Element(tag.clone()),
+            NodeData::Text(text) => NodeKind::Text(text.clone()),
+            NodeData::Comment(_) => NodeKind::Comment,
+            NodeData::Raw(raw_html) => NodeKind::Other(raw_html.clone()),
+        }
+    }
+
+    fn children(&self, parent: &PuppetNode) -> Vec<PuppetNode> {
+        parent.children()
+    }
+
+    fn attribute(&self, element: &PuppetNode, name: &str) -> Option<String> {
+        element.get_attribute(name)
+    }
+
+    fn attribute_names(&self, element: &PuppetNode) -> Vec<String> {
+        match &*element.0.borrow() {
+            NodeData::Element { attributes, .. } => {
+                attributes.iter().map(|(name, _)| name.clone()).collect()
+            }
+            _ => Vec::default(),
+        }
+    }
+
+    fn set_attribute(
+        &mut self,
+        element: &PuppetNode,
+        name: &str,
+        value: &str,
+    ) -> JsValueResult<()> {
+        element.clone().attribute(name, value);
+
+        Ok(())
+    }
+
+    fn remove_attribute(&mut self, element: &PuppetNode, name: &str) -> JsValueResult<()> {
+        if let NodeData::Element { attributes, .. } = &mut *element.0.borrow_mut() {
+            attributes.retain(|(attribute, _)| attribute != name);
+        }
+
+        Ok(())
+    }
+
+    fn set_text(&mut self, node: &PuppetNode, text: &str) {
+        if let NodeData::Text(found) = &mut *node.0.borrow_mut() {
+            *found = text.into();
+        }
+    }
+
+    fn split_text(
+        &mut self,
+        parent: &PuppetNode,
+        node: &PuppetNode,
+        head: &str,
+    ) -> JsValueResult<PuppetNode> {
+        let rest = match &mut *node.0.borrow_mut() {
+            NodeData::Text(text) => text.split_off(head.len().min(text.len())),
+            _ => String::default(),
+        };
+        let rest = PuppetNode::text(rest);
+
+        if let Some(position) = parent.position_of(node) {
+            parent.with_children(|children| children.insert(position + 1, rest.clone()));
+        }
+
+        Ok(rest)
+    }
+
+    fn insert(
+        &mut self,
+        parent: &PuppetNode,
+        child: &PuppetChild,
+        before: Option<&PuppetNode>,
+    ) -> JsValueResult<()> {
+        let node = match child {
+            PuppetChild::Element(element) => PuppetNode::from_element(element),
+            PuppetChild::Text(text) => PuppetNode::text(text.resolve()),
+        };
+        let position = before.and_then(|before| parent.position_of(before));
+
+        parent.with_children(|children| match position {
+            Some(position) => children.insert(position, node),
+            None => children.push(node),
+        });
+
+        Ok(())
+    }
+
+    fn remove(&mut self, parent: &PuppetNode, node: &PuppetNode) -> JsValueResult<()> {
+        parent.with_children(|children| children.retain(|child| !child.is_same_node(node)));
+
+        Ok(())
+    }
+
+    fn rule_classes(&mut self, expected: &PuppetElement) -> JsValueResult<Vec<String>> {
+        let mut stylesheet = PuppetStylesheet::new();
+        let state_class = match expected.states().is_empty() {
+            true => None,
+            false => Some(stylesheet.state_class_name(expected.states())),
+        };
+        let scope_class = expected
+            .get_scoped_style()
+            .map(|scoped_style| scoped_style.class_name());
+
+        Ok([state_class, scope_class].into_iter().flatten().collect())
+    }
+
+    fn raw_html(
+        &mut self,
+        element: &PuppetNode,
+        expected: &str,
+    ) -> JsValueResult<(String, String)> {
+        Ok((expected.into(), element.inner_html()))
+    }
+
+    fn set_raw_html(&mut self, element: &PuppetNode, raw_html: &str) {
+        element.with_children(|children| {
+            *children = vec![PuppetNode::new(NodeData::Raw(raw_html.into()))]
+        });
+    }
+}
@@ -0,0 +1,753 @@
+use crate::{
+    current_direction, JsValueResult, PuppetColor, PuppetColumn, PuppetDirection, PuppetElement,
+    PuppetStyle, PuppetText, PuppetUnit, Transparency,
+};
+use core::{
+    cell::{Cell, RefCell},
+    fmt,
+    fmt::{Debug, Display},
+};
+use log::trace;
+use std::rc::{Rc, Weak};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Document, Element, Event, EventTarget, HtmlElement, KeyboardEvent, Node};
+
+/// Elements that can receive the keyboard focus inside an overlay
+const FOCUSABLE: &str = "a[href], button:not([disabled]), input:not([disabled]), \
+    select:not([disabled]), textarea:not([disabled]), [tabindex]:not([tabindex=\"-1\"])";
+
+/// Distance in pixels between a popover and its anchor
+const ANCHOR_OFFSET: f64 = 4.0;
+
+/// Stacking order of overlays above the page
+const OVERLAY_Z_INDEX: &str = "1000";
+
+/// The edge of the screen a drawer slides from, `Start` and `End` following the
+/// text direction
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum DrawerSide {
+    #[default]
+    Start,
+    End,
+    Top,
+    Bottom,
+}
+
+impl DrawerSide {
+    pub fn to_html_side(&self) -> &'static str {
+        match self {
+            Self::Start => "start",
+            Self::End => "end",
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+        }
+    }
+}
+
+impl Debug for DrawerSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Start => "Start",
+                Self::End => "End",
+                Self::Top => "Top",
+                Self::Bottom => "Bottom",
+            }
+        )
+    }
+}
+
+impl Display for DrawerSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Where a popover or tooltip opens next to its anchor, `Start` and `End` following
+/// the text direction
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum PopoverPlacement {
+    Top,
+    #[default]
+    Bottom,
+    Start,
+    End,
+}
+
+impl PopoverPlacement {
+    pub fn to_html_placement(&self) -> &'static str {
+        match self {
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::Start => "start",
+            Self::End => "end",
+        }
+    }
+}
+
+impl Debug for PopoverPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Top => "Top",
+                Self::Bottom => "Bottom",
+                Self::Start => "Start",
+                Self::End => "End",
+            }
+        )
+    }
+}
+
+impl Display for PopoverPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum OverlayKind {
+    /// A dialog above a backdrop, trapping the focus and locking the page scroll
+    Modal,
+    /// A modal panel along one edge of the screen
+    Drawer(DrawerSide),
+    /// A non modal dialog next to the element with the `anchor` id
+    Popover {
+        anchor: &'static str,
+        placement: PopoverPlacement,
+    },
+    /// A description of the element with the `anchor` id, never focused
+    Tooltip {
+        anchor: &'static str,
+        placement: PopoverPlacement,
+    },
+}
+
+impl OverlayKind {
+    pub fn to_html_overlay(&self) -> &'static str {
+        match self {
+            Self::Modal => "modal",
+            Self::Drawer(_) => "drawer",
+            Self::Popover { .. } => "popover",
+            Self::Tooltip { .. } => "tooltip",
+        }
+    }
+
+    pub fn is_modal(&self) -> bool {
+        matches!(self, Self::Modal | Self::Drawer(_))
+    }
+
+    pub fn anchor(&self) -> Option<&'static str> {
+        match self {
+            Self::Popover { anchor, .. } | Self::Tooltip { anchor, .. } => Some(anchor),
+            _ => None,
+        }
+    }
+}
+
+impl Debug for OverlayKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Modal => write!(f, "Modal"),
+            Self::Drawer(side) => write!(f, "Drawer({:?})", side),
+            Self::Popover { anchor, placement } => {
+                write!(f, "Popover({:?}, {:?})", anchor, placement)
+            }
+            Self::Tooltip { anchor, placement } => {
+                write!(f, "Tooltip({:?}, {:?})", anchor, placement)
+            }
+        }
+    }
+}
+
+impl Display for OverlayKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A modal, drawer, popover or tooltip whose content is an ordinary [PuppetColumn].
+///
+/// [PuppetOverlay::to_element] describes the overlay for every backend, while
+/// [PuppetOverlay::open] shows it in the DOM and handles focus, Escape and the
+/// scroll of the page until it is closed.
+///
+/// ```
+/// use puppeteer_builders::{puppet, PuppetOverlay};
+///
+/// let mut dialog = PuppetOverlay::modal(puppet! {
+///     column { row { heading("Delete this user?") button("Delete") button("Cancel") } }
+/// });
+/// dialog.label("Delete user");
+///
+/// assert!(dialog.to_html_string().contains(r#"role="dialog" aria-modal="true""#));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetOverlay {
+    kind: OverlayKind,
+    overlay_id: Option<&'static str>,
+    label: Option<PuppetText>,
+    backdrop: PuppetStyle,
+    dismissible: bool,
+    content: PuppetColumn,
+}
+
+impl PuppetOverlay {
+    pub fn new(kind: OverlayKind, content: PuppetColumn) -> Self {
+        let mut backdrop = PuppetStyle::default();
+        backdrop
+            .padding(PuppetUnit::Pixels(0))
+            .background_color(PuppetColor::Rgba(0, 0, 0, Transparency::Fifty));
+
+        Self {
+            kind,
+            overlay_id: Option::default(),
+            label: Option::default(),
+            backdrop,
+            dismissible: true,
+            content,
+        }
+    }
+
+    pub fn modal(content: PuppetColumn) -> Self {
+        Self::new(OverlayKind::Modal, content)
+    }
+
+    pub fn drawer(side: DrawerSide, content: PuppetColumn) -> Self {
+        Self::new(OverlayKind::Drawer(side), content)
+    }
+
+    pub fn popover(
+        anchor: &'static str,
+        placement: PopoverPlacement,
+        content: PuppetColumn,
+    ) -> Self {
+        Self::new(OverlayKind::Popover { anchor, placement }, content)
+    }
+
+    pub fn tooltip(
+        anchor: &'static str,
+        placement: PopoverPlacement,
+        content: PuppetColumn,
+    ) -> Self {
+        Self::new(OverlayKind::Tooltip { anchor, placement }, content)
+    }
+
+    pub fn overlay_id(&mut self, overlay_id: &'static str) -> &mut Self {
+        self.overlay_id = Some(overlay_id);
+
+        self
+    }
+
+    /// The accessible name of the dialog, read by screen readers when it opens
+    pub fn label<T>(&mut self, label: T) -> &mut Self
+    where
+        T: Into<PuppetText>,
+    {
+        self.label = Some(label.into());
+
+        self
+    }
+
+    /// Style of the backdrop of modals and drawers, half transparent black by default
+    pub fn backdrop(&mut self, backdrop: &PuppetStyle) -> &mut Self {
+        self.backdrop = *backdrop;
+
+        self
+    }
+
+    /// Whether Escape and clicks outside of the overlay close it, `true` by default
+    pub fn dismissible(&mut self, dismissible: bool) -> &mut Self {
+        self.dismissible = dismissible;
+
+        self
+    }
+
+    pub fn kind(&self) -> OverlayKind {
+        self.kind
+    }
+
+    /// The id of the panel holding the content, generated from the anchor for
+    /// popovers and tooltips so that the anchor can reference it
+    pub fn panel_id(&self) -> Option<String> {
+        match (self.overlay_id, self.kind.anchor()) {
+            (Some(overlay_id), _) => Some(overlay_id.into()),
+            (None, Some(anchor)) => Some(format!("{}-{}", anchor, self.kind.to_html_overlay())),
+            (None, None) => None,
+        }
+    }
+
+    pub fn to_element(&self) -> PuppetElement {
+        let mut panel = PuppetElement::new("div");
+
+        if let Some(panel_id) = self.panel_id() {
+            panel.attribute("id", panel_id);
+        }
+
+        match self.kind {
+            OverlayKind::Tooltip { .. } => {
+                panel.attribute("role", "tooltip");
+            }
+            kind => {
+                panel.attribute("role", "dialog");
+                if kind.is_modal() {
+                    panel.attribute("aria-modal", "true");
+                }
+                panel.attribute("tabindex", "-1");
+            }
+        }
+
+        if let Some(label) = &self.label {
+            panel.attribute("aria-label", label.clone());
+        }
+
+        panel.add_child(self.content.to_element());
+
+        if let OverlayKind::Popover { placement, .. } | OverlayKind::Tooltip { placement, .. } =
+            self.kind
+        {
+            panel
+                .attribute("data-overlay", self.kind.to_html_overlay())
+                .attribute("data-placement", placement.to_html_placement());
+
+            return panel;
+        }
+
+        let mut backdrop = PuppetElement::new("div");
+        backdrop
+            .attribute("data-overlay", self.kind.to_html_overlay())
+            .set_style(&self.backdrop);
+
+        if let OverlayKind::Drawer(side) = self.kind {
+            backdrop.attribute("data-side", side.to_html_side());
+        }
+
+        backdrop.add_child(panel);
+
+        backdrop
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+
+    /// Show the overlay at the end of `mount_node`, usually the app node. The
+    /// overlay closes when the returned handle is dropped.
+    #[must_use = "the overlay closes as soon as its handle is dropped"]
+    pub fn open(
+        &self,
+        document: &Document,
+        mount_node: &Element,
+    ) -> JsValueResult<Rc<PuppetOverlayHandle>> {
+        self.to_element().to_html(document, mount_node)?;
+
+        let root = mount_node
+            .last_element_child()
+            .ok_or(JsValue::from_str("Could not find the rendered overlay"))?;
+        let panel = match self.kind.is_modal() {
+            true => root.first_element_child(),
+            false => Some(root.clone()),
+        }
+        .and_then(|panel| panel.dyn_into::<HtmlElement>().ok())
+        .ok_or(JsValue::from_str("Could not find the panel of the overlay"))?;
+
+        let anchor = match self.kind.anchor() {
+            Some(anchor) => Some(document.get_element_by_id(anchor).ok_or_else(|| {
+                JsValue::from_str(&format!("Could not find the anchor `{}`", anchor))
+            })?),
+            None => None,
+        };
+
+        let handle = Rc::new(PuppetOverlayHandle {
+            kind: self.kind,
+            document: document.clone(),
+            root,
+            panel,
+            anchor,
+            previous_focus: document
+                .active_element()
+                .and_then(|element| element.dyn_into::<HtmlElement>().ok()),
+            previous_overflow: RefCell::default(),
+            listeners: RefCell::default(),
+            open: Cell::new(true),
+        });
+
+        handle.position()?;
+        handle.attach_anchor()?;
+        if self.kind.is_modal() {
+            handle.lock_scroll()?;
+        }
+        handle.listen(self.dismissible)?;
+        handle.focus_first();
+
+        Ok(handle)
+    }
+}
+
+type Listener = (EventTarget, &'static str, Closure<dyn FnMut(Event)>);
+
+/// An open [PuppetOverlay], removed from the DOM together with its listeners by
+/// [PuppetOverlayHandle::close] or when the handle is dropped
+pub struct PuppetOverlayHandle {
+    kind: OverlayKind,
+    document: Document,
+    /// The backdrop of modals and drawers, the panel itself otherwise
+    root: Element,
+    panel: HtmlElement,
+    anchor: Option<Element>,
+    previous_focus: Option<HtmlElement>,
+    previous_overflow: RefCell<Option<String>>,
+    listeners: RefCell<Vec<Listener>>,
+    open: Cell<bool>,
+}
+
+impl PuppetOverlayHandle {
+    pub fn is_open(&self) -> bool {
+        self.open.get()
+    }
+
+    pub fn panel(&self) -> &HtmlElement {
+        &self.panel
+    }
+
+    /// Remove the overlay, unlock the scroll and give the focus back to the element
+    /// that had it before opening
+    pub fn close(&self) -> JsValueResult<()> {
+        if !self.open.replace(false) {
+            return Ok(());
+        }
+
+        for (target, event, listener) in self.listeners.borrow_mut().drain(..) {
+            target.remove_event_listener_with_callback(event, listener.as_ref().unchecked_ref())?;
+        }
+
+        if let Some(anchor) = &self.anchor {
+            match self.kind {
+                OverlayKind::Tooltip { .. } => anchor.remove_attribute("aria-describedby")?,
+                _ => anchor.set_attribute("aria-expanded", "false")?,
+            }
+        }
+
+        if let Some(overflow) = self.previous_overflow.borrow_mut().take() {
+            if let Some(body) = self.document.body() {
+                body.style().set_property("overflow", &overflow)?;
+            }
+        }
+
+        self.root.remove();
+
+        if let Some(previous_focus) = &self.previous_focus {
+            previous_focus.focus()?;
+        }
+
+        Ok(())
+    }
+
+    fn position(&self) -> JsValueResult<()> {
+        let root_style = self
+            .root
+            .dyn_ref::<HtmlElement>()
+            .ok_or(JsValue::from_str(
+                "Could not convert `Element` to `HtmlElement`",
+            ))?
+            .style();
+        root_style.set_property("position", "fixed")?;
+        root_style.set_property("z-index", OVERLAY_Z_INDEX)?;
+
+        let placement = match self.kind {
+            OverlayKind::Modal => {
+                return set_properties(
+                    &self.root,
+                    &[
+                        ("inset", "0"),
+                        ("display", "flex"),
+                        ("justify-content", "center"),
+                        ("align-items", "center"),
+                    ],
+                );
+            }
+            OverlayKind::Drawer(side) => {
+                let (flex_direction, justify_content, panel_size) = match side {
+                    DrawerSide::Start => ("row", "flex-start", ("height", "100%")),
+                    DrawerSide::End => ("row", "flex-end", ("height", "100%")),
+                    DrawerSide::Top => ("column", "flex-start", ("width", "100%")),
+                    DrawerSide::Bottom => ("column", "flex-end", ("width", "100%")),
+                };
+
+                set_properties(
+                    &self.root,
+                    &[
+                        ("inset", "0"),
+                        ("display", "flex"),
+                        ("flex-direction", flex_direction),
+                        ("justify-content", justify_content),
+                    ],
+                )?;
+                return set_properties(&self.panel, &[panel_size]);
+            }
+            OverlayKind::Popover { placement, .. } | OverlayKind::Tooltip { placement, .. } => {
+                placement
+            }
+        };
+
+        let anchor = match &self.anchor {
+            Some(anchor) => anchor.get_bounding_client_rect(),
+            None => return Ok(()),
+        };
+        let viewport = self
+            .document
+            .document_element()
+            .ok_or(JsValue::from_str("Could not get the document element"))?;
+        let viewport_width = f64::from(viewport.client_width());
+        let viewport_height = f64::from(viewport.client_height());
+
+        let rtl = current_direction() == PuppetDirection::Rtl;
+        let (start_edge, end_edge) = match rtl {
+            false => ("left", "right"),
+            true => ("right", "left"),
+        };
+        // Distance from the start edge of the viewport to the start and end of the anchor
+        let (anchor_start, anchor_end) = match rtl {
+            false => (anchor.left(), anchor.right()),
+            true => (
+                viewport_width - anchor.right(),
+                viewport_width - anchor.left(),
+            ),
+        };
+
+        let properties = match placement {
+            PopoverPlacement::Bottom => [
+                ("top", anchor.bottom() + ANCHOR_OFFSET),
+                (start_edge, anchor_start),
+            ],
+            PopoverPlacement::Top => [
+                ("bottom", viewport_height - anchor.top() + ANCHOR_OFFSET),
+                (start_edge, anchor_start),
+            ],
+            PopoverPlacement::Start => [
+                ("top", anchor.top()),
+                (end_edge, viewport_width - anchor_start + ANCHOR_OFFSET),
+            ],
+            PopoverPlacement::End => [
+                ("top", anchor.top()),
+                (start_edge, anchor_end + ANCHOR_OFFSET),
+            ],
+        };
+
+        for (property, pixels) in properties {
+            root_style.set_property(property, &format!("{}px", pixels))?;
+        }
+
+        Ok(())
+    }
+
+    /// Let assistive technologies know what the anchor opened
+    fn attach_anchor(&self) -> JsValueResult<()> {
+        let Some(anchor) = &self.anchor else {
+            return Ok(());
+        };
+        let panel_id = self.panel.id();
+
+        match self.kind {
+            OverlayKind::Tooltip { .. } => anchor.set_attribute("aria-describedby", &panel_id),
+            _ => {
+                anchor.set_attribute("aria-controls", &panel_id)?;
+                anchor.set_attribute("aria-expanded", "true")
+            }
+        }
+    }
+
+    fn lock_scroll(&self) -> JsValueResult<()> {
+        if let Some(body) = self.document.body() {
+            let style = body.style();
+            *self.previous_overflow.borrow_mut() = Some(style.get_property_value("overflow")?);
+            style.set_property("overflow", "hidden")?;
+        }
+
+        Ok(())
+    }
+
+    fn listen(self: &Rc<Self>, dismissible: bool) -> JsValueResult<()> {
+        if matches!(self.kind, OverlayKind::Tooltip { .. }) {
+            return Ok(());
+        }
+
+        let handle = Rc::downgrade(self);
+        self.add_listener(self.document.clone().into(), "keydown", move |event| {
+            with_handle(&handle, |handle| handle.on_keydown(event, dismissible))
+        })?;
+
+        if !dismissible {
+            return Ok(());
+        }
+
+        let handle = Rc::downgrade(self);
+        self.add_listener(self.document.clone().into(), "click", move |event| {
+            with_handle(&handle, |handle| handle.on_click(event))
+        })
+    }
+
+    fn add_listener<F>(
+        &self,
+        target: EventTarget,
+        event: &'static str,
+        listener: F,
+    ) -> JsValueResult<()>
+    where
+        F: FnMut(Event) + 'static,
+    {
+        let closure = Closure::wrap(Box::new(listener) as Box<dyn FnMut(Event)>);
+        target.add_event_listener_with_callback(event, closure.as_ref().unchecked_ref())?;
+        self.listeners.borrow_mut().push((target, event, closure));
+
+        Ok(())
+    }
+
+    fn on_keydown(&self, event: Event, dismissible: bool) -> JsValueResult<()> {
+        let Some(keyboard_event) = event.dyn_ref::<KeyboardEvent>() else {
+            return Ok(());
+        };
+
+        match keyboard_event.key().as_str() {
+            "Escape" if dismissible => {
+                event.prevent_default();
+                self.close()
+            }
+            "Tab" if self.kind.is_modal() => {
+                self.trap_focus(keyboard_event);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Close when clicking the backdrop of a modal, or anywhere else than a popover
+    /// and its anchor
+    fn on_click(&self, event: Event) -> JsValueResult<()> {
+        let Some(target) = event
+            .target()
+            .and_then(|target| target.dyn_into::<Node>().ok())
+        else {
+            return Ok(());
+        };
+
+        let outside = match self.kind.is_modal() {
+            true => self.root.is_same_node(Some(&target)),
+            false => {
+                !self.panel.contains(Some(&target))
+                    && !self
+                        .anchor
+                        .as_ref()
+                        .is_some_and(|anchor| anchor.contains(Some(&target)))
+            }
+        };
+
+        match outside {
+            true => self.close(),
+            false => Ok(()),
+        }
+    }
+
+    fn focusable(&self) -> Vec<HtmlElement> {
+        let Ok(nodes) = self.panel.query_selector_all(FOCUSABLE) else {
+            return Vec::default();
+        };
+
+        (0..nodes.length())
+            .filter_map(|index| nodes.get(index))
+            .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+            .collect()
+    }
+
+    fn focus_first(&self) {
+        if matches!(self.kind, OverlayKind::Tooltip { .. }) {
+            return;
+        }
+
+        let focused = match self.focusable().first() {
+            Some(first) => first.focus(),
+            None => self.panel.focus(),
+        };
+
+        if let Err(error) = focused {
+            trace!("{:?}", error);
+        }
+    }
+
+    /// Keep Tab and Shift+Tab cycling through the focusable elements of the panel
+    fn trap_focus(&self, event: &KeyboardEvent) {
+        let focusable = self.focusable();
+        let (Some(first), Some(last)) = (focusable.first(), focusable.last()) else {
+            event.prevent_default();
+            return;
+        };
+
+        let active = self.document.active_element();
+        let is_active = |element: &HtmlElement| {
+            active
+                .as_ref()
+                .is_some_and(|active| active.is_same_node(Some(element)))
+        };
+        let inside = active
+            .as_ref()
+            .is_some_and(|active| self.panel.contains(Some(active)));
+
+        let target = match event.shift_key() {
+            true if is_active(first) || !inside => last,
+            false if is_active(last) || !inside => first,
+            _ => return,
+        };
+
+        event.prevent_default();
+        if let Err(error) = target.focus() {
+            trace!("{:?}", error);
+        }
+    }
+}
+
+impl Drop for PuppetOverlayHandle {
+    /// Listeners must not outlive their closures, which the handle owns
+    fn drop(&mut self) {
+        if let Err(error) = self.close() {
+            trace!("{:?}", error);
+        }
+    }
+}
+
+impl Debug for PuppetOverlayHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetOverlayHandle")
+            .field("kind", &self.kind)
+            .field("open", &self.open.get())
+            .finish()
+    }
+}
+
+fn with_handle<F>(handle: &Weak<PuppetOverlayHandle>, callback: F)
+where
+    F: FnOnce(&PuppetOverlayHandle) -> JsValueResult<()>,
+{
+    if let Some(handle) = handle.upgrade() {
+        if let Err(error) = callback(&handle) {
+            trace!("{:?}", error);
+        }
+    }
+}
+
+fn set_properties(element: &Element, properties: &[(&str, &str)]) -> JsValueResult<()> {
+    let style = element
+        .dyn_ref::<HtmlElement>()
+        .ok_or(JsValue::from_str(
+            "Could not convert `Element` to `HtmlElement`",
+        ))?
+        .style();
+
+    for (property, value) in properties {
+        style.set_property(property, value)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,683 @@
+use crate::{
+    focus_element, get_document, get_window, install_focus_ring, mount_locale_root,
+    mount_skip_link, mount_toasts, restore_toasts, screen_root, JsValueResult, PuppetColumn,
+    PuppetFocusRing, PuppetMismatch, PuppetRow, PuppetText, SKIP_LINK_LABEL,
+};
+use core::{cell::Cell, cell::RefCell, fmt, fmt::Debug};
+use log::trace;
+use std::{collections::HashMap, rc::Rc};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Element, Event, MouseEvent};
+
+/// Builds the screen of a route from the parameters of the current URL
+pub type PuppetScreen = Rc<dyn Fn(&PuppetParams) -> PuppetColumn>;
+/// Wraps the screen of a nested route, the second argument is the child screen
+pub type PuppetLayout = Rc<dyn Fn(&PuppetParams, PuppetColumn) -> PuppetColumn>;
+
+/// Where the [PuppetRouter] reads and writes the current URL
+pub trait PuppetHistory {
+    /// The current path including its query string, for example `/users/42?tab=posts`
+    fn location(&self) -> String;
+    fn push(&self, url: &str) -> JsValueResult<()>;
+    fn replace(&self, url: &str) -> JsValueResult<()>;
+}
+
+/// The History API of the browser window
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BrowserHistory;
+
+impl PuppetHistory for BrowserHistory {
+    fn location(&self) -> String {
+        let location = get_window().location();
+
+        match (location.pathname(), location.search()) {
+            (Ok(pathname), Ok(search)) => pathname + &search,
+            _ => {
+                trace!("Could not read the current Location");
+                "/".into()
+            }
+        }
+    }
+
+    fn push(&self, url: &str) -> JsValueResult<()> {
+        get_window()
+            .history()?
+            .push_state_with_url(&JsValue::NULL, "", Some(url))
+    }
+
+    fn replace(&self, url: &str) -> JsValueResult<()> {
+        get_window()
+            .history()?
+            .replace_state_with_url(&JsValue::NULL, "", Some(url))
+    }
+}
+
+/// A history kept in memory, for tests and non-browser targets
+#[derive(Debug)]
+pub struct MemoryHistory {
+    entries: RefCell<Vec<String>>,
+    index: Cell<usize>,
+}
+
+impl MemoryHistory {
+    pub fn new(url: &str) -> Self {
+        Self {
+            entries: RefCell::new(vec![url.into()]),
+            index: Cell::new(0),
+        }
+    }
+
+    /// Returns `false` when already at the first entry
+    pub fn back(&self) -> bool {
+        match self.index.get() {
+            0 => false,
+            index => {
+                self.index.set(index - 1);
+                true
+            }
+        }
+    }
+
+    /// Returns `false` when already at the last entry
+    pub fn forward(&self) -> bool {
+        let index = self.index.get();
+
+        if index + 1 < self.entries.borrow().len() {
+            self.index.set(index + 1);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn entries(&self) -> Vec<String> {
+        self.entries.borrow().clone()
+    }
+}
+
+impl PuppetHistory for MemoryHistory {
+    fn location(&self) -> String {
+        self.entries.borrow()[self.index.get()].clone()
+    }
+
+    fn push(&self, url: &str) -> JsValueResult<()> {
+        let mut entries = self.entries.borrow_mut();
+        let index = self.index.get() + 1;
+
+        entries.truncate(index);
+        entries.push(url.into());
+        self.index.set(index);
+
+        Ok(())
+    }
+
+    fn replace(&self, url: &str) -> JsValueResult<()> {
+        self.entries.borrow_mut()[self.index.get()] = url.into();
+
+        Ok(())
+    }
+}
+
+impl<T: PuppetHistory> PuppetHistory for Rc<T> {
+    fn location(&self) -> String {
+        (**self).location()
+    }
+
+    fn push(&self, url: &str) -> JsValueResult<()> {
+        (**self).push(url)
+    }
+
+    fn replace(&self, url: &str) -> JsValueResult<()> {
+        (**self).replace(url)
+    }
+}
+
+/// The path, path parameters and query string of a matched URL
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct PuppetParams {
+    path: String,
+    params: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+}
+
+impl PuppetParams {
+    pub fn parse(url: &str) -> Self {
+        let url = url.split('#').next().unwrap_or_default();
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+
+        let query = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode_component(key, true), decode_component(value, true))
+            })
+            .collect();
+
+        Self {
+            path: path.into(),
+            params: Vec::default(),
+            query,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// A `:name` segment of the route pattern, or `*` for the rest of a wildcard route
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The first value of `name` in the query string
+    pub fn query(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn query_all<'p>(&'p self, name: &'p str) -> impl Iterator<Item = &'p str> + 'p {
+        self.query
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Percent-decode a URL component, falling back to the raw input on malformed UTF-8
+fn decode_component(component: &str, plus_as_space: bool) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' if index + 2 < bytes.len() => {
+                match (hex_value(bytes[index + 1]), hex_value(bytes[index + 2])) {
+                    (Some(high), Some(low)) => {
+                        decoded.push(high << 4 | low);
+                        index += 3;
+                        continue;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            b'+' if plus_as_space => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+
+    String::from_utf8(decoded).unwrap_or_else(|_| component.into())
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn split_segments(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// Maps a path pattern such as `/users/:id` or `/files/*` to a screen.
+///
+/// A route created with [PuppetRoute::layout] only matches through its children,
+/// whose patterns are relative to the parent, and wraps their screens.
+#[derive(Clone)]
+pub struct PuppetRoute {
+    pattern: &'static str,
+    screen: Option<PuppetScreen>,
+    layout: Option<PuppetLayout>,
+    children: Vec<PuppetRoute>,
+}
+
+impl PuppetRoute {
+    pub fn new<F>(pattern: &'static str, screen: F) -> Self
+    where
+        F: Fn(&PuppetParams) -> PuppetColumn + 'static,
+    {
+        Self {
+            pattern,
+            screen: Some(Rc::new(screen)),
+            layout: Option::default(),
+            children: Vec::default(),
+        }
+    }
+
+    pub fn layout<F>(pattern: &'static str, layout: F) -> Self
+    where
+        F: Fn(&PuppetParams, PuppetColumn) -> PuppetColumn + 'static,
+    {
+        Self {
+            pattern,
+            screen: Option::default(),
+            layout: Some(Rc::new(layout)),
+            children: Vec::default(),
+        }
+    }
+
+    pub fn add_child(&mut self, route: PuppetRoute) -> &mut Self {
+        self.children.push(route);
+
+        self
+    }
+
+    /// Returns the chain of matched routes, from the outermost layout to the screen
+    fn matches<'r>(
+        &'r self,
+        segments: &[&str],
+        params: &mut Vec<(String, String)>,
+    ) -> Option<Vec<&'r PuppetRoute>> {
+        let params_len = params.len();
+        let mut consumed = 0;
+
+        for pattern_segment in split_segments(self.pattern) {
+            if pattern_segment == "*" {
+                params.push(("*".into(), segments[consumed..].join("/")));
+                consumed = segments.len();
+                break;
+            }
+
+            let segment = match segments.get(consumed) {
+                Some(segment) => *segment,
+                None => {
+                    params.truncate(params_len);
+                    return None;
+                }
+            };
+
+            if let Some(name) = pattern_segment.strip_prefix(':') {
+                params.push((name.into(), decode_component(segment, false)));
+            } else if pattern_segment != segment {
+                params.truncate(params_len);
+                return None;
+            }
+
+            consumed += 1;
+        }
+
+        let rest = &segments[consumed..];
+
+        for child in &self.children {
+            if let Some(mut chain) = child.matches(rest, params) {
+                chain.insert(0, self);
+                return Some(chain);
+            }
+        }
+
+        if rest.is_empty() && self.screen.is_some() {
+            return Some(vec![self]);
+        }
+
+        params.truncate(params_len);
+        None
+    }
+}
+
+impl Debug for PuppetRoute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetRoute")
+            .field("pattern", &self.pattern)
+            .field("children", &self.children)
+            .finish()
+    }
+}
+
+/// Renders the [PuppetColumn] of the route matching the current URL
+///
+/// ```
+/// use puppeteer_builders::{puppet, MemoryHistory, PuppetHistory, PuppetRoute, PuppetRouter};
+/// use std::rc::Rc;
+///
+/// let history = Rc::new(MemoryHistory::new("/"));
+/// let mut router = PuppetRouter::new(Rc::clone(&history));
+/// router.route(PuppetRoute::new("/users/:id", |params| {
+///     let mut column = puppet! { column {} };
+///     column.column_id(if params.param("id") == Some("42") { "answer" } else { "user" });
+///     column
+/// }));
+///
+/// router.navigate("/users/42?tab=posts").unwrap();
+///
+/// assert_eq!(history.location(), "/users/42?tab=posts");
+/// assert_eq!(router.current(), puppet! { column { id("answer") } });
+/// assert_eq!(router.resolve("/nowhere"), PuppetRouter::default_not_found());
+/// ```
+pub struct PuppetRouter {
+    routes: Vec<PuppetRoute>,
+    not_found: PuppetScreen,
+    history: Box<dyn PuppetHistory>,
+    mount_node: RefCell<Option<Element>>,
+    skip_link: Option<PuppetText>,
+    focus_ring: Option<PuppetFocusRing>,
+    /// The location on screen, once mounted
+    rendered: RefCell<Option<String>>,
+    /// Id of the element focused when each location was left, focused again when
+    /// users come back to it
+    focus_history: RefCell<HashMap<String, String>>,
+}
+
+impl PuppetRouter {
+    pub fn new<H>(history: H) -> Self
+    where
+        H: PuppetHistory + 'static,
+    {
+        Self {
+            routes: Vec::default(),
+            not_found: Rc::new(|_| PuppetRouter::default_not_found()),
+            history: Box::new(history),
+            mount_node: RefCell::default(),
+            skip_link: Some(SKIP_LINK_LABEL.into()),
+            focus_ring: Option::default(),
+            rendered: RefCell::default(),
+            focus_history: RefCell::default(),
+        }
+    }
+
+    pub fn route(&mut self, route: PuppetRoute) -> &mut Self {
+        self.routes.push(route);
+
+        self
+    }
+
+    /// The text of the link jumping over the navigation to the screen, `None` to
+    /// leave it out
+    pub fn skip_link<T>(&mut self, label: Option<T>) -> &mut Self
+    where
+        T: Into<PuppetText>,
+    {
+        self.skip_link = label.map(Into::into);
+
+        self
+    }
+
+    /// The outline of the focused element, derived from the style of the first
+    /// screen by default
+    pub fn focus_ring(&mut self, focus_ring: PuppetFocusRing) -> &mut Self {
+        self.focus_ring = Some(focus_ring);
+
+        self
+    }
+
+    /// The screen rendered when no route matches the URL
+    pub fn not_found<F>(&mut self, screen: F) -> &mut Self
+    where
+        F: Fn(&PuppetParams) -> PuppetColumn + 'static,
+    {
+        self.not_found = Rc::new(screen);
+
+        self
+    }
+
+    pub fn default_not_found() -> PuppetColumn {
+        let mut row = PuppetRow::new();
+        row.add_component(crate::Component::new(
+            crate::ComponentType::Heading,
+            "Page not found",
+        ));
+
+        let mut column = PuppetColumn::new();
+        column.add_row(row);
+
+        column
+    }
+
+    /// Build the screen for `url` without touching the history
+    pub fn resolve(&self, url: &str) -> PuppetColumn {
+        let mut params = PuppetParams::parse(url);
+        let segments = split_segments(&params.path);
+        let mut path_params = Vec::default();
+
+        let chain = self
+            .routes
+            .iter()
+            .find_map(|route| route.matches(&segments, &mut path_params));
+        params.params = path_params;
+
+        match chain {
+            Some(chain) => {
+                let (screen, layouts) = chain.split_last().expect("a matched chain is never empty");
+                let screen = screen
+                    .screen
+                    .as_ref()
+                    .expect("only routes with a screen end a chain");
+
+                layouts
+                    .iter()
+                    .rev()
+                    .filter_map(|route| route.layout.as_ref())
+                    .fold(screen(&params), |child, layout| layout(&params, child))
+            }
+            None => (self.not_found)(&params),
+        }
+    }
+
+    pub fn current(&self) -> PuppetColumn {
+        self.resolve(&self.history.location())
+    }
+
+    /// Push `url` onto the history and render it when the router is mounted. A link
+    /// to the current URL replaces its entry so that going back leaves the page.
+    pub fn navigate(&self, url: &str) -> JsValueResult<()> {
+        match url == self.history.location() {
+            true => self.history.replace(url)?,
+            false => self.history.push(url)?,
+        }
+        self.render()
+    }
+
+    /// Replace the current history entry with `url` and render it when mounted
+    pub fn redirect(&self, url: &str) -> JsValueResult<()> {
+        self.history.replace(url)?;
+        self.render()
+    }
+
+    /// Links to the same origin are handled by the router instead of the browser
+    pub fn is_internal(href: &str) -> bool {
+        href.starts_with('/') && !href.starts_with("//")
+    }
+
+    fn render(&self) -> JsValueResult<()> {
+        let Some(mount_node) = self.mount_node.borrow().clone() else {
+            return Ok(());
+        };
+        let document = get_document();
+        let location = self.history.location();
+
+        let previous = self.rendered.borrow_mut().replace(location.clone());
+        if let Some(previous) = previous.as_ref() {
+            self.remember_focus(&mount_node, previous);
+        }
+
+        mount_node.set_inner_html("");
+        self.current().to_html(&document, &mount_node)?;
+        if let Some(label) = &self.skip_link {
+            mount_skip_link(&document, &mount_node, label)?;
+        }
+        restore_toasts(&mount_node)?;
+
+        // The first render leaves the focus where the browser put it
+        if previous.is_some() {
+            self.restore_focus(&mount_node, &location)?;
+        }
+
+        Ok(())
+    }
+
+    fn remember_focus(&self, mount_node: &Element, location: &str) {
+        let focused = get_document()
+            .active_element()
+            .filter(|active| mount_node.contains(Some(active)) && !active.id().is_empty());
+
+        if let Some(focused) = focused {
+            self.focus_history
+                .borrow_mut()
+                .insert(location.into(), focused.id());
+        }
+    }
+
+    /// Focus the element that was focused when users left `location`, or else the
+    /// new screen so that screen readers announce it
+    fn restore_focus(&self, mount_node: &Element, location: &str) -> JsValueResult<()> {
+        let remembered = self
+            .focus_history
+            .borrow()
+            .get(location)
+            .and_then(|id| get_document().get_element_by_id(id))
+            .filter(|element| mount_node.contains(Some(element)));
+
+        match remembered.or_else(|| screen_root(mount_node)) {
+            Some(element) => focus_element(&element),
+            None => Ok(()),
+        }
+    }
+
+    fn intercept_link(&self, event: &Event) {
+        if event.default_prevented() {
+            return;
+        }
+
+        if let Some(mouse_event) = event.dyn_ref::<MouseEvent>() {
+            if mouse_event.button() != 0
+                || mouse_event.ctrl_key()
+                || mouse_event.meta_key()
+                || mouse_event.shift_key()
+                || mouse_event.alt_key()
+            {
+                return;
+            }
+        }
+
+        let anchor = event
+            .target()
+            .and_then(|target| target.dyn_into::<Element>().ok())
+            .and_then(|target| target.closest("a[href]").ok().flatten());
+
+        if let Some(skip_link) = anchor
+            .as_ref()
+            .filter(|anchor| anchor.has_attribute("data-skip-link"))
+        {
+            // Jumping to the fragment would change the URL the router reads
+            event.prevent_default();
+
+            let target = skip_link
+                .get_attribute("href")
+                .and_then(|href| get_document().get_element_by_id(href.trim_start_matches('#')));
+            if let Err(error) = target.map(|target| focus_element(&target)).transpose() {
+                trace!("{:?}", error);
+            }
+
+            return;
+        }
+
+        let anchor = match anchor {
+            Some(anchor)
+                if !anchor.has_attribute("target") && !anchor.has_attribute("download") =>
+            {
+                anchor
+            }
+            _ => return,
+        };
+
+        match anchor.get_attribute("href") {
+            Some(href) if Self::is_internal(&href) => {
+                event.prevent_default();
+
+                if let Err(error) = self.navigate(&href) {
+                    trace!("{:?}", error);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Render the current route into `app_node`, which also stacks the [crate::notify]
+    /// toasts, and keep it in sync with the history
+    pub fn mount(self, app_node: &Element) -> JsValueResult<Rc<Self>> {
+        let (router, _) = self.start(app_node, false)?;
+
+        Ok(router)
+    }
+
+    /// Like [PuppetRouter::mount], but take over the markup a server rendered for the
+    /// current route instead of rendering it again, see [crate::PuppetElement::hydrate]
+    pub fn hydrate(self, app_node: &Element) -> JsValueResult<(Rc<Self>, Vec<PuppetMismatch>)> {
+        self.start(app_node, true)
+    }
+
+    fn start(
+        self,
+        app_node: &Element,
+        hydrate: bool,
+    ) -> JsValueResult<(Rc<Self>, Vec<PuppetMismatch>)> {
+        let router = Rc::new(self);
+        *router.mount_node.borrow_mut() = Some(app_node.clone());
+        mount_locale_root(app_node)?;
+
+        let document = get_document();
+        let screen = router.current();
+        let focus_ring = router.focus_ring.unwrap_or_else(|| {
+            screen
+                .to_element()
+                .style()
+                .map(|style| style.focus_ring())
+                .unwrap_or_default()
+        });
+        install_focus_ring(&document, &focus_ring)?;
+
+        let mismatches = if hydrate {
+            let mismatches = screen.hydrate(&document, app_node)?;
+            *router.rendered.borrow_mut() = Some(router.history.location());
+            if let Some(label) = &router.skip_link {
+                mount_skip_link(&document, app_node, label)?;
+            }
+
+            mismatches
+        } else {
+            router.render()?;
+            Vec::default()
+        };
+        mount_toasts(app_node)?;
+
+        let popstate_router = Rc::clone(&router);
+        let on_popstate = Closure::wrap(Box::new(move |_event: Event| {
+            if let Err(error) = popstate_router.render() {
+                trace!("{:?}", error);
+            }
+        }) as Box<dyn FnMut(Event)>);
+        get_window()
+            .add_event_listener_with_callback("popstate", on_popstate.as_ref().unchecked_ref())?;
+        on_popstate.forget();
+
+        let click_router = Rc::clone(&router);
+        let on_click =
+            Closure::wrap(
+                Box::new(move |event: Event| click_router.intercept_link(&event))
+                    as Box<dyn FnMut(Event)>,
+            );
+        app_node.add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref())?;
+        on_click.forget();
+
+        Ok((router, mismatches))
+    }
+}
+
+impl Debug for PuppetRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetRouter")
+            .field("routes", &self.routes)
+            .finish()
+    }
+}
@@ -0,0 +1,107 @@
+use log::trace;
+use std::borrow::Cow;
+
+/// Schemes a link may use, any other such as `javascript:` being dropped
+pub const SAFE_URL_SCHEMES: &[&str] = &["http", "https", "mailto", "tel"];
+
+/// Attributes holding URLs, rejected by [crate::PuppetElement::attribute] when unsafe
+pub(crate) const URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "cite",
+    "formaction",
+    "href",
+    "poster",
+    "src",
+    "srcset",
+    "xlink:href",
+];
+
+/// The URL if it is relative or uses one of [SAFE_URL_SCHEMES], `None` otherwise.
+///
+/// Browsers ignore tabs and newlines in URLs and leading control characters and
+/// spaces, so that `" java\tscript:"` still runs script: they are removed before
+/// the scheme is read.
+///
+/// ```
+/// use puppeteer_builders::sanitize_url;
+///
+/// assert_eq!(sanitize_url("/users?page=2").as_deref(), Some("/users?page=2"));
+/// assert_eq!(sanitize_url("https://example.com").as_deref(), Some("https://example.com"));
+/// assert_eq!(sanitize_url(" JavaScript:alert(1)"), None);
+/// assert_eq!(sanitize_url("java\nscript:alert(1)"), None);
+/// ```
+pub fn sanitize_url(url: &str) -> Option<Cow<'_, str>> {
+    sanitize(url, SAFE_URL_SCHEMES, false)
+}
+
+/// [sanitize_url] also letting images be inlined as `data:image/*` URLs
+pub fn sanitize_image_url(url: &str) -> Option<Cow<'_, str>> {
+    sanitize(url, SAFE_URL_SCHEMES, true)
+}
+
+fn sanitize<'u>(url: &'u str, schemes: &[&str], data_images: bool) -> Option<Cow<'u, str>> {
+    let trimmed = url.trim_matches(|character: char| character <= ' ');
+    let url = match trimmed.contains(['\t', '\n', '\r']) {
+        true => Cow::Owned(trimmed.replace(['\t', '\n', '\r'], "")),
+        false => Cow::Borrowed(trimmed),
+    };
+
+    let scheme = url
+        .find([':', '/', '?', '#'])
+        .filter(|index| url[*index..].starts_with(':'))
+        .map(|index| url[..index].to_ascii_lowercase());
+
+    let safe = match scheme {
+        None => true,
+        Some(scheme) if schemes.contains(&scheme.as_str()) => true,
+        Some(scheme) => {
+            data_images && scheme == "data" && url[5..].to_ascii_lowercase().starts_with("image/")
+        }
+    };
+
+    if !safe {
+        trace!("UNSAFE URL {:?}", url);
+
+        return None;
+    }
+
+    Some(url)
+}
+
+/// Whether `value` is safe for the attribute `name`, which only matters for the
+/// [URL_ATTRIBUTES] whatever the case they are written in as HTML ignores it
+pub(crate) fn is_safe_attribute(name: &str, value: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+
+    !URL_ATTRIBUTES.contains(&name.as_str()) || is_safe_url_attribute(&name, value)
+}
+
+/// Whether the URLs of the lowercase attribute `name` are safe, images being allowed
+/// `data:` URLs
+fn is_safe_url_attribute(name: &str, value: &str) -> bool {
+    match name {
+        "srcset" => value.split(',').all(|candidate| {
+            let src = candidate.split_whitespace().next().unwrap_or_default();
+            sanitize_image_url(src).is_some()
+        }),
+        "src" | "poster" => sanitize_image_url(value).is_some(),
+        _ => sanitize_url(value).is_some(),
+    }
+}
+
+/// The text of `html` without its tags, for backends that cannot show markup
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for character in html.chars() {
+        match character {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            character if !in_tag => text.push(character),
+            _ => (),
+        }
+    }
+
+    text
+}
@@ -0,0 +1,250 @@
+use crate::{fnv1a, get_document, to_css_text, Declarations, JsValueResult, PuppetStylesheet};
+use core::cell::{Cell, RefCell};
+use log::trace;
+use std::{borrow::Cow, collections::HashSet};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{MutationObserver, MutationObserverInit};
+
+thread_local! {
+    /// Scope classes whose rules were added to the document
+    static INSTALLED_SCOPES: RefCell<HashSet<String>> = RefCell::default();
+    /// Whether the document is watched for instances of scopes being removed
+    static WATCHING_SCOPES: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Rules written relative to the root of a node, for what a [crate::PuppetStyle]
+/// cannot express: child selectors, `::before` and `::after`, `:nth-child`...
+///
+/// In a selector `&` stands for the root as in CSS nesting, and a selector without
+/// it matches descendants of the root. The root is given a class derived from the
+/// rules, which are written once whatever the number of instances: into the
+/// stylesheet of server rendered pages, or into a `style` element of the document
+/// removed once no instance remains.
+///
+/// ```
+/// use puppeteer_builders::{puppet, PuppetScopedStyle, PuppetStylesheet};
+///
+/// let mut striped = PuppetScopedStyle::new();
+/// striped
+///     .rule("& > div:nth-child(odd)", [("background-color", "#EEEEEE")])
+///     .rule("p::before", [("content", r#""- ""#)]);
+///
+/// let column = puppet! { column { scoped(striped) row { paragraph("One") } } };
+/// let mut stylesheet = PuppetStylesheet::new();
+/// let html = column.to_element().to_html_string_with_stylesheet(&mut stylesheet);
+///
+/// let class_name = striped.class_name();
+/// assert!(html.starts_with(&format!(r#"<div class="{}">"#, class_name)));
+/// assert!(stylesheet
+///     .to_css()
+///     .contains(&format!(".{} p::before {{\n  content: \"- \";\n}}", class_name)));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetScopedStyle {
+    /// Selectors relative to the root and their declarations
+    rules: Vec<(Cow<'static, str>, Declarations)>,
+}
+
+impl PuppetScopedStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule for `selector`, ignored when the selector, a property or a value
+    /// could end the rule or the `style` element it is written in: `{`, `}`, `;`,
+    /// `<`, `@` and comments are not allowed.
+    pub fn rule<S, I, V>(&mut self, selector: S, declarations: I) -> &mut Self
+    where
+        S: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = (&'static str, V)>,
+        V: AsRef<str>,
+    {
+        let selector = selector.into();
+        let mut css = Declarations::default();
+
+        for (property, value) in declarations {
+            let value = value.as_ref().trim();
+
+            if !is_valid_property(property) || value.is_empty() || !is_valid_css(value) {
+                trace!("INVALID SCOPED DECLARATION {}: {:?}", property, value);
+
+                return self;
+            }
+
+            css.push((property, value.into()));
+        }
+
+        if selector.trim().is_empty() || !is_valid_css(&selector) || css.is_empty() {
+            trace!("INVALID SCOPED RULE {:?}", selector);
+
+            return self;
+        }
+
+        self.rules.push((selector, css));
+
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The class given to the root, derived from the rules so that every instance
+    /// shares it
+    pub fn class_name(&self) -> String {
+        let key = self
+            .rules
+            .iter()
+            .map(|(selector, declarations)| {
+                format!("{}{{{}}}", selector, to_css_text(declarations))
+            })
+            .collect::<String>();
+
+        format!("puppet-c{:08x}", fnv1a(key.as_bytes()))
+    }
+
+    /// The rules with their selectors scoped to `class_name`
+    pub(crate) fn scoped_rules(&self, class_name: &str) -> Vec<(String, Declarations)> {
+        self.rules
+            .iter()
+            .map(|(selector, declarations)| {
+                let selector = split_selectors(selector)
+                    .iter()
+                    .map(|selector| scope_selector(selector, class_name))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                (selector, declarations.clone())
+            })
+            .collect()
+    }
+}
+
+/// A property name, custom properties such as `--accent` included
+fn is_valid_property(property: &str) -> bool {
+    !property.is_empty()
+        && property
+            .chars()
+            .all(|character| matches!(character, 'a'..='z' | '0'..='9' | '-'))
+}
+
+/// Whether `css` stays within the selector or value it is written as
+fn is_valid_css(css: &str) -> bool {
+    !css.contains(['{', '}', ';', '<', '@', '\n', '\r']) && !css.contains("/*")
+}
+
+/// The selectors of a list, leaving the commas of `:is(a, b)` and the like alone
+fn split_selectors(selector: &str) -> Vec<&str> {
+    let mut selectors = Vec::default();
+    let mut depth = 0_usize;
+    let mut start = 0;
+
+    for (index, character) in selector.char_indices() {
+        match character {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                selectors.push(selector[start..index].trim());
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+    selectors.push(selector[start..].trim());
+
+    selectors
+}
+
+fn scope_selector(selector: &str, class_name: &str) -> String {
+    let root = format!(".{}", class_name);
+
+    match selector.contains('&') {
+        true => selector.replace('&', &root),
+        false => format!("{} {}", root, selector),
+    }
+}
+
+/// Add the rules of `scoped_style` to the document unless another instance did,
+/// returning the class of its root
+pub(crate) fn install_scoped_rules(scoped_style: &PuppetScopedStyle) -> JsValueResult<String> {
+    let class_name = scoped_style.class_name();
+
+    if INSTALLED_SCOPES.with(|installed| installed.borrow().contains(&class_name)) {
+        return Ok(class_name);
+    }
+
+    watch_scopes()?;
+
+    let mut stylesheet = PuppetStylesheet::new();
+    stylesheet.scope_class_name(scoped_style);
+
+    let document = get_document();
+    let style = document.create_element("style")?;
+    style.set_attribute("data-puppet-scope", &class_name)?;
+    style.set_text_content(Some(&stylesheet.to_css()));
+
+    document
+        .query_selector("head")?
+        .or(document.document_element())
+        .ok_or(JsValue::from_str("The document has no root element"))?
+        .append_child(&style)?;
+
+    INSTALLED_SCOPES.with(|installed| installed.borrow_mut().insert(class_name.clone()));
+
+    Ok(class_name)
+}
+
+/// Remove the rules of the scopes without any instance left in the document
+fn release_scoped_rules() -> JsValueResult<()> {
+    let document = get_document();
+    let installed = INSTALLED_SCOPES
+        .with(|installed| installed.borrow().iter().cloned().collect::<Vec<String>>());
+
+    for class_name in installed {
+        if document
+            .query_selector(&format!(".{}", class_name))?
+            .is_some()
+        {
+            continue;
+        }
+
+        if let Some(style) =
+            document.query_selector(&format!(r#"style[data-puppet-scope="{}"]"#, class_name))?
+        {
+            style.remove();
+        }
+
+        INSTALLED_SCOPES.with(|installed| installed.borrow_mut().remove(&class_name));
+    }
+
+    Ok(())
+}
+
+/// Release the rules of scopes after nodes are removed from the document. Mutations
+/// are delivered once the current task is over, so that a tree rendered again in
+/// place keeps its rules.
+fn watch_scopes() -> JsValueResult<()> {
+    if WATCHING_SCOPES.with(|watching| watching.replace(true)) {
+        return Ok(());
+    }
+
+    let on_mutation = Closure::wrap(Box::new(move |_records: JsValue| {
+        if let Err(error) = release_scoped_rules() {
+            trace!("{:?}", error);
+        }
+    }) as Box<dyn FnMut(JsValue)>);
+
+    let options = MutationObserverInit::new();
+    options.set_child_list(true);
+    options.set_subtree(true);
+
+    let root = get_document()
+        .document_element()
+        .ok_or(JsValue::from_str("The document has no root element"))?;
+    MutationObserver::new(on_mutation.as_ref().unchecked_ref())?
+        .observe_with_options(&root, &options)?;
+    // The observer lives as long as the document
+    on_mutation.forget();
+
+    Ok(())
+}
@@ -0,0 +1,325 @@
+use crate::{
+    current_direction, current_locale, escape_html_attribute, escape_html_text, PuppetChild,
+    PuppetColumn, PuppetElement, PuppetStylesheet, PuppetText,
+};
+use core::fmt::Write;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// File name of the stylesheet shared by every page of a [PuppetSite]
+pub const SITE_STYLESHEET: &str = "styles.css";
+
+/// Name of the page listing every other page, unless a page of that name is added
+pub const SITE_INDEX: &str = "index";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct PuppetSitePage {
+    /// Path of the page without its `.html` extension, such as `docs/intro`
+    name: String,
+    title: PuppetText,
+    column: PuppetColumn,
+}
+
+/// Named [PuppetColumn] pages exported as a static site, without wasm.
+///
+/// Every page becomes `<name>.html`, every style used by any page lands once in
+/// [SITE_STYLESHEET] and the sources of images that are not URLs are copied from
+/// the asset root, which defaults to the current directory. Image sources are
+/// written relative to the asset root and rewritten relative to each page.
+///
+/// ```no_run
+/// use puppeteer_builders::{puppet, PuppetSite};
+///
+/// let mut site = PuppetSite::new("Puppeteer");
+/// site.asset_root("assets")
+///     .page("getting-started", "Getting started", puppet! {
+///         column { row { heading("Getting started") image("Logo") { src("images/logo.png") } } }
+///     })
+///     .page("docs/layout", "Layout", puppet! { column { row { paragraph("Flexbox") } } });
+///
+/// // Writes getting-started.html, docs/layout.html, index.html, styles.css and
+/// // images/logo.png into `site`
+/// site.export("site").unwrap();
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PuppetSite {
+    title: PuppetText,
+    asset_root: PathBuf,
+    pages: Vec<PuppetSitePage>,
+}
+
+impl PuppetSite {
+    pub fn new<T>(title: T) -> Self
+    where
+        T: Into<PuppetText>,
+    {
+        Self {
+            title: title.into(),
+            asset_root: PathBuf::from("."),
+            pages: Vec::default(),
+        }
+    }
+
+    /// The directory image sources are copied from
+    pub fn asset_root<P>(&mut self, asset_root: P) -> &mut Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.asset_root = asset_root.into();
+
+        self
+    }
+
+    /// Add a page, replacing any previous page of the same name. Names are paths
+    /// relative to the exported directory, `.` and `..` segments being dropped.
+    pub fn page<T>(&mut self, name: &str, title: T, column: PuppetColumn) -> &mut Self
+    where
+        T: Into<PuppetText>,
+    {
+        let page = PuppetSitePage {
+            name: normalize_path(name),
+            title: title.into(),
+            column,
+        };
+
+        match self
+            .pages
+            .iter_mut()
+            .find(|previous| previous.name == page.name)
+        {
+            Some(previous) => *previous = page,
+            None => self.pages.push(page),
+        }
+
+        self
+    }
+
+    /// Every file of the site except the copied images, as paths relative to the
+    /// exported directory and their contents
+    pub fn render(&self) -> Vec<(String, String)> {
+        let mut stylesheet = PuppetStylesheet::new();
+        let mut files = Vec::default();
+
+        for page in &self.pages {
+            let element = page.column.to_element();
+            let html = self.render_page(&page.name, &page.title, element, &mut stylesheet);
+            files.push((format!("{}.html", page.name), html));
+        }
+
+        if !self.pages.iter().any(|page| page.name == SITE_INDEX) {
+            let html = self.render_page(SITE_INDEX, &self.title, self.index(), &mut stylesheet);
+            files.push((format!("{}.html", SITE_INDEX), html));
+        }
+
+        files.push((SITE_STYLESHEET.into(), stylesheet.to_css()));
+
+        files
+    }
+
+    /// Sources of the images to copy, relative to the asset root, without duplicates
+    pub fn assets(&self) -> Vec<String> {
+        let mut assets = Vec::default();
+
+        for page in &self.pages {
+            collect_assets(&page.column.to_element(), &mut assets);
+        }
+
+        assets.sort();
+        assets.dedup();
+
+        assets
+    }
+
+    /// Write the site into `directory`, creating it when needed
+    pub fn export<P>(&self, directory: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let directory = directory.as_ref();
+
+        for (path, contents) in self.render() {
+            write_file(&directory.join(path), contents.as_bytes())?;
+        }
+
+        for asset in self.assets() {
+            let source = self.asset_root.join(&asset);
+            let contents = fs::read(&source).map_err(|error| {
+                io::Error::new(
+                    error.kind(),
+                    format!("could not read image {}: {}", source.display(), error),
+                )
+            })?;
+
+            write_file(&directory.join(asset), &contents)?;
+        }
+
+        Ok(())
+    }
+
+    /// A list of links to every page
+    fn index(&self) -> PuppetElement {
+        let mut list = PuppetElement::new("ul");
+
+        for page in &self.pages {
+            let mut link = PuppetElement::new("a");
+            link.attribute("href", format!("{}.html", page.name))
+                .add_child(page.title.clone());
+
+            let mut item = PuppetElement::new("li");
+            item.add_child(link);
+            list.add_child(item);
+        }
+
+        let mut heading = PuppetElement::new("h1");
+        heading.add_child(self.title.clone());
+
+        let mut index = PuppetElement::new("div");
+        index.add_child(heading).add_child(list);
+
+        index
+    }
+
+    fn render_page(
+        &self,
+        name: &str,
+        title: &PuppetText,
+        mut body: PuppetElement,
+        stylesheet: &mut PuppetStylesheet,
+    ) -> String {
+        // Pages in subdirectories reach the root of the site through `..`
+        let root = "../".repeat(name.matches('/').count());
+        relocate_assets(&mut body, &root);
+
+        let mut title = title.resolve().into_owned();
+        if name != SITE_INDEX {
+            title.push_str(" - ");
+            title.push_str(&self.title.resolve());
+        }
+
+        let mut html = String::from("<!DOCTYPE html>\n");
+        // Writing to a String never fails
+        let _ = write!(html, r#"<html dir="{}""#, current_direction().to_html_dir());
+        if let Some(locale) = current_locale() {
+            let _ = write!(html, r#" lang="{}""#, escape_html_attribute(locale));
+        }
+        html.push_str(">\n<head>\n");
+        html.push_str("<meta charset=\"utf-8\">\n");
+        html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        let _ = writeln!(html, "<title>{}</title>", escape_html_text(&title));
+        let _ = writeln!(
+            html,
+            r#"<link rel="stylesheet" href="{}{}">"#,
+            root, SITE_STYLESHEET
+        );
+        html.push_str("</head>\n<body>\n<section id=\"app\">");
+        html.push_str(&body.to_html_string_with_stylesheet(stylesheet));
+        html.push_str("</section>\n</body>\n</html>\n");
+
+        html
+    }
+}
+
+/// Image sources that point into the site rather than to a URL
+fn local_asset(src: &str) -> Option<String> {
+    if src.is_empty() || src.starts_with("//") || src.contains(':') {
+        return None;
+    }
+
+    let path = normalize_path(src);
+
+    if path.is_empty() {
+        None
+    } else {
+        Some(path)
+    }
+}
+
+fn collect_assets(element: &PuppetElement, assets: &mut Vec<String>) {
+    if element.tag() == "img" {
+        if let Some(asset) = element
+            .get_attribute("src")
+            .and_then(|src| local_asset(&src.resolve()))
+        {
+            assets.push(asset);
+        }
+
+        if let Some(srcset) = element.get_attribute("srcset") {
+            assets.extend(
+                srcset_candidates(&srcset.resolve()).filter_map(|(src, _)| local_asset(src)),
+            );
+        }
+    }
+
+    for child in element.children() {
+        if let PuppetChild::Element(child) = child {
+            collect_assets(child, assets);
+        }
+    }
+}
+
+fn relocate_assets(element: &mut PuppetElement, root: &str) {
+    if element.tag() == "img" {
+        if let Some(asset) = element
+            .get_attribute("src")
+            .and_then(|src| local_asset(&src.resolve()))
+        {
+            element.attribute("src", format!("{}{}", root, asset));
+        }
+
+        if let Some(srcset) = element.get_attribute("srcset") {
+            let srcset = srcset_candidates(&srcset.resolve())
+                .map(|(src, descriptor)| {
+                    let src = match local_asset(src) {
+                        Some(asset) => format!("{}{}", root, asset),
+                        None => src.into(),
+                    };
+
+                    match descriptor {
+                        "" => src,
+                        descriptor => format!("{} {}", src, descriptor),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            element.attribute("srcset", srcset);
+        }
+    }
+
+    for child in element.children_mut() {
+        if let PuppetChild::Element(child) = child {
+            relocate_assets(child, root);
+        }
+    }
+}
+
+/// The sources of a `srcset` with their descriptors, which may be empty
+fn srcset_candidates(srcset: &str) -> impl Iterator<Item = (&str, &str)> {
+    srcset
+        .split(',')
+        .map(str::trim)
+        .filter(|candidate| !candidate.is_empty())
+        .map(
+            |candidate| match candidate.split_once(char::is_whitespace) {
+                Some((src, descriptor)) => (src, descriptor.trim()),
+                None => (candidate, ""),
+            },
+        )
+}
+
+/// `/` separated segments, without empty, `.` nor `..` segments
+fn normalize_path(path: &str) -> String {
+    path.split(['/', '\\'])
+        .filter(|segment| !matches!(*segment, "" | "." | ".."))
+        .collect::<Vec<&str>>()
+        .join("/")
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+
+    fs::write(path, contents)
+}
@@ -0,0 +1,263 @@
+//! Snapshot testing of rendered layouts in plain `cargo test`, without a browser
+//!
+//! ```no_run
+//! use puppeteer_builders::{puppet, snapshot::assert_snapshot};
+//!
+//! let column = puppet! { column { row { paragraph("Hello") } } };
+//!
+//! // Compared against `tests/snapshots/hello.snap`
+//! assert_snapshot("hello", &column.to_element());
+//! ```
+//!
+//! Run the tests with `PUPPETEER_UPDATE_SNAPSHOTS=1` to write new snapshots, or to
+//! overwrite the stored ones after an intended change. Without it a missing snapshot
+//! fails, so that a snapshot never committed cannot pass unnoticed.
+
+use crate::{PuppetChild, PuppetElement, PuppetStylesheet};
+use core::fmt::Write;
+use std::{env, fs, path::PathBuf};
+
+/// Set this environment variable to write missing snapshots and overwrite mismatching
+/// ones
+pub const UPDATE_SNAPSHOTS_VAR: &str = "PUPPETEER_UPDATE_SNAPSHOTS";
+
+/// Unchanged lines shown around every change of a diff
+const DIFF_CONTEXT: usize = 3;
+
+/// Pretty-print `element` with one tag per line, sorted attributes, collapsed
+/// whitespace and its styles moved into a deduplicated `<style>` block
+pub fn render_snapshot(element: &PuppetElement) -> String {
+    let mut stylesheet = PuppetStylesheet::new();
+    let mut snapshot = String::default();
+
+    write_element(element, 0, &mut stylesheet, &mut snapshot);
+
+    if !stylesheet.is_empty() {
+        snapshot.push_str("\n<style>\n");
+        snapshot.push_str(&stylesheet.to_css());
+        snapshot.push_str("</style>\n");
+    }
+
+    snapshot
+}
+
+fn write_element(
+    element: &PuppetElement,
+    depth: usize,
+    stylesheet: &mut PuppetStylesheet,
+    snapshot: &mut String,
+) {
+    let indent = "  ".repeat(depth);
+
+    let mut attributes = element
+        .attributes()
+        .iter()
+        .map(|(name, value)| (name.to_string(), Some(value.resolve().into_owned())))
+        .chain(
+            element
+                .dataset()
+                .iter()
+                .map(|(key, value)| (format!("data-{}", key), Some(value.to_string()))),
+        )
+        .collect::<Vec<(String, Option<String>)>>();
+
+    if let Some(class_name) = element.class_names(stylesheet, false) {
+        match attributes.iter_mut().find(|(name, _)| name == "class") {
+            Some((_, Some(classes))) => {
+                classes.push(' ');
+                classes.push_str(&class_name);
+            }
+            _ => attributes.push(("class".into(), Some(class_name))),
+        }
+    }
+
+    for handler in element.handlers() {
+        attributes.push((format!("on:{}", handler.event()), None));
+    }
+
+    attributes.sort();
+
+    let _ = write!(snapshot, "{}<{}", indent, element.tag());
+    for (name, value) in attributes {
+        match value {
+            Some(value) => {
+                let _ = write!(snapshot, r#" {}="{}""#, name, normalize_whitespace(&value));
+            }
+            None => {
+                let _ = write!(snapshot, " {}", name);
+            }
+        }
+    }
+    snapshot.push('>');
+
+    if element.is_void() {
+        snapshot.push('\n');
+        return;
+    }
+
+    match element.children() {
+        _ if element.get_raw_html().is_some() => snapshot.push_str(&normalize_whitespace(
+            element.get_raw_html().unwrap_or_default(),
+        )),
+        [] => (),
+        [PuppetChild::Text(text)] => snapshot.push_str(&normalize_whitespace(&text.resolve())),
+        children => {
+            snapshot.push('\n');
+
+            for child in children {
+                match child {
+                    PuppetChild::Element(child) => {
+                        write_element(child, depth + 1, stylesheet, snapshot)
+                    }
+                    PuppetChild::Text(text) => {
+                        let _ = writeln!(
+                            snapshot,
+                            "{}  {}",
+                            indent,
+                            normalize_whitespace(&text.resolve())
+                        );
+                    }
+                }
+            }
+
+            snapshot.push_str(&indent);
+        }
+    }
+
+    let _ = writeln!(snapshot, "</{}>", element.tag());
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Where the snapshot called `name` is stored, `tests/snapshots/<name>.snap` of the
+/// crate under test
+pub fn snapshot_path(name: &str) -> PathBuf {
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").unwrap_or_else(|| ".".into());
+
+    PathBuf::from(manifest_dir)
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.snap", name))
+}
+
+/// Compare `element` against the stored snapshot `name`, which is written instead when
+/// [UPDATE_SNAPSHOTS_VAR] is set
+#[track_caller]
+pub fn assert_snapshot(name: &str, element: &PuppetElement) {
+    assert_text_snapshot(name, &render_snapshot(element));
+}
+
+/// Compare any rendered text, such as [crate::PuppetTerminal::to_text], against the
+/// stored snapshot `name`
+#[track_caller]
+pub fn assert_text_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    let update = env::var_os(UPDATE_SNAPSHOTS_VAR).is_some();
+
+    match fs::read_to_string(&path) {
+        Ok(expected) if expected.replace("\r\n", "\n") == actual => return,
+        Ok(expected) if !update => panic!(
+            "snapshot `{}` does not match {}\n\n{}\nrun with {}=1 to update it",
+            name,
+            path.display(),
+            diff_lines(&expected, actual),
+            UPDATE_SNAPSHOTS_VAR
+        ),
+        Err(error) if !update => panic!(
+            "snapshot `{}` could not be read from {}: {}\nrun with {}=1 to write it",
+            name,
+            path.display(),
+            error,
+            UPDATE_SNAPSHOTS_VAR
+        ),
+        _ => (),
+    }
+
+    if let Some(directory) = path.parent() {
+        if let Err(error) = fs::create_dir_all(directory) {
+            panic!("could not create {}: {}", directory.display(), error);
+        }
+    }
+
+    if let Err(error) = fs::write(&path, actual) {
+        panic!("could not write snapshot {}: {}", path.display(), error);
+    }
+}
+
+/// A line diff of `expected` against `actual`, `-` marking removed lines and `+`
+/// added ones, with a few unchanged lines of context around each change
+pub fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<&str>>();
+    let actual = actual.lines().collect::<Vec<&str>>();
+
+    // Longest common subsequence of every pair of suffixes
+    let mut lengths = vec![vec![0_usize; actual.len() + 1]; expected.len() + 1];
+    for expected_index in (0..expected.len()).rev() {
+        for actual_index in (0..actual.len()).rev() {
+            lengths[expected_index][actual_index] =
+                if expected[expected_index] == actual[actual_index] {
+                    lengths[expected_index + 1][actual_index + 1] + 1
+                } else {
+                    lengths[expected_index + 1][actual_index]
+                        .max(lengths[expected_index][actual_index + 1])
+                };
+        }
+    }
+
+    let mut lines = Vec::default();
+    let (mut expected_index, mut actual_index) = (0, 0);
+    while expected_index < expected.len() || actual_index < actual.len() {
+        if expected_index < expected.len()
+            && actual_index < actual.len()
+            && expected[expected_index] == actual[actual_index]
+        {
+            lines.push((' ', expected[expected_index]));
+            expected_index += 1;
+            actual_index += 1;
+        } else if actual_index == actual.len()
+            || (expected_index < expected.len()
+                && lengths[expected_index + 1][actual_index]
+                    >= lengths[expected_index][actual_index + 1])
+        {
+            lines.push(('-', expected[expected_index]));
+            expected_index += 1;
+        } else {
+            lines.push(('+', actual[actual_index]));
+            actual_index += 1;
+        }
+    }
+
+    let changes = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (marker, _))| *marker != ' ')
+        .map(|(index, _)| index)
+        .collect::<Vec<usize>>();
+
+    let mut diff = String::default();
+    let mut last_written = None;
+    for (index, (marker, line)) in lines.iter().enumerate() {
+        let near_change = changes
+            .iter()
+            .any(|change| index + DIFF_CONTEXT >= *change && index <= change + DIFF_CONTEXT);
+
+        if !near_change {
+            continue;
+        }
+
+        if last_written.map_or(index > 0, |last: usize| index > last + 1) {
+            diff.push_str("...\n");
+        }
+
+        let _ = writeln!(diff, "{} {}", marker, line);
+        last_written = Some(index);
+    }
+
+    if last_written.is_some_and(|last| last + 1 < lines.len()) {
+        diff.push_str("...\n");
+    }
+
+    diff
+}
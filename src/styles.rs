@@ -1,6 +1,6 @@
 use crate::{
     AlignContent, AlignItems, AlignSelf, FlexDirection, FlexGap, FlexWrap, JsValueResult,
-    JustifyContent, PERCENTAGE_SYMBOL, PIXELS_SYMBOL,
+    JustifyContent, PuppetDirection, PERCENTAGE_SYMBOL, PIXELS_SYMBOL,
 };
 use web_sys::HtmlElement;
 
@@ -11,138 +11,232 @@ use core::{
 };
 use log::trace;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+/// Bytes reserved by [PuppetStyle::to_css], enough for most styles without growing
+const CSS_CAPACITY: usize = 320;
+
+/// The CSS properties of a node. Every property is optional and unset properties
+/// are not written, leaving them to inheritance and the cascade.
+///
+/// ```
+/// use puppeteer_builders::{PuppetColor, PuppetStyle, PuppetUnit};
+///
+/// assert_eq!(PuppetStyle::default().to_css(), "");
+///
+/// let mut style = PuppetStyle::default();
+/// style.padding(PuppetUnit::Pixels(8)).max_width(PuppetUnit::Pixels(0));
+/// assert_eq!(style.to_css(), "padding: 8px; max-width: 0;");
+///
+/// // The opinionated defaults of earlier versions
+/// assert_eq!(
+///     PuppetStyle::reset().to_css(),
+///     "margin: 0; padding: 2%; width: 100%; height: auto; background-color: #FFFFFF;"
+/// );
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct PuppetStyle {
-    padding: PuppetUnit,
-    margin: PuppetUnit,
-    width: PuppetLength,
-    height: PuppetLength,
+    pub(crate) padding: Option<PuppetUnit>,
+    pub(crate) margin: Option<PuppetUnit>,
+    pub(crate) width: Option<PuppetLength>,
+    pub(crate) height: Option<PuppetLength>,
     /// Based on screen pixels
-    min_width: PuppetUnit,
+    pub(crate) min_width: Option<PuppetUnit>,
     /// Based on screen pixels
-    max_width: PuppetUnit,
+    pub(crate) max_width: Option<PuppetUnit>,
     /// Based on screen pixels
-    min_height: PuppetUnit,
+    pub(crate) min_height: Option<PuppetUnit>,
     /// Based on screen pixels
-    max_height: PuppetUnit,
+    pub(crate) max_height: Option<PuppetUnit>,
     /// How children will be aligned inside the [Column]
-    alignment: PuppetAlignment,
-    background_color: PuppetColor,
+    pub(crate) alignment: Option<PuppetAlignment>,
+    pub(crate) background_color: Option<PuppetColor>,
+    /// How replaced content such as an image fills the box
+    pub(crate) object_fit: Option<ObjectFit>,
 }
 
-impl Default for PuppetStyle {
-    fn default() -> Self {
+impl PuppetStyle {
+    /// Every box padded by 2% of its container, as wide as it, without margin and
+    /// on a white background: what every style used to write.
+    ///
+    /// The margin is written `0`, where earlier versions wrote their zero margin as
+    /// `auto`, see [PuppetUnit::to_html_units]. Both leave no space around a block,
+    /// but `auto` also took up the free space around a flex item, centering it.
+    pub fn reset() -> Self {
         Self {
-            padding: PuppetUnit::Percentage(2),
-            margin: PuppetUnit::Pixels(0),
-            width: PuppetLength::Full,
-            height: PuppetLength::Normal,
-            min_width: PuppetUnit::Pixels(0),
-            min_height: PuppetUnit::Pixels(0),
-            max_width: PuppetUnit::Pixels(0),
-            max_height: PuppetUnit::Pixels(0),
-            alignment: PuppetAlignment::default(),
-            background_color: PuppetColor::Hex("FFFFFF"),
+            padding: Some(PuppetUnit::Percentage(2)),
+            margin: Some(PuppetUnit::Pixels(0)),
+            width: Some(PuppetLength::Full),
+            height: Some(PuppetLength::Normal),
+            background_color: Some(PuppetColor::Hex("FFFFFF")),
+            ..Self::default()
         }
     }
-}
 
-impl PuppetStyle {
     pub fn padding(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
-        self.padding = puppet_unit;
+        self.padding = Some(puppet_unit);
 
         self
     }
 
     pub fn margin(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
-        self.margin = puppet_unit;
+        self.margin = Some(puppet_unit);
 
         self
     }
 
     pub fn width(&mut self, puppet_length: PuppetLength) -> &mut Self {
-        self.width = puppet_length;
+        self.width = Some(puppet_length);
 
         self
     }
 
     pub fn height(&mut self, puppet_length: PuppetLength) -> &mut Self {
-        self.height = puppet_length;
+        self.height = Some(puppet_length);
 
         self
     }
 
     pub fn min_width(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
-        self.min_width = puppet_unit;
+        self.min_width = Some(puppet_unit);
 
         self
     }
 
     pub fn min_height(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
-        self.min_height = puppet_unit;
+        self.min_height = Some(puppet_unit);
 
         self
     }
 
     pub fn max_width(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
-        self.max_width = puppet_unit;
+        self.max_width = Some(puppet_unit);
 
         self
     }
 
     pub fn max_height(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
-        self.max_height = puppet_unit;
+        self.max_height = Some(puppet_unit);
 
         self
     }
 
     pub fn align_items(&mut self, alignment: PuppetAlignment) -> &mut Self {
-        self.alignment = alignment;
+        self.alignment = Some(alignment);
 
         self
     }
 
     pub fn background_color(&mut self, background_color: PuppetColor) -> &mut Self {
-        self.background_color = background_color;
+        self.background_color = Some(background_color);
 
         self
     }
 
+    pub fn object_fit(&mut self, object_fit: ObjectFit) -> &mut Self {
+        self.object_fit = Some(object_fit);
+
+        self
+    }
+
+    /// Whether the style sets no property at all
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The style with its rows laid out for `direction`.
+    ///
+    /// The DOM backend does not need this since browsers already mirror flex rows
+    /// under the `dir` of the mount root. Padding, margin and gaps are applied to
+    /// both sides, so only the flex direction changes.
+    pub fn for_direction(&self, direction: PuppetDirection) -> Self {
+        let mut style = *self;
+        style.alignment = self
+            .alignment
+            .map(|alignment| alignment.for_direction(direction));
+
+        style
+    }
+
+    /// Every CSS property set by this style, in order
+    pub fn css_properties(&self) -> Vec<(&'static str, String)> {
+        [
+            ("margin", self.margin.map(|margin| margin.to_html_units())),
+            (
+                "padding",
+                self.padding.map(|padding| padding.to_html_units()),
+            ),
+            (
+                "width",
+                self.width.map(|width| width.to_html_units().into()),
+            ),
+            (
+                "height",
+                self.height.map(|height| height.to_html_units().into()),
+            ),
+            (
+                "min-width",
+                self.min_width.map(|min_width| min_width.to_html_units()),
+            ),
+            (
+                "min-height",
+                self.min_height.map(|min_height| min_height.to_html_units()),
+            ),
+            (
+                "max-width",
+                self.max_width.map(|max_width| max_width.to_html_units()),
+            ),
+            (
+                "max-height",
+                self.max_height.map(|max_height| max_height.to_html_units()),
+            ),
+            (
+                "background-color",
+                self.background_color.map(|color| color.to_html_color()),
+            ),
+            (
+                "object-fit",
+                self.object_fit
+                    .map(|object_fit| object_fit.to_html_fit().into()),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(property, value)| value.map(|value| (property, value)))
+        .chain(
+            self.alignment
+                .iter()
+                .flat_map(PuppetAlignment::css_properties),
+        )
+        .collect()
+    }
+
+    /// The declarations of this style as written in a `style` attribute
+    pub fn to_css(&self) -> String {
+        let mut css = String::with_capacity(CSS_CAPACITY);
+
+        for (property, value) in self.css_properties() {
+            if !css.is_empty() {
+                css.push(' ');
+            }
+
+            css.push_str(property);
+            css.push_str(": ");
+            css.push_str(&value);
+            css.push(';');
+        }
+
+        css
+    }
+
+    /// Replaces the inline style of `target` with [PuppetStyle::to_css] through
+    /// `cssText`, crossing the wasm boundary twice whatever the number of properties
+    /// instead of twice per property
     pub fn to_html(&self, target: &HtmlElement) -> JsValueResult<()> {
-        // FIXME find an efficient way to bundle all css Properties at once
-        target
-            .style()
-            .set_property("margin", &self.margin.to_html_units())?;
-        target
-            .style()
-            .set_property("padding", &self.padding.to_html_units())?;
-        target
-            .style()
-            .set_property("width", self.width.to_html_units())?;
-        target
-            .style()
-            .set_property("height", self.height.to_html_units())?;
-        target
-            .style()
-            .set_property("min-width", &self.min_width.to_html_units())?;
-        target
-            .style()
-            .set_property("min-height", &self.min_height.to_html_units())?;
-        target
-            .style()
-            .set_property("max-width", &self.max_width.to_html_units())?;
-        target
-            .style()
-            .set_property("max-height", &self.max_height.to_html_units())?;
-        target
-            .style()
-            .set_property("background-color", &self.background_color.to_html_color())?;
+        target.style().set_css_text(&self.to_css());
 
         Ok(())
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
 pub enum Transparency {
     // Zero on alpha channel
     Full,
@@ -156,6 +250,7 @@ pub enum Transparency {
     Eighty,
     Ninety,
     // Color is opaque on alpha channel
+    #[default]
     Opaque,
 }
 
@@ -175,11 +270,22 @@ impl Transparency {
             Self::Opaque => "1",
         }
     }
-}
 
-impl Default for Transparency {
-    fn default() -> Self {
-        Self::Opaque
+    /// The alpha channel between `0.0`, fully transparent, and `1.0`
+    pub fn to_opacity(&self) -> f32 {
+        match self {
+            Self::Full => 0.0,
+            Self::Ten => 0.1,
+            Self::Twenty => 0.2,
+            Self::Thirty => 0.3,
+            Self::Forty => 0.4,
+            Self::Fifty => 0.5,
+            Self::Sixty => 0.6,
+            Self::Seventy => 0.7,
+            Self::Eighty => 0.8,
+            Self::Ninety => 0.9,
+            Self::Opaque => 1.0,
+        }
     }
 }
 
@@ -263,8 +369,8 @@ impl PuppetColor {
             PuppetColor::Hex(hex_color) => {
                 if PuppetColor::hex_color_constraints(&hex_color.to_lowercase()) {
                     let mut formatted_hex_color = String::default();
-                    formatted_hex_color.push_str("#");
-                    formatted_hex_color.push_str(&hex_color);
+                    formatted_hex_color.push('#');
+                    formatted_hex_color.push_str(hex_color);
 
                     formatted_hex_color
                 } else {
@@ -282,6 +388,33 @@ impl PuppetColor {
             }
         }
     }
+
+    /// The red, green and blue channels, ignoring transparency, or `None` for an
+    /// invalid hex color
+    pub fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        match self {
+            PuppetColor::Hex(hex_color) => {
+                if !hex_color.is_ascii() || hex_color.len() != 6 {
+                    return None;
+                }
+
+                let channel = |index: usize| u8::from_str_radix(&hex_color[index..index + 2], 16);
+
+                match (channel(0), channel(2), channel(4)) {
+                    (Ok(r), Ok(g), Ok(b)) => Some((r, g, b)),
+                    _ => None,
+                }
+            }
+            PuppetColor::Rgb(r, g, b) | PuppetColor::Rgba(r, g, b, _) => Some((*r, *g, *b)),
+        }
+    }
+
+    pub fn opacity(&self) -> f32 {
+        match self {
+            PuppetColor::Rgba(_, _, _, a) => a.to_opacity(),
+            _ => 1.0,
+        }
+    }
 }
 
 impl Default for PuppetColor {
@@ -337,11 +470,14 @@ pub enum PuppetUnit {
 }
 
 impl PuppetUnit {
+    /// The unit as CSS, zero being `0` whatever the unit. Earlier versions wrote a
+    /// zero as `auto`, so that `max-width` set to zero did not limit anything, and a
+    /// zero margin centered flex items: a style relying on it now sets `0`.
     pub fn to_html_units(&self) -> String {
         match self {
             PuppetUnit::Pixels(units) => {
                 if *units == 0_u16 {
-                    "auto".into()
+                    "0".into()
                 } else {
                     // FIXME move format_args to own function for reusability
                     format!("{}", format_args!("{:?}{}", units, PIXELS_SYMBOL))
@@ -349,7 +485,7 @@ impl PuppetUnit {
             }
             PuppetUnit::Percentage(units) => {
                 if *units == 0_u8 {
-                    "auto".into()
+                    "0".into()
                 } else {
                     // FIXME move format_args to own function for reusability
                     format!("{}", format_args!("{:?}{}", units, PERCENTAGE_SYMBOL))
@@ -377,6 +513,65 @@ impl Debug for PuppetUnit {
     }
 }
 
+/// How an image, or other replaced content, is resized to fill its box
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum ObjectFit {
+    /// Stretched to the box, the browser default
+    #[default]
+    Fill,
+    /// Scaled to fit inside the box, keeping its aspect ratio
+    Contain,
+    /// Scaled to cover the box, keeping its aspect ratio and cropping the rest
+    Cover,
+    None,
+    /// The smaller of [ObjectFit::None] and [ObjectFit::Contain]
+    ScaleDown,
+}
+
+impl ObjectFit {
+    pub fn to_html_fit(&self) -> &'static str {
+        match self {
+            Self::Fill => "fill",
+            Self::Contain => "contain",
+            Self::Cover => "cover",
+            Self::None => "none",
+            Self::ScaleDown => "scale-down",
+        }
+    }
+}
+
+impl Debug for ObjectFit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}",
+            match self {
+                Self::Fill => "ObjectFit::Fill",
+                Self::Contain => "ObjectFit::Contain",
+                Self::Cover => "ObjectFit::Cover",
+                Self::None => "ObjectFit::None",
+                Self::ScaleDown => "ObjectFit::ScaleDown",
+            }
+        )
+    }
+}
+
+impl Display for ObjectFit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Fill => "Fill",
+                Self::Contain => "Contain",
+                Self::Cover => "Cover",
+                Self::None => "None",
+                Self::ScaleDown => "ScaleDown",
+            }
+        )
+    }
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum PuppetLength {
     ViewPortWidth,
@@ -456,6 +651,57 @@ pub enum PuppetAlignment {
     Grid,
 }
 
+impl PuppetAlignment {
+    /// The `display` of the container and the properties laying its children out, as
+    /// [crate::PuppetElement::compute_layout] assumes them
+    pub fn css_properties(&self) -> Vec<(&'static str, String)> {
+        match self {
+            PuppetAlignment::Flex {
+                flex_direction,
+                flex_wrap,
+                justify_content,
+                align_self,
+                align_items,
+                align_content,
+                gap,
+            } => vec![
+                ("display", "flex".into()),
+                ("flex-direction", flex_direction.to_html_direction().into()),
+                ("flex-wrap", flex_wrap.to_html_wrap().into()),
+                ("justify-content", justify_content.to_html_justify().into()),
+                ("align-self", align_self.to_html_align().into()),
+                ("align-items", align_items.to_html_align().into()),
+                ("align-content", align_content.to_html_align().into()),
+                gap.to_html_gap(),
+            ],
+            PuppetAlignment::Grid => vec![("display", "grid".into())],
+        }
+    }
+
+    pub fn for_direction(&self, direction: PuppetDirection) -> Self {
+        match *self {
+            PuppetAlignment::Flex {
+                flex_direction,
+                flex_wrap,
+                justify_content,
+                align_self,
+                align_items,
+                align_content,
+                gap,
+            } => PuppetAlignment::Flex {
+                flex_direction: flex_direction.for_direction(direction),
+                flex_wrap,
+                justify_content,
+                align_self,
+                align_items,
+                align_content,
+                gap,
+            },
+            PuppetAlignment::Grid => PuppetAlignment::Grid,
+        }
+    }
+}
+
 impl Default for PuppetAlignment {
     fn default() -> Self {
         PuppetAlignment::Flex {
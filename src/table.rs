@@ -0,0 +1,796 @@
+use crate::{JsValueResult, PuppetChild, PuppetElement, PuppetLength, PuppetStyle, PuppetText};
+use core::{
+    cell::RefCell,
+    cmp::Ordering,
+    fmt,
+    fmt::{Debug, Display},
+};
+use log::trace;
+use std::{collections::BTreeSet, rc::Rc};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Document, Element, Event, HtmlElement};
+
+const PREVIOUS_PAGE_LABEL: &str = "Previous";
+const NEXT_PAGE_LABEL: &str = "Next";
+const PAGINATION_LABEL: &str = "Pagination";
+const SELECT_ALL_LABEL: &str = "Select all rows";
+const SELECT_ROW_LABEL: &str = "Select row";
+
+/// Builds the content of a cell from a row
+pub type PuppetCellRenderer<T> = Rc<dyn Fn(&T) -> PuppetChild>;
+/// Orders two rows of a sortable column
+pub type PuppetRowComparator<T> = Rc<dyn Fn(&T, &T) -> Ordering>;
+
+/// How the content of the cells of a column is aligned, following the text direction
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum CellAlignment {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+impl CellAlignment {
+    pub fn to_html_text_align(&self) -> &'static str {
+        match self {
+            Self::Start => "start",
+            Self::Center => "center",
+            Self::End => "end",
+        }
+    }
+}
+
+impl Debug for CellAlignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Start => "Start",
+                Self::Center => "Center",
+                Self::End => "End",
+            }
+        )
+    }
+}
+
+impl Display for CellAlignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    pub fn reversed(&self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+
+    pub fn to_html_aria_sort(&self) -> &'static str {
+        match self {
+            Self::Ascending => "ascending",
+            Self::Descending => "descending",
+        }
+    }
+}
+
+impl Debug for SortDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Ascending => "Ascending",
+                Self::Descending => "Descending",
+            }
+        )
+    }
+}
+
+impl Display for SortDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// One column of a [PuppetTable], rendering a cell from every row
+pub struct PuppetTableColumn<T> {
+    header: PuppetText,
+    width: PuppetLength,
+    alignment: CellAlignment,
+    render: PuppetCellRenderer<T>,
+    compare: Option<PuppetRowComparator<T>>,
+}
+
+impl<T> PuppetTableColumn<T> {
+    /// A column titled `header` whose cells are text or elements built by `render`
+    pub fn new<H, F, C>(header: H, render: F) -> Self
+    where
+        H: Into<PuppetText>,
+        F: Fn(&T) -> C + 'static,
+        C: Into<PuppetChild>,
+    {
+        Self {
+            header: header.into(),
+            width: PuppetLength::Normal,
+            alignment: CellAlignment::default(),
+            render: Rc::new(move |row| render(row).into()),
+            compare: Option::default(),
+        }
+    }
+
+    pub fn width(&mut self, width: PuppetLength) -> &mut Self {
+        self.width = width;
+
+        self
+    }
+
+    pub fn alignment(&mut self, alignment: CellAlignment) -> &mut Self {
+        self.alignment = alignment;
+
+        self
+    }
+
+    /// Let users sort the table by this column, in the order of `compare`
+    pub fn sortable_by<F>(&mut self, compare: F) -> &mut Self
+    where
+        F: Fn(&T, &T) -> Ordering + 'static,
+    {
+        self.compare = Some(Rc::new(compare));
+
+        self
+    }
+
+    /// Let users sort the table by this column, in the order of the keys
+    pub fn sortable_by_key<F, K>(&mut self, key: F) -> &mut Self
+    where
+        F: Fn(&T) -> K + 'static,
+        K: Ord,
+    {
+        self.sortable_by(move |left, right| key(left).cmp(&key(right)))
+    }
+
+    pub fn is_sortable(&self) -> bool {
+        self.compare.is_some()
+    }
+
+    /// Width and alignment, shared by the header and every cell of the column
+    fn cell_style(&self, sticky: bool) -> String {
+        let mut style = format!(
+            "width: {}; text-align: {};",
+            self.width.to_html_units(),
+            self.alignment.to_html_text_align()
+        );
+
+        if sticky {
+            style.push_str(" position: sticky; top: 0;");
+        }
+
+        style
+    }
+}
+
+impl<T> Clone for PuppetTableColumn<T> {
+    fn clone(&self) -> Self {
+        Self {
+            header: self.header.clone(),
+            width: self.width,
+            alignment: self.alignment,
+            render: Rc::clone(&self.render),
+            compare: self.compare.clone(),
+        }
+    }
+}
+
+impl<T> Debug for PuppetTableColumn<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetTableColumn")
+            .field("header", &self.header)
+            .field("width", &self.width)
+            .field("alignment", &self.alignment)
+            .field("sortable", &self.is_sortable())
+            .finish()
+    }
+}
+
+/// Typed rows shown as a semantic `<table>`, sorted, paged and selected on the
+/// client.
+///
+/// Sorting and paging only change which rows are shown, selection is kept by the
+/// index of the rows in [PuppetTable::rows] so it survives both.
+///
+/// ```
+/// use puppeteer_builders::{CellAlignment, PuppetTable, PuppetTableColumn, SortDirection};
+///
+/// struct User {
+///     name: &'static str,
+///     age: u8,
+/// }
+///
+/// let mut age = PuppetTableColumn::new("Age", |user: &User| user.age.to_string());
+/// age.alignment(CellAlignment::End).sortable_by_key(|user| user.age);
+///
+/// let mut table = PuppetTable::new();
+/// table
+///     .add_column(PuppetTableColumn::new("Name", |user: &User| user.name))
+///     .add_column(age)
+///     .extend_rows([User { name: "Ada", age: 36 }, User { name: "Alan", age: 41 }])
+///     .sort_by(1, SortDirection::Descending);
+///
+/// let names = table.visible_rows().map(|(_, user)| user.name).collect::<Vec<_>>();
+/// assert_eq!(names, ["Alan", "Ada"]);
+/// ```
+pub struct PuppetTable<T> {
+    table_id: Option<&'static str>,
+    caption: Option<PuppetText>,
+    style: Option<PuppetStyle>,
+    columns: Vec<PuppetTableColumn<T>>,
+    rows: Vec<T>,
+    sort: Option<(usize, SortDirection)>,
+    page_size: Option<usize>,
+    page: usize,
+    selectable: bool,
+    selected: BTreeSet<usize>,
+    sticky_header: bool,
+}
+
+impl<T> PuppetTable<T> {
+    pub fn new() -> Self {
+        Self {
+            table_id: Option::default(),
+            caption: Option::default(),
+            style: Option::default(),
+            columns: Vec::default(),
+            rows: Vec::default(),
+            sort: Option::default(),
+            page_size: Option::default(),
+            page: 0,
+            selectable: false,
+            selected: BTreeSet::default(),
+            sticky_header: false,
+        }
+    }
+
+    pub fn table_id(&mut self, table_id: &'static str) -> &mut Self {
+        self.table_id = Some(table_id);
+
+        self
+    }
+
+    pub fn caption<C>(&mut self, caption: C) -> &mut Self
+    where
+        C: Into<PuppetText>,
+    {
+        self.caption = Some(caption.into());
+
+        self
+    }
+
+    pub fn set_style(&mut self, style: &PuppetStyle) -> &mut Self {
+        self.style = Some(*style);
+
+        self
+    }
+
+    pub fn add_column(&mut self, column: PuppetTableColumn<T>) -> &mut Self {
+        self.columns.push(column);
+
+        self
+    }
+
+    pub fn add_row(&mut self, row: T) -> &mut Self {
+        self.rows.push(row);
+
+        self
+    }
+
+    pub fn extend_rows<I>(&mut self, rows: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.rows.extend(rows);
+
+        self
+    }
+
+    pub fn rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    pub fn columns(&self) -> &[PuppetTableColumn<T>] {
+        &self.columns
+    }
+
+    /// Keep the header visible while the rows scroll
+    pub fn sticky_header(&mut self, sticky_header: bool) -> &mut Self {
+        self.sticky_header = sticky_header;
+
+        self
+    }
+
+    /// Sort the rows by a sortable column, ignored for other columns
+    pub fn sort_by(&mut self, column: usize, direction: SortDirection) -> &mut Self {
+        if self
+            .columns
+            .get(column)
+            .is_some_and(PuppetTableColumn::is_sortable)
+        {
+            self.sort = Some((column, direction));
+            self.page = 0;
+        }
+
+        self
+    }
+
+    /// Sort by `column` in ascending order, or reverse the order when the table is
+    /// already sorted by it, like clicking a column header
+    pub fn toggle_sort(&mut self, column: usize) -> &mut Self {
+        let direction = match self.sort {
+            Some((sorted, direction)) if sorted == column => direction.reversed(),
+            _ => SortDirection::Ascending,
+        };
+
+        self.sort_by(column, direction)
+    }
+
+    pub fn sort(&self) -> Option<(usize, SortDirection)> {
+        self.sort
+    }
+
+    /// Show `page_size` rows at once, or every row with `None`
+    pub fn page_size(&mut self, page_size: Option<usize>) -> &mut Self {
+        self.page_size = page_size.filter(|page_size| *page_size > 0);
+        self.page = 0;
+
+        self
+    }
+
+    /// Go to the zero based `page`, clamped to the last page
+    pub fn page(&mut self, page: usize) -> &mut Self {
+        self.page = page.min(self.page_count() - 1);
+
+        self
+    }
+
+    pub fn current_page(&self) -> usize {
+        self.page.min(self.page_count() - 1)
+    }
+
+    /// At least one page, even without any row
+    pub fn page_count(&self) -> usize {
+        match self.page_size {
+            Some(page_size) => self.rows.len().div_ceil(page_size).max(1),
+            None => 1,
+        }
+    }
+
+    pub fn selectable(&mut self, selectable: bool) -> &mut Self {
+        self.selectable = selectable;
+
+        self
+    }
+
+    pub fn select(&mut self, row: usize, selected: bool) -> &mut Self {
+        if row < self.rows.len() {
+            match selected {
+                true => self.selected.insert(row),
+                false => self.selected.remove(&row),
+            };
+        }
+
+        self
+    }
+
+    pub fn is_selected(&self, row: usize) -> bool {
+        self.selected.contains(&row)
+    }
+
+    /// Select every row of the current page, or unselect them when they all are
+    pub fn toggle_page_selection(&mut self) -> &mut Self {
+        let page_rows = self
+            .visible_rows()
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+        let selected = !page_rows.iter().all(|row| self.selected.contains(row));
+
+        for row in page_rows {
+            self.select(row, selected);
+        }
+
+        self
+    }
+
+    /// Indexes and rows that are selected, in the order of [PuppetTable::rows]
+    pub fn selected(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.selected
+            .iter()
+            .map(|index| (*index, &self.rows[*index]))
+    }
+
+    /// Indexes of every row in the current sort order
+    fn sorted_indexes(&self) -> Vec<usize> {
+        let mut indexes = (0..self.rows.len()).collect::<Vec<usize>>();
+
+        if let Some((column, direction)) = self.sort {
+            if let Some(compare) = &self.columns[column].compare {
+                indexes.sort_by(|left, right| {
+                    let ordering = compare(&self.rows[*left], &self.rows[*right]);
+
+                    match direction {
+                        SortDirection::Ascending => ordering,
+                        SortDirection::Descending => ordering.reverse(),
+                    }
+                });
+            }
+        }
+
+        indexes
+    }
+
+    /// Rows of the current page in the current order, with their index in
+    /// [PuppetTable::rows]
+    pub fn visible_rows(&self) -> impl Iterator<Item = (usize, &T)> {
+        let page_size = self.page_size.unwrap_or(self.rows.len());
+
+        self.sorted_indexes()
+            .into_iter()
+            .skip(self.current_page() * page_size)
+            .take(page_size)
+            .map(move |index| (index, &self.rows[index]))
+    }
+
+    /// The table followed by its pagination, inside a `div`
+    pub fn to_element(&self) -> PuppetElement {
+        let mut table = PuppetElement::new("table");
+
+        if let Some(table_id) = self.table_id {
+            table.attribute("id", table_id);
+        }
+
+        if let Some(style) = &self.style {
+            table.set_style(style);
+        }
+
+        if let Some(caption) = &self.caption {
+            let mut caption_element = PuppetElement::new("caption");
+            caption_element.add_child(caption.clone());
+            table.add_child(caption_element);
+        }
+
+        if self.selectable {
+            table.attribute("aria-multiselectable", "true");
+        }
+
+        table.add_child(self.header()).add_child(self.body());
+
+        let mut container = PuppetElement::new("div");
+        container.add_child(table);
+
+        if self.page_count() > 1 {
+            container.add_child(self.pagination());
+        }
+
+        container
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+
+    fn header(&self) -> PuppetElement {
+        let mut header_row = PuppetElement::new("tr");
+
+        if self.selectable {
+            let page_rows = self.visible_rows().collect::<Vec<(usize, &T)>>();
+            let all_selected = !page_rows.is_empty()
+                && page_rows.iter().all(|(index, _)| self.is_selected(*index));
+
+            let mut cell = PuppetElement::new("th");
+            cell.attribute("scope", "col").add_child(checkbox(
+                SELECT_ALL_LABEL,
+                "page",
+                all_selected,
+            ));
+            if self.sticky_header {
+                cell.attribute("style", "position: sticky; top: 0;");
+            }
+            header_row.add_child(cell);
+        }
+
+        for (index, column) in self.columns.iter().enumerate() {
+            let mut cell = PuppetElement::new("th");
+            cell.attribute("scope", "col")
+                .attribute("style", column.cell_style(self.sticky_header));
+
+            match (column.is_sortable(), self.sort) {
+                (true, Some((sorted, direction))) if sorted == index => {
+                    cell.attribute("aria-sort", direction.to_html_aria_sort());
+                }
+                (true, _) => {
+                    cell.attribute("aria-sort", "none");
+                }
+                _ => (),
+            }
+
+            if column.is_sortable() {
+                let mut button = PuppetElement::new("button");
+                button
+                    .attribute("type", "button")
+                    .attribute("data-sort", index.to_string())
+                    .add_child(column.header.clone());
+                cell.add_child(button);
+            } else {
+                cell.add_child(column.header.clone());
+            }
+
+            header_row.add_child(cell);
+        }
+
+        let mut header = PuppetElement::new("thead");
+        header.add_child(header_row);
+
+        header
+    }
+
+    fn body(&self) -> PuppetElement {
+        let mut body = PuppetElement::new("tbody");
+
+        for (index, row) in self.visible_rows() {
+            let mut table_row = PuppetElement::new("tr");
+
+            if self.selectable {
+                let selected = self.is_selected(index);
+                table_row.attribute("aria-selected", selected.to_string());
+
+                let mut cell = PuppetElement::new("td");
+                cell.add_child(checkbox(SELECT_ROW_LABEL, &index.to_string(), selected));
+                table_row.add_child(cell);
+            }
+
+            for column in &self.columns {
+                let mut cell = PuppetElement::new("td");
+                cell.attribute("style", column.cell_style(false))
+                    .add_child((column.render)(row));
+                table_row.add_child(cell);
+            }
+
+            body.add_child(table_row);
+        }
+
+        body
+    }
+
+    fn pagination(&self) -> PuppetElement {
+        let page = self.current_page();
+        let last_page = self.page_count() - 1;
+
+        let page_button = |label: &'static str, target: usize, disabled: bool| {
+            let mut button = PuppetElement::new("button");
+            button
+                .attribute("type", "button")
+                .attribute("data-page", target.to_string())
+                .add_child(label);
+            if disabled {
+                button.attribute("disabled", "");
+            }
+
+            button
+        };
+
+        let mut status = PuppetElement::new("span");
+        status.attribute("aria-live", "polite").add_child(format!(
+            "{} / {}",
+            page + 1,
+            last_page + 1
+        ));
+
+        let mut navigation = PuppetElement::new("nav");
+        navigation
+            .attribute("aria-label", PAGINATION_LABEL)
+            .add_child(page_button(
+                PREVIOUS_PAGE_LABEL,
+                page.saturating_sub(1),
+                page == 0,
+            ))
+            .add_child(status)
+            .add_child(page_button(
+                NEXT_PAGE_LABEL,
+                (page + 1).min(last_page),
+                page == last_page,
+            ));
+
+        navigation
+    }
+}
+
+impl<T: 'static> PuppetTable<T> {
+    /// Render the table at the end of `parent_node` and update it in place when
+    /// users sort, page or select rows
+    pub fn mount(
+        self,
+        document: &Document,
+        parent_node: &Element,
+    ) -> JsValueResult<Rc<PuppetTableHandle<T>>> {
+        let container = document.create_element("div")?;
+        parent_node.append_with_node_1(&container)?;
+
+        let handle = Rc::new(PuppetTableHandle {
+            table: RefCell::new(self),
+            document: document.clone(),
+            container: container.clone(),
+            on_change: RefCell::default(),
+        });
+        handle.render()?;
+
+        let weak_handle = Rc::downgrade(&handle);
+        let on_click = Closure::wrap(Box::new(move |event: Event| {
+            if let Some(handle) = weak_handle.upgrade() {
+                if let Err(error) = handle.on_click(&event) {
+                    trace!("{:?}", error);
+                }
+            }
+        }) as Box<dyn FnMut(Event)>);
+        container.add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref())?;
+        // The listener lives as long as the table it is attached to
+        on_click.forget();
+
+        Ok(handle)
+    }
+}
+
+impl<T> Default for PuppetTable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for PuppetTable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetTable")
+            .field("table_id", &self.table_id)
+            .field("columns", &self.columns)
+            .field("rows", &self.rows)
+            .field("sort", &self.sort)
+            .field("page_size", &self.page_size)
+            .field("page", &self.page)
+            .field("selected", &self.selected)
+            .finish()
+    }
+}
+
+type TableListener<T> = Rc<dyn Fn(&PuppetTable<T>)>;
+
+/// A mounted [PuppetTable]
+pub struct PuppetTableHandle<T> {
+    table: RefCell<PuppetTable<T>>,
+    document: Document,
+    container: Element,
+    on_change: RefCell<Option<TableListener<T>>>,
+}
+
+impl<T> PuppetTableHandle<T> {
+    pub fn with_table<F, R>(&self, callback: F) -> R
+    where
+        F: FnOnce(&PuppetTable<T>) -> R,
+    {
+        callback(&self.table.borrow())
+    }
+
+    /// Change the table, then render it again
+    pub fn update<F>(&self, callback: F) -> JsValueResult<()>
+    where
+        F: FnOnce(&mut PuppetTable<T>),
+    {
+        callback(&mut self.table.borrow_mut());
+
+        self.render()
+    }
+
+    /// Called after users sorted, paged or selected rows
+    pub fn on_change<F>(&self, callback: F)
+    where
+        F: Fn(&PuppetTable<T>) + 'static,
+    {
+        *self.on_change.borrow_mut() = Some(Rc::new(callback));
+    }
+
+    fn render(&self) -> JsValueResult<()> {
+        self.container.set_inner_html("");
+        self.table
+            .borrow()
+            .to_element()
+            .to_html(&self.document, &self.container)
+    }
+
+    fn on_click(&self, event: &Event) -> JsValueResult<()> {
+        let Some(target) = event
+            .target()
+            .and_then(|target| target.dyn_into::<Element>().ok())
+        else {
+            return Ok(());
+        };
+
+        // The control that was clicked, to focus it again once rendered anew
+        let mut control = None;
+
+        for attribute in ["data-sort", "data-page", "data-select"] {
+            let Some(element) = target.closest(&format!("[{}]", attribute))? else {
+                continue;
+            };
+            let Some(value) = element.get_attribute(attribute) else {
+                continue;
+            };
+
+            let mut table = self.table.borrow_mut();
+            match (attribute, value.parse::<usize>()) {
+                ("data-sort", Ok(column)) => {
+                    table.toggle_sort(column);
+                }
+                ("data-page", Ok(page)) => {
+                    table.page(page);
+                }
+                ("data-select", Ok(row)) => {
+                    let selected = !table.is_selected(row);
+                    table.select(row, selected);
+                }
+                ("data-select", Err(_)) => {
+                    table.toggle_page_selection();
+                }
+                _ => continue,
+            }
+
+            control = Some(format!(r#"[{}="{}"]"#, attribute, value));
+            break;
+        }
+
+        let Some(control) = control else {
+            return Ok(());
+        };
+
+        self.render()?;
+
+        if let Some(element) = self
+            .container
+            .query_selector(&control)?
+            .and_then(|element| element.dyn_into::<HtmlElement>().ok())
+        {
+            element.focus()?;
+        }
+
+        let on_change = self.on_change.borrow().clone();
+        if let Some(on_change) = on_change {
+            on_change(&self.table.borrow());
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Debug for PuppetTableHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetTableHandle").finish_non_exhaustive()
+    }
+}
+
+fn checkbox(label: &'static str, select: &str, checked: bool) -> PuppetElement {
+    let mut checkbox = PuppetElement::new("input");
+    checkbox
+        .attribute("type", "checkbox")
+        .attribute("aria-label", label)
+        .attribute("data-select", select.to_string());
+
+    if checked {
+        checkbox.attribute("checked", "");
+    }
+
+    checkbox
+}
@@ -0,0 +1,333 @@
+use crate::{PuppetBox, PuppetColor, PuppetDirection, PuppetElement, PuppetViewport};
+use core::fmt::{self, Debug, Display, Write};
+
+/// Pixels covered by one terminal character, matching the default text metrics of
+/// [PuppetViewport] so that pixel units keep their proportions
+const CELL_WIDTH: f32 = 8.0;
+const CELL_HEIGHT: f32 = 16.0;
+
+const RESET: &str = "\x1b[0m";
+
+/// Drawn in place of control characters of the text, which would otherwise reach the
+/// terminal as escape sequences moving the cursor, clearing the screen and the like
+const CONTROL_REPLACEMENT: char = '\u{FFFD}';
+
+type Rgb = (u8, u8, u8);
+
+/// The colors a terminal understands
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+pub enum PuppetColorDepth {
+    /// 24 bit colors
+    #[default]
+    TrueColor,
+    /// The 6x6x6 color cube of 256 color terminals
+    Palette256,
+    /// No colors, only bold and underlined text
+    Monochrome,
+}
+
+impl Debug for PuppetColorDepth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}",
+            match self {
+                Self::TrueColor => "PuppetColorDepth::TrueColor",
+                Self::Palette256 => "PuppetColorDepth::Palette256",
+                Self::Monochrome => "PuppetColorDepth::Monochrome",
+            }
+        )
+    }
+}
+
+impl Display for PuppetColorDepth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?}",
+            match self {
+                Self::TrueColor => "TrueColor",
+                Self::Palette256 => "Palette256",
+                Self::Monochrome => "Monochrome",
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct TerminalCell {
+    character: char,
+    foreground: Option<Rgb>,
+    background: Option<Rgb>,
+    bold: bool,
+    underline: bool,
+}
+
+impl Default for TerminalCell {
+    fn default() -> Self {
+        Self {
+            character: ' ',
+            foreground: None,
+            background: None,
+            bold: false,
+            underline: false,
+        }
+    }
+}
+
+/// A character grid backend, drawing the same trees as the DOM backend for text
+/// user interfaces.
+///
+/// The tree is laid out with [PuppetElement::compute_layout] on a viewport of 8x16
+/// pixel characters, then every box is rounded to whole characters. Backgrounds come
+/// from [crate::PuppetStyle::background_color] and text is drawn in black or white,
+/// whichever reads better on it. Every character is assumed to be one column wide.
+///
+/// ```
+/// use puppeteer_builders::{puppet, PuppetTerminal};
+///
+/// let column = puppet! { column { row { heading("Users") } } };
+///
+/// let mut terminal = PuppetTerminal::new(20, 3);
+/// terminal.draw(&column.to_element());
+///
+/// // Without an alignment, rows stack from the top left corner like blocks
+/// assert_eq!(terminal.to_text().lines().next(), Some("Users"));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PuppetTerminal {
+    columns: usize,
+    rows: usize,
+    direction: PuppetDirection,
+    color_depth: PuppetColorDepth,
+    cells: Vec<TerminalCell>,
+}
+
+impl PuppetTerminal {
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            direction: PuppetDirection::default(),
+            color_depth: PuppetColorDepth::default(),
+            cells: vec![TerminalCell::default(); columns * rows],
+        }
+    }
+
+    pub fn direction(&mut self, direction: PuppetDirection) -> &mut Self {
+        self.direction = direction;
+
+        self
+    }
+
+    pub fn color_depth(&mut self, color_depth: PuppetColorDepth) -> &mut Self {
+        self.color_depth = color_depth;
+
+        self
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Clear the grid and draw `element` on it, anything outside of the grid being
+    /// cut off
+    pub fn draw(&mut self, element: &PuppetElement) -> &mut Self {
+        self.cells.fill(TerminalCell::default());
+
+        let mut viewport = PuppetViewport::new(
+            self.columns as f32 * CELL_WIDTH,
+            self.rows as f32 * CELL_HEIGHT,
+        );
+        viewport
+            .direction(self.direction)
+            .text_metrics(CELL_WIDTH, CELL_HEIGHT);
+
+        let root = element.compute_layout(&viewport);
+        self.draw_box(&root, false, false);
+
+        self
+    }
+
+    fn draw_box(&mut self, puppet_box: &PuppetBox, bold: bool, underline: bool) {
+        let rect = puppet_box.rect();
+        let left = to_column(rect.x);
+        let top = to_row(rect.y);
+
+        if puppet_box.is_text() {
+            for (line_index, line) in puppet_box.lines().iter().enumerate() {
+                for (character_index, character) in line.chars().enumerate() {
+                    if let Some(cell) = self.cell_mut(left + character_index, top + line_index) {
+                        cell.character = match character.is_control() {
+                            true => CONTROL_REPLACEMENT,
+                            false => character,
+                        };
+                        cell.foreground = cell.background.map(readable_on);
+                        cell.bold = bold;
+                        cell.underline = underline;
+                    }
+                }
+            }
+
+            return;
+        }
+
+        if let Some(color) = puppet_box.style().and_then(|style| style.background_color) {
+            self.fill(
+                left..to_column(rect.right()),
+                top..to_row(rect.bottom()),
+                &color,
+            );
+        }
+
+        let bold = bold || matches!(puppet_box.tag(), "h1" | "button");
+        let underline = underline || puppet_box.tag() == "a";
+
+        for child in puppet_box.children() {
+            self.draw_box(child, bold, underline);
+        }
+    }
+
+    /// Paint a background, blending translucent colors with what is already drawn
+    fn fill(
+        &mut self,
+        columns: core::ops::Range<usize>,
+        rows: core::ops::Range<usize>,
+        color: &PuppetColor,
+    ) {
+        let Some(rgb) = color.to_rgb() else {
+            return;
+        };
+        let opacity = color.opacity();
+
+        for row in rows {
+            for column in columns.clone() {
+                if let Some(cell) = self.cell_mut(column, row) {
+                    cell.background = match cell.background {
+                        Some(below) => Some(blend(rgb, below, opacity)),
+                        None if opacity >= 0.5 => Some(rgb),
+                        None => None,
+                    };
+                }
+            }
+        }
+    }
+
+    fn cell_mut(&mut self, column: usize, row: usize) -> Option<&mut TerminalCell> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        self.cells.get_mut(row * self.columns + column)
+    }
+
+    fn grid_rows(&self) -> impl Iterator<Item = &[TerminalCell]> {
+        self.cells.chunks(self.columns.max(1)).take(self.rows)
+    }
+
+    /// The characters of the grid without any escape sequence, one line per row with
+    /// trailing spaces removed, for snapshot tests
+    pub fn to_text(&self) -> String {
+        self.grid_rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.character)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// The grid with ANSI escape sequences for colors, bold and underlined text,
+    /// every row ending with a reset
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::default();
+
+        for row in self.grid_rows() {
+            let mut previous = None;
+
+            for cell in row {
+                let attributes = (cell.foreground, cell.background, cell.bold, cell.underline);
+
+                if previous != Some(attributes) {
+                    ansi.push_str(RESET);
+                    self.write_attributes(cell, &mut ansi);
+                    previous = Some(attributes);
+                }
+
+                ansi.push(cell.character);
+            }
+
+            ansi.push_str(RESET);
+            ansi.push('\n');
+        }
+
+        ansi
+    }
+
+    fn write_attributes(&self, cell: &TerminalCell, ansi: &mut String) {
+        if cell.bold {
+            ansi.push_str("\x1b[1m");
+        }
+
+        if cell.underline {
+            ansi.push_str("\x1b[4m");
+        }
+
+        // Writing to a String never fails
+        for (layer, color) in [(38, cell.foreground), (48, cell.background)] {
+            match (self.color_depth, color) {
+                (PuppetColorDepth::TrueColor, Some((r, g, b))) => {
+                    let _ = write!(ansi, "\x1b[{};2;{};{};{}m", layer, r, g, b);
+                }
+                (PuppetColorDepth::Palette256, Some(rgb)) => {
+                    let _ = write!(ansi, "\x1b[{};5;{}m", layer, to_palette_256(rgb));
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+fn to_column(x: f32) -> usize {
+    (x / CELL_WIDTH).round().max(0.0) as usize
+}
+
+fn to_row(y: f32) -> usize {
+    (y / CELL_HEIGHT).round().max(0.0) as usize
+}
+
+fn blend(above: Rgb, below: Rgb, opacity: f32) -> Rgb {
+    let channel = |above: u8, below: u8| {
+        (f32::from(above) * opacity + f32::from(below) * (1.0 - opacity)).round() as u8
+    };
+
+    (
+        channel(above.0, below.0),
+        channel(above.1, below.1),
+        channel(above.2, below.2),
+    )
+}
+
+/// Black text on light backgrounds and white text on dark ones
+fn readable_on((r, g, b): Rgb) -> Rgb {
+    let luminance = 0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b);
+
+    if luminance > 127.5 {
+        (0, 0, 0)
+    } else {
+        (255, 255, 255)
+    }
+}
+
+fn to_palette_256((r, g, b): Rgb) -> u8 {
+    let level = |channel: u8| ((u16::from(channel) * 5 + 127) / 255) as u8;
+
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
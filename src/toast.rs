@@ -0,0 +1,628 @@
+use crate::{
+    get_document, get_window, JsValueResult, PuppetColor, PuppetElement, PuppetStyle, PuppetText,
+};
+use core::{
+    cell::RefCell,
+    fmt,
+    fmt::{Debug, Display},
+};
+use gloo_timers::callback::Timeout;
+use log::trace;
+use std::collections::{HashMap, VecDeque};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Element, Event, HtmlElement};
+
+thread_local! {
+    static TOASTER: RefCell<Toaster> = RefCell::default();
+}
+
+/// How long a toast stays on screen by default, in milliseconds
+pub const TOAST_TIMEOUT: u32 = 5000;
+
+/// How many toasts are shown at once by default, the others waiting their turn
+pub const TOAST_LIMIT: usize = 3;
+
+const TOAST_REGION_LABEL: &str = "Notifications";
+const TOAST_DISMISS_LABEL: &str = "Dismiss";
+
+/// How important a toast is, which decides its color and how it is announced
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub enum ToastSeverity {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastSeverity {
+    pub fn to_html_severity(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+
+    /// Warnings and errors interrupt screen readers, other toasts wait for a pause
+    pub fn to_html_role(&self) -> &'static str {
+        match self {
+            Self::Info | Self::Success => "status",
+            Self::Warning | Self::Error => "alert",
+        }
+    }
+
+    pub fn style(&self) -> PuppetStyle {
+        let mut style = PuppetStyle::default();
+        style.background_color(match self {
+            Self::Info => PuppetColor::Hex("E8F0FE"),
+            Self::Success => PuppetColor::Hex("E6F4EA"),
+            Self::Warning => PuppetColor::Hex("FEF7E0"),
+            Self::Error => PuppetColor::Hex("FCE8E6"),
+        });
+
+        style
+    }
+}
+
+impl Debug for ToastSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Info => "Info",
+                Self::Success => "Success",
+                Self::Warning => "Warning",
+                Self::Error => "Error",
+            }
+        )
+    }
+}
+
+impl Display for ToastSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Identifies a toast handed to [notify] so that it can be dismissed early
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct ToastId(u32);
+
+impl ToastId {
+    pub fn to_html_id(&self) -> String {
+        format!("toast-{}", self.0)
+    }
+}
+
+impl Display for ToastId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A short message shown on top of the app, see [notify]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetToast {
+    message: PuppetText,
+    severity: ToastSeverity,
+    timeout: Option<u32>,
+}
+
+impl PuppetToast {
+    pub fn new<T>(message: T) -> Self
+    where
+        T: Into<PuppetText>,
+    {
+        Self {
+            message: message.into(),
+            severity: ToastSeverity::default(),
+            timeout: Some(TOAST_TIMEOUT),
+        }
+    }
+
+    pub fn info<T>(message: T) -> Self
+    where
+        T: Into<PuppetText>,
+    {
+        Self::new(message)
+    }
+
+    pub fn success<T>(message: T) -> Self
+    where
+        T: Into<PuppetText>,
+    {
+        let mut toast = Self::new(message);
+        toast.severity(ToastSeverity::Success);
+
+        toast
+    }
+
+    pub fn warning<T>(message: T) -> Self
+    where
+        T: Into<PuppetText>,
+    {
+        let mut toast = Self::new(message);
+        toast.severity(ToastSeverity::Warning);
+
+        toast
+    }
+
+    pub fn error<T>(message: T) -> Self
+    where
+        T: Into<PuppetText>,
+    {
+        let mut toast = Self::new(message);
+        toast.severity(ToastSeverity::Error);
+
+        toast
+    }
+
+    pub fn severity(&mut self, severity: ToastSeverity) -> &mut Self {
+        self.severity = severity;
+
+        self
+    }
+
+    /// Milliseconds before the toast dismisses itself, `None` keeping it until the
+    /// user dismisses it
+    pub fn timeout(&mut self, timeout: Option<u32>) -> &mut Self {
+        self.timeout = timeout;
+
+        self
+    }
+
+    pub fn get_severity(&self) -> ToastSeverity {
+        self.severity
+    }
+
+    pub fn get_timeout(&self) -> Option<u32> {
+        self.timeout
+    }
+
+    pub fn to_element(&self, id: ToastId) -> PuppetElement {
+        let mut message = PuppetElement::new("p");
+        message.add_child(self.message.clone());
+
+        let mut dismiss = PuppetElement::new("button");
+        dismiss
+            .attribute("type", "button")
+            .attribute("aria-label", TOAST_DISMISS_LABEL)
+            .attribute("data-toast-dismiss", "")
+            .add_child("×");
+
+        let mut toast = PuppetElement::new("div");
+        toast
+            .attribute("id", id.to_html_id())
+            .attribute("data-toast", id.to_string())
+            .attribute("data-severity", self.severity.to_html_severity())
+            .attribute("role", self.severity.to_html_role())
+            .attribute("aria-atomic", "true")
+            .set_style(&self.severity.style())
+            .add_child(message)
+            .add_child(dismiss);
+
+        toast
+    }
+}
+
+/// The toasts waiting to be dismissed, oldest first, of which only the first
+/// [PuppetToastQueue::limit] are shown
+///
+/// ```
+/// use puppeteer_builders::{PuppetToast, PuppetToastQueue};
+///
+/// let mut queue = PuppetToastQueue::new();
+/// queue.limit(1);
+///
+/// let saved = queue.push(PuppetToast::success("Saved"));
+/// let synced = queue.push(PuppetToast::info("Synced"));
+/// assert_eq!(queue.visible().map(|(id, _)| id).collect::<Vec<_>>(), [saved]);
+///
+/// queue.dismiss(saved);
+/// assert_eq!(queue.visible().map(|(id, _)| id).collect::<Vec<_>>(), [synced]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PuppetToastQueue {
+    toasts: VecDeque<(ToastId, PuppetToast)>,
+    next_id: u32,
+    limit: usize,
+}
+
+impl Default for PuppetToastQueue {
+    fn default() -> Self {
+        Self {
+            toasts: VecDeque::default(),
+            next_id: 0,
+            limit: TOAST_LIMIT,
+        }
+    }
+}
+
+impl PuppetToastQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many toasts are stacked on screen at once, at least one
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit.max(1);
+
+        self
+    }
+
+    pub fn push(&mut self, toast: PuppetToast) -> ToastId {
+        let id = ToastId(self.next_id);
+        self.next_id = self.next_id.wrapping_add(1);
+        self.toasts.push_back((id, toast));
+
+        id
+    }
+
+    pub fn dismiss(&mut self, id: ToastId) -> Option<PuppetToast> {
+        let index = self
+            .toasts
+            .iter()
+            .position(|(toast_id, _)| *toast_id == id)?;
+
+        self.toasts.remove(index).map(|(_, toast)| toast)
+    }
+
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+    }
+
+    pub fn contains(&self, id: ToastId) -> bool {
+        self.toasts.iter().any(|(toast_id, _)| *toast_id == id)
+    }
+
+    /// The toasts on screen, oldest first
+    pub fn visible(&self) -> impl Iterator<Item = (ToastId, &PuppetToast)> {
+        self.toasts
+            .iter()
+            .take(self.limit)
+            .map(|(id, toast)| (*id, toast))
+    }
+
+    /// How many toasts wait for a visible one to be dismissed
+    pub fn waiting(&self) -> usize {
+        self.toasts.len().saturating_sub(self.limit)
+    }
+
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+/// Show `toast` on top of the app, from anywhere in it.
+///
+/// Toasts are queued until [mount_toasts] gives them a place in the page, which the
+/// router does on its mount node.
+pub fn notify(toast: PuppetToast) -> ToastId {
+    TOASTER.with(|toaster| {
+        let mut toaster = toaster.borrow_mut();
+        let id = toaster.queue.push(toast);
+
+        if let Err(error) = toaster.sync() {
+            trace!("{:?}", error);
+        }
+
+        id
+    })
+}
+
+/// Remove a toast before its timeout, showing the next waiting one
+pub fn dismiss_toast(id: ToastId) {
+    TOASTER.with(|toaster| {
+        if let Err(error) = toaster.borrow_mut().dismiss(id) {
+            trace!("{:?}", error);
+        }
+    })
+}
+
+/// Remove every toast, shown or waiting
+pub fn clear_toasts() {
+    TOASTER.with(|toaster| {
+        let mut toaster = toaster.borrow_mut();
+        let ids = toaster
+            .queue
+            .toasts
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        for id in ids {
+            if let Err(error) = toaster.dismiss(id) {
+                trace!("{:?}", error);
+            }
+        }
+    })
+}
+
+/// How many toasts are stacked on screen at once, [TOAST_LIMIT] by default
+pub fn set_toast_limit(limit: usize) {
+    TOASTER.with(|toaster| {
+        let mut toaster = toaster.borrow_mut();
+        toaster.queue.limit(limit);
+
+        if let Err(error) = toaster.sync() {
+            trace!("{:?}", error);
+        }
+    })
+}
+
+/// The toasts shown or waiting, oldest first
+pub fn pending_toasts() -> Vec<ToastId> {
+    TOASTER.with(|toaster| {
+        toaster
+            .borrow()
+            .queue
+            .toasts
+            .iter()
+            .map(|(id, _)| *id)
+            .collect()
+    })
+}
+
+/// Stack toasts in a live region at the end of `root`, moving it there if it was
+/// mounted elsewhere
+pub fn mount_toasts(root: &Element) -> JsValueResult<()> {
+    let region = TOASTER.with(|toaster| toaster.borrow().region.clone());
+
+    let region = match region {
+        Some(region) => region,
+        None => {
+            let region = create_region()?;
+            TOASTER.with(|toaster| toaster.borrow_mut().region = Some(region.clone()));
+
+            region
+        }
+    };
+    root.append_child(&region)?;
+
+    TOASTER.with(|toaster| toaster.borrow_mut().sync())
+}
+
+/// Put the toast region back into `root` after its content was replaced
+pub(crate) fn restore_toasts(root: &Element) -> JsValueResult<()> {
+    let region = TOASTER.with(|toaster| toaster.borrow().region.clone());
+
+    match region {
+        Some(region) if !region.is_connected() => {
+            root.append_child(&region)?;
+
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn create_region() -> JsValueResult<Element> {
+    let region = get_document().create_element("section")?;
+    region.set_attribute("data-toasts", "")?;
+    region.set_attribute("aria-label", TOAST_REGION_LABEL)?;
+    region.set_attribute(
+        "style",
+        "position: fixed; inset-inline-end: 1rem; bottom: 1rem; z-index: 1100; \
+        display: flex; flex-direction: column; gap: 0.5rem;",
+    )?;
+
+    listen(&region, "click", false, |event| {
+        let target = event_target(&event)?;
+
+        if target.closest("[data-toast-dismiss]")?.is_some() {
+            if let Some(id) = toast_id(&target)? {
+                dismiss_toast(id);
+            }
+        }
+
+        Ok(())
+    })?;
+
+    // Hovering or focusing a toast keeps it on screen, `mouseenter` and `mouseleave`
+    // only reach the region while capturing
+    for (event, capture, paused) in [
+        ("mouseenter", true, true),
+        ("mouseleave", true, false),
+        ("focusin", false, true),
+        ("focusout", false, false),
+    ] {
+        listen(&region, event, capture, move |event| {
+            let target = event_target(&event)?;
+            if capture && !target.has_attribute("data-toast") {
+                return Ok(());
+            }
+
+            if let Some(id) = toast_id(&target)? {
+                TOASTER.with(|toaster| {
+                    // Removing a focused toast fires `focusout` while it is dismissed
+                    if let Ok(mut toaster) = toaster.try_borrow_mut() {
+                        match paused {
+                            true => toaster.pause(id),
+                            false => toaster.resume(id),
+                        }
+                    }
+                });
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(region)
+}
+
+#[derive(Default)]
+struct Toaster {
+    queue: PuppetToastQueue,
+    region: Option<Element>,
+    /// The toasts rendered into the region
+    shown: HashMap<ToastId, ToastTimer>,
+}
+
+impl Toaster {
+    /// Render the visible toasts which are not on screen yet and start their timers
+    fn sync(&mut self) -> JsValueResult<()> {
+        let Some(region) = self.region.clone() else {
+            return Ok(());
+        };
+
+        let visible = self
+            .queue
+            .visible()
+            .filter(|(id, _)| !self.shown.contains_key(id))
+            .map(|(id, toast)| (id, toast.to_element(id), toast.timeout))
+            .collect::<Vec<_>>();
+
+        for (id, element, timeout) in visible {
+            element.to_html(&get_document(), &region)?;
+
+            let mut timer = ToastTimer {
+                timeout: None,
+                remaining: timeout.map(f64::from),
+                started: now(),
+                hovered: 0,
+            };
+            timer.start(id);
+            self.shown.insert(id, timer);
+        }
+
+        Ok(())
+    }
+
+    fn dismiss(&mut self, id: ToastId) -> JsValueResult<()> {
+        self.queue.dismiss(id);
+
+        if self.shown.remove(&id).is_some() {
+            // Dropping the timer cancels its timeout
+            if let Some(region) = &self.region {
+                if let Some(element) = region.query_selector(&format!("#{}", id.to_html_id()))? {
+                    element.remove();
+                }
+            }
+        }
+
+        self.sync()
+    }
+
+    fn pause(&mut self, id: ToastId) {
+        if let Some(timer) = self.shown.get_mut(&id) {
+            timer.hovered += 1;
+            timer.pause();
+        }
+    }
+
+    fn resume(&mut self, id: ToastId) {
+        if let Some(timer) = self.shown.get_mut(&id) {
+            timer.hovered = timer.hovered.saturating_sub(1);
+            if timer.hovered == 0 {
+                timer.start(id);
+            }
+        }
+    }
+}
+
+struct ToastTimer {
+    timeout: Option<Timeout>,
+    /// Milliseconds left when the timer was last started, `None` for toasts that
+    /// never dismiss themselves
+    remaining: Option<f64>,
+    started: f64,
+    /// The pointer and the focus both pause the timer until they have left
+    hovered: u8,
+}
+
+impl ToastTimer {
+    fn start(&mut self, id: ToastId) {
+        if self.timeout.is_some() {
+            return;
+        }
+
+        if let Some(remaining) = self.remaining {
+            self.started = now();
+            self.timeout = Some(Timeout::new(remaining.max(0.0) as u32, move || {
+                TOASTER.with(|toaster| {
+                    let mut toaster = toaster.borrow_mut();
+
+                    // The timeout is running, forgetting it keeps it from being
+                    // cancelled while it is being dismissed
+                    if let Some(timeout) = toaster
+                        .shown
+                        .get_mut(&id)
+                        .and_then(|timer| timer.timeout.take())
+                    {
+                        timeout.forget();
+                    }
+
+                    if let Err(error) = toaster.dismiss(id) {
+                        trace!("{:?}", error);
+                    }
+                })
+            }));
+        }
+    }
+
+    fn pause(&mut self) {
+        if let Some(timeout) = self.timeout.take() {
+            drop(timeout);
+
+            if let Some(remaining) = self.remaining.as_mut() {
+                *remaining -= now() - self.started;
+            }
+        }
+    }
+}
+
+fn now() -> f64 {
+    get_window()
+        .performance()
+        .map(|performance| performance.now())
+        .unwrap_or_default()
+}
+
+fn toast_id(target: &Element) -> JsValueResult<Option<ToastId>> {
+    Ok(target
+        .closest("[data-toast]")?
+        .and_then(|toast| toast.get_attribute("data-toast"))
+        .and_then(|id| id.parse().ok())
+        .map(ToastId))
+}
+
+fn event_target(event: &Event) -> JsValueResult<Element> {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .ok_or(JsValue::from_str("The event has no target element"))
+}
+
+fn listen<F>(region: &Element, event: &str, capture: bool, listener: F) -> JsValueResult<()>
+where
+    F: Fn(Event) -> JsValueResult<()> + 'static,
+{
+    let closure = Closure::wrap(Box::new(move |event: Event| {
+        if let Err(error) = listener(event) {
+            trace!("{:?}", error);
+        }
+    }) as Box<dyn FnMut(Event)>);
+
+    region
+        .dyn_ref::<HtmlElement>()
+        .ok_or(JsValue::from_str(
+            "Could not convert `Element` to `HtmlElement`",
+        ))?
+        .add_event_listener_with_callback_and_bool(
+            event,
+            closure.as_ref().unchecked_ref(),
+            capture,
+        )?;
+    // The region is kept for the lifetime of the page
+    closure.forget();
+
+    Ok(())
+}
@@ -0,0 +1,436 @@
+use crate::{
+    get_document, JsValueResult, ObjectFit, PuppetAlignment, PuppetColor, PuppetLength,
+    PuppetStyle, PuppetStylesheet, PuppetUnit,
+};
+use core::{
+    cell::RefCell,
+    fmt,
+    fmt::{Debug, Display},
+};
+use std::collections::HashSet;
+use wasm_bindgen::JsValue;
+use web_sys::Element;
+
+thread_local! {
+    /// Classes whose state rules were added to the document
+    static INSTALLED_STATES: RefCell<HashSet<String>> = RefCell::default();
+}
+
+/// A partial [PuppetStyle]: only the properties it sets override the style it
+/// extends, see [PuppetStyle::extend]
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct PuppetStylePatch {
+    padding: Option<PuppetUnit>,
+    margin: Option<PuppetUnit>,
+    width: Option<PuppetLength>,
+    height: Option<PuppetLength>,
+    min_width: Option<PuppetUnit>,
+    max_width: Option<PuppetUnit>,
+    min_height: Option<PuppetUnit>,
+    max_height: Option<PuppetUnit>,
+    alignment: Option<PuppetAlignment>,
+    background_color: Option<PuppetColor>,
+    object_fit: Option<ObjectFit>,
+}
+
+impl PuppetStylePatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn padding(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
+        self.padding = Some(puppet_unit);
+
+        self
+    }
+
+    pub fn margin(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
+        self.margin = Some(puppet_unit);
+
+        self
+    }
+
+    pub fn width(&mut self, puppet_length: PuppetLength) -> &mut Self {
+        self.width = Some(puppet_length);
+
+        self
+    }
+
+    pub fn height(&mut self, puppet_length: PuppetLength) -> &mut Self {
+        self.height = Some(puppet_length);
+
+        self
+    }
+
+    pub fn min_width(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
+        self.min_width = Some(puppet_unit);
+
+        self
+    }
+
+    pub fn min_height(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
+        self.min_height = Some(puppet_unit);
+
+        self
+    }
+
+    pub fn max_width(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
+        self.max_width = Some(puppet_unit);
+
+        self
+    }
+
+    pub fn max_height(&mut self, puppet_unit: PuppetUnit) -> &mut Self {
+        self.max_height = Some(puppet_unit);
+
+        self
+    }
+
+    pub fn align_items(&mut self, alignment: PuppetAlignment) -> &mut Self {
+        self.alignment = Some(alignment);
+
+        self
+    }
+
+    pub fn background_color(&mut self, background_color: PuppetColor) -> &mut Self {
+        self.background_color = Some(background_color);
+
+        self
+    }
+
+    pub fn object_fit(&mut self, object_fit: ObjectFit) -> &mut Self {
+        self.object_fit = Some(object_fit);
+
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// This patch followed by `other`, whose properties win where both set one
+    pub fn merge(&self, other: &PuppetStylePatch) -> Self {
+        Self {
+            padding: other.padding.or(self.padding),
+            margin: other.margin.or(self.margin),
+            width: other.width.or(self.width),
+            height: other.height.or(self.height),
+            min_width: other.min_width.or(self.min_width),
+            max_width: other.max_width.or(self.max_width),
+            min_height: other.min_height.or(self.min_height),
+            max_height: other.max_height.or(self.max_height),
+            alignment: other.alignment.or(self.alignment),
+            background_color: other.background_color.or(self.background_color),
+            object_fit: other.object_fit.or(self.object_fit),
+        }
+    }
+
+    /// The CSS properties this patch sets, in the order of
+    /// [PuppetStyle::css_properties]
+    pub fn css_properties(&self) -> Vec<(&'static str, String)> {
+        PuppetStyle::default().extend(self).css_properties()
+    }
+}
+
+impl PuppetStyle {
+    /// This style with the properties set by `patch` replaced
+    pub fn extend(&self, patch: &PuppetStylePatch) -> Self {
+        Self {
+            padding: patch.padding.or(self.padding),
+            margin: patch.margin.or(self.margin),
+            width: patch.width.or(self.width),
+            height: patch.height.or(self.height),
+            min_width: patch.min_width.or(self.min_width),
+            max_width: patch.max_width.or(self.max_width),
+            min_height: patch.min_height.or(self.min_height),
+            max_height: patch.max_height.or(self.max_height),
+            alignment: patch.alignment.or(self.alignment),
+            background_color: patch.background_color.or(self.background_color),
+            object_fit: patch.object_fit.or(self.object_fit),
+        }
+    }
+
+    /// The patch turning this style into `other`, setting only what `other` sets
+    /// differently. Properties `other` leaves unset cannot be patched away.
+    pub fn diff(&self, other: &PuppetStyle) -> PuppetStylePatch {
+        fn changed<T: PartialEq + Copy>(from: Option<T>, to: Option<T>) -> Option<T> {
+            to.filter(|to| from != Some(*to))
+        }
+
+        PuppetStylePatch {
+            padding: changed(self.padding, other.padding),
+            margin: changed(self.margin, other.margin),
+            width: changed(self.width, other.width),
+            height: changed(self.height, other.height),
+            min_width: changed(self.min_width, other.min_width),
+            max_width: changed(self.max_width, other.max_width),
+            min_height: changed(self.min_height, other.min_height),
+            max_height: changed(self.max_height, other.max_height),
+            alignment: changed(self.alignment, other.alignment),
+            background_color: changed(self.background_color, other.background_color),
+            object_fit: changed(self.object_fit, other.object_fit),
+        }
+    }
+}
+
+/// An interaction state styled by a pseudo-class rule. States are listed by
+/// increasing precedence: a disabled element that is hovered is styled as disabled.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum StyleState {
+    Hover,
+    /// Focused from the keyboard, see [crate::PuppetFocusRing]
+    Focus,
+    /// Pressed
+    Active,
+    /// Either `disabled` or `aria-disabled="true"`
+    Disabled,
+}
+
+impl StyleState {
+    /// The selector of the state on elements of the class `class_name`
+    pub fn to_html_selector(&self, class_name: &str) -> String {
+        match self {
+            Self::Hover => format!(".{}:hover", class_name),
+            Self::Focus => format!(".{}:focus-visible", class_name),
+            Self::Active => format!(".{}:active", class_name),
+            Self::Disabled => format!(r#".{0}:disabled, .{0}[aria-disabled="true"]"#, class_name),
+        }
+    }
+}
+
+impl Debug for StyleState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StyleState::{}", self)
+    }
+}
+
+impl Display for StyleState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Hover => "Hover",
+                Self::Focus => "Focus",
+                Self::Active => "Active",
+                Self::Disabled => "Disabled",
+            }
+        )
+    }
+}
+
+/// The name of a variant of a [PuppetStyleVariants]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum PuppetVariant {
+    Primary,
+    Secondary,
+    Danger,
+    Named(&'static str),
+}
+
+/// Named and state variants extending the style of a [crate::Component].
+///
+/// The style of a component is its own, extended by the patch of its variant,
+/// then by the patches of the states it is in from [StyleState::Hover] to
+/// [StyleState::Disabled], patches for every variant before the patch of its own
+/// variant. States are written as pseudo-class rules of a stylesheet, which win
+/// over inline styles.
+///
+/// ```
+/// use puppeteer_builders::{
+///     Component, ComponentType, PuppetColor, PuppetStylePatch, PuppetStyleVariants,
+///     PuppetVariant, StyleState,
+/// };
+///
+/// let mut primary = PuppetStylePatch::new();
+/// primary.background_color(PuppetColor::Hex("D2E3FC"));
+/// let mut hover = PuppetStylePatch::new();
+/// hover.background_color(PuppetColor::Hex("AECBFA"));
+///
+/// let mut variants = PuppetStyleVariants::new();
+/// variants
+///     .variant(PuppetVariant::Primary, primary)
+///     .state(StyleState::Hover, hover);
+///
+/// let mut save = Component::new(ComponentType::Button, "Save");
+/// save.style_variants(&variants).variant(PuppetVariant::Primary);
+///
+/// let html = save.to_html_string();
+/// assert!(html.starts_with(r#"<button class="puppet-s"#));
+/// assert!(html.contains("background-color: #D2E3FC;"));
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct PuppetStyleVariants {
+    variants: Vec<(PuppetVariant, PuppetStylePatch)>,
+    /// By state, for every variant when the variant is `None`
+    states: Vec<(StyleState, Option<PuppetVariant>, PuppetStylePatch)>,
+}
+
+impl PuppetStyleVariants {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Primary, secondary and danger buttons, darker while hovered or pressed and
+    /// greyed out while disabled
+    pub fn buttons() -> Self {
+        let mut variants = Self::new();
+
+        for (variant, background, hover, active) in [
+            (PuppetVariant::Primary, "D2E3FC", "AECBFA", "8AB4F8"),
+            (PuppetVariant::Secondary, "F1F3F4", "E8EAED", "DADCE0"),
+            (PuppetVariant::Danger, "FCE8E6", "FAD2CF", "F6AEA9"),
+        ] {
+            let mut patch = PuppetStylePatch::new();
+            variants.variant(
+                variant,
+                *patch.background_color(PuppetColor::Hex(background)),
+            );
+            variants.variant_state(
+                variant,
+                StyleState::Hover,
+                *patch.background_color(PuppetColor::Hex(hover)),
+            );
+            variants.variant_state(
+                variant,
+                StyleState::Active,
+                *patch.background_color(PuppetColor::Hex(active)),
+            );
+        }
+
+        let mut disabled = PuppetStylePatch::new();
+        disabled.background_color(PuppetColor::Hex("F8F9FA"));
+        variants.state(StyleState::Disabled, disabled);
+
+        variants
+    }
+
+    /// Replaces any previous patch of `variant`
+    pub fn variant(&mut self, variant: PuppetVariant, patch: PuppetStylePatch) -> &mut Self {
+        match self.variants.iter_mut().find(|(name, _)| *name == variant) {
+            Some((_, previous)) => *previous = patch,
+            None => self.variants.push((variant, patch)),
+        }
+
+        self
+    }
+
+    /// Style `state` for every variant, replacing any previous patch of the state
+    pub fn state(&mut self, state: StyleState, patch: PuppetStylePatch) -> &mut Self {
+        self.set_state(state, None, patch)
+    }
+
+    /// Style `state` for `variant` only, over the patch of the state for every variant
+    pub fn variant_state(
+        &mut self,
+        variant: PuppetVariant,
+        state: StyleState,
+        patch: PuppetStylePatch,
+    ) -> &mut Self {
+        self.set_state(state, Some(variant), patch)
+    }
+
+    fn set_state(
+        &mut self,
+        state: StyleState,
+        variant: Option<PuppetVariant>,
+        patch: PuppetStylePatch,
+    ) -> &mut Self {
+        match self
+            .states
+            .iter_mut()
+            .find(|(previous, of, _)| *previous == state && *of == variant)
+        {
+            Some((_, _, previous)) => *previous = patch,
+            None => self.states.push((state, variant, patch)),
+        }
+
+        self
+    }
+
+    pub fn get_variant(&self, variant: PuppetVariant) -> Option<&PuppetStylePatch> {
+        self.variants
+            .iter()
+            .find(|(name, _)| *name == variant)
+            .map(|(_, patch)| patch)
+    }
+
+    /// `base` extended by the patch of `variant`
+    pub fn resolve(&self, base: &PuppetStyle, variant: Option<PuppetVariant>) -> PuppetStyle {
+        match variant.and_then(|variant| self.get_variant(variant)) {
+            Some(patch) => base.extend(patch),
+            None => *base,
+        }
+    }
+
+    /// The patch of every state of `variant`, by increasing precedence
+    pub fn resolve_states(
+        &self,
+        variant: Option<PuppetVariant>,
+    ) -> Vec<(StyleState, PuppetStylePatch)> {
+        let mut states = self
+            .states
+            .iter()
+            .filter(|(_, of, _)| of.is_none() || *of == variant)
+            .collect::<Vec<_>>();
+        // Patches for every variant come first within a state
+        states.sort_by_key(|(state, of, _)| (*state, of.is_some()));
+
+        let mut resolved: Vec<(StyleState, PuppetStylePatch)> = Vec::default();
+        for (state, _, patch) in states {
+            match resolved.last_mut() {
+                Some((previous, merged)) if previous == state => *merged = merged.merge(patch),
+                _ => resolved.push((*state, *patch)),
+            }
+        }
+
+        resolved
+    }
+}
+
+/// Add the rules of `states` to the document once, returning the class they apply to
+pub(crate) fn install_state_rules(
+    states: &[(StyleState, PuppetStylePatch)],
+) -> JsValueResult<String> {
+    let mut stylesheet = PuppetStylesheet::new();
+    let class_name = stylesheet.state_class_name(states);
+
+    if INSTALLED_STATES.with(|installed| installed.borrow().contains(&class_name)) {
+        return Ok(class_name);
+    }
+
+    let document = get_document();
+    let style = match document.query_selector("style[data-puppet-states]")? {
+        Some(style) => style,
+        None => {
+            let style = document.create_element("style")?;
+            style.set_attribute("data-puppet-states", "")?;
+
+            document
+                .query_selector("head")?
+                .or(document.document_element())
+                .ok_or(JsValue::from_str("The document has no root element"))?
+                .append_child(&style)?;
+
+            style
+        }
+    };
+
+    let mut css = style.text_content().unwrap_or_default();
+    css.push_str(&stylesheet.to_css());
+    style.set_text_content(Some(&css));
+
+    INSTALLED_STATES.with(|installed| installed.borrow_mut().insert(class_name.clone()));
+
+    Ok(class_name)
+}
+
+/// Add `class_name` to the classes of `element`
+pub(crate) fn add_class(element: &Element, class_name: &str) -> JsValueResult<()> {
+    match element.get_attribute("class") {
+        Some(classes) if !classes.is_empty() => {
+            element.set_attribute("class", &format!("{} {}", classes, class_name))
+        }
+        _ => element.set_attribute("class", class_name),
+    }
+}
@@ -0,0 +1,547 @@
+use crate::{JsValueResult, PuppetChild, PuppetElement, PuppetRow};
+use core::{
+    cell::RefCell,
+    fmt,
+    fmt::{Debug, Display},
+    ops::Range,
+};
+use log::trace;
+use std::rc::Rc;
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{Document, Element, Event, HtmlElement};
+
+/// Builds the row at an index of a [PuppetVirtualList]
+pub type PuppetRowRenderer = Rc<dyn Fn(usize) -> PuppetRow>;
+
+/// Where a [PuppetVirtualList] takes its rows from
+#[derive(Clone)]
+enum PuppetRows {
+    Rendered(PuppetRowRenderer),
+    /// Shared so that cloning a list does not clone every row
+    Existing(Rc<Vec<PuppetRow>>),
+}
+
+/// How tall the rows of a [PuppetVirtualList] are, in pixels
+#[derive(PartialEq, PartialOrd, Clone, Copy)]
+pub enum RowHeight {
+    /// Every row has this height
+    Fixed(f32),
+    /// Rows are assumed to have this height until they are rendered and measured
+    Estimated(f32),
+}
+
+impl RowHeight {
+    pub fn height(&self) -> f32 {
+        match self {
+            Self::Fixed(height) | Self::Estimated(height) => *height,
+        }
+    }
+}
+
+impl Default for RowHeight {
+    fn default() -> Self {
+        Self::Fixed(32.0)
+    }
+}
+
+impl Debug for RowHeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed(height) => write!(f, "Fixed({})", height),
+            Self::Estimated(height) => write!(f, "Estimated({})", height),
+        }
+    }
+}
+
+impl Display for RowHeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A scrolling list that only renders the rows inside its viewport, plus
+/// `overscan` rows on each side, so that it can hold thousands of rows.
+///
+/// ```
+/// use puppeteer_builders::{puppet, PuppetVirtualList, RowHeight};
+///
+/// let mut list = PuppetVirtualList::new(10_000, |index| puppet! { row { paragraph(index.to_string()) } });
+/// list.row_height(RowHeight::Fixed(20.0)).viewport_height(100.0).overscan(2);
+///
+/// list.scroll_to(1_000.0);
+/// // Rows 50 to 54 are visible
+/// assert_eq!(list.visible_range(), 48..57);
+/// ```
+#[derive(Clone)]
+pub struct PuppetVirtualList {
+    list_id: Option<&'static str>,
+    row_count: usize,
+    rows: PuppetRows,
+    row_height: RowHeight,
+    /// Heights of the rendered rows, for [RowHeight::Estimated]
+    measured: Vec<Option<f32>>,
+    viewport_height: f32,
+    overscan: usize,
+    scroll_top: f32,
+}
+
+impl PuppetVirtualList {
+    pub fn new<F>(row_count: usize, render_row: F) -> Self
+    where
+        F: Fn(usize) -> PuppetRow + 'static,
+    {
+        Self {
+            list_id: Option::default(),
+            row_count,
+            rows: PuppetRows::Rendered(Rc::new(render_row)),
+            row_height: RowHeight::default(),
+            measured: vec![None; row_count],
+            viewport_height: 480.0,
+            overscan: 4,
+            scroll_top: 0.0,
+        }
+    }
+
+    /// A list over rows that already exist, only rendering the visible ones. Rows
+    /// are added with [Self::insert_existing_rows].
+    pub fn from_rows(rows: Vec<PuppetRow>) -> Self {
+        let mut list = Self::new(rows.len(), |_| PuppetRow::new());
+        list.rows = PuppetRows::Existing(Rc::new(rows));
+
+        list
+    }
+
+    /// Build rows with `render_row` from now on, such as after the data behind them
+    /// changed
+    pub fn set_render_row<F>(&mut self, render_row: F) -> &mut Self
+    where
+        F: Fn(usize) -> PuppetRow + 'static,
+    {
+        self.rows = PuppetRows::Rendered(Rc::new(render_row));
+
+        self
+    }
+
+    pub fn list_id(&mut self, list_id: &'static str) -> &mut Self {
+        self.list_id = Some(list_id);
+
+        self
+    }
+
+    pub fn row_height(&mut self, row_height: RowHeight) -> &mut Self {
+        self.row_height = row_height;
+
+        self
+    }
+
+    pub fn viewport_height(&mut self, viewport_height: f32) -> &mut Self {
+        self.viewport_height = viewport_height.max(0.0);
+
+        self
+    }
+
+    /// Rows rendered above and below the viewport so that fast scrolling does not
+    /// show blank space, 4 by default
+    pub fn overscan(&mut self, overscan: usize) -> &mut Self {
+        self.overscan = overscan;
+
+        self
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn scroll_top(&self) -> f32 {
+        self.scroll_top
+    }
+
+    /// Scroll to `scroll_top` pixels, clamped to the height of the rows
+    pub fn scroll_to(&mut self, scroll_top: f32) -> &mut Self {
+        let max_scroll = (self.total_height() - self.viewport_height).max(0.0);
+        self.scroll_top = scroll_top.clamp(0.0, max_scroll);
+
+        self
+    }
+
+    /// Scroll so that the row at `index` is at the top of the viewport
+    pub fn scroll_to_row(&mut self, index: usize) -> &mut Self {
+        self.scroll_to(self.offset_of(index))
+    }
+
+    /// The height of the row at `index`, measured or fixed if possible
+    pub fn height_of(&self, index: usize) -> f32 {
+        match self.row_height {
+            RowHeight::Fixed(height) => height,
+            RowHeight::Estimated(estimate) => self
+                .measured
+                .get(index)
+                .copied()
+                .flatten()
+                .unwrap_or(estimate),
+        }
+    }
+
+    /// Distance from the top of the list to the top of the row at `index`
+    pub fn offset_of(&self, index: usize) -> f32 {
+        match self.row_height {
+            RowHeight::Fixed(height) => height * index.min(self.row_count) as f32,
+            RowHeight::Estimated(_) => (0..index.min(self.row_count))
+                .map(|index| self.height_of(index))
+                .sum(),
+        }
+    }
+
+    pub fn total_height(&self) -> f32 {
+        self.offset_of(self.row_count)
+    }
+
+    /// Record the rendered height of a row, which moves every row below it.
+    /// Ignored for [RowHeight::Fixed] lists.
+    pub fn measure_row(&mut self, index: usize, height: f32) -> &mut Self {
+        if let (RowHeight::Estimated(_), Some(measured)) =
+            (self.row_height, self.measured.get_mut(index))
+        {
+            *measured = Some(height);
+        }
+
+        self
+    }
+
+    /// The row at `index`, if the list has that many
+    pub fn row(&self, index: usize) -> Option<PuppetRow> {
+        match &self.rows {
+            PuppetRows::Rendered(render_row) => (index < self.row_count).then(|| render_row(index)),
+            PuppetRows::Existing(rows) => rows.get(index).cloned(),
+        }
+    }
+
+    /// Insert `count` rows before `index`, which the renderer of the list builds.
+    /// When they are above the viewport the list scrolls by their height so that the
+    /// visible rows do not move.
+    ///
+    /// Lists built [Self::from_rows] have no renderer to build them, so they take
+    /// [Self::insert_existing_rows] instead.
+    pub fn insert_rows(&mut self, index: usize, count: usize) -> &mut Self {
+        if let PuppetRows::Existing(_) = self.rows {
+            trace!("INVALID INSERT {} ROWS WITHOUT A RENDERER", count);
+            return self;
+        }
+
+        self.insert_count(index, count)
+    }
+
+    /// Insert `rows` before `index` in a list built [Self::from_rows], keeping the
+    /// visible rows in place like [Self::insert_rows]
+    pub fn insert_existing_rows(&mut self, index: usize, rows: Vec<PuppetRow>) -> &mut Self {
+        let PuppetRows::Existing(existing) = &mut self.rows else {
+            trace!("INVALID INSERT {} ROWS INTO A RENDERED LIST", rows.len());
+            return self;
+        };
+
+        let index = index.min(self.row_count);
+        let count = rows.len();
+        Rc::make_mut(existing).splice(index..index, rows);
+
+        self.insert_count(index, count)
+    }
+
+    fn insert_count(&mut self, index: usize, count: usize) -> &mut Self {
+        let index = index.min(self.row_count);
+        let above_viewport = self.offset_of(index) < self.scroll_top;
+
+        self.row_count += count;
+        self.measured
+            .splice(index..index, core::iter::repeat_n(None, count));
+
+        if above_viewport {
+            self.scroll_top += self.offset_of(index + count) - self.offset_of(index);
+        }
+
+        self
+    }
+
+    /// Remove the rows in `range`, scrolling up by their height when they are above
+    /// the viewport
+    pub fn remove_rows(&mut self, range: Range<usize>) -> &mut Self {
+        let range = range.start.min(self.row_count)..range.end.min(self.row_count);
+        let removed_above = self.offset_of(range.end).min(self.scroll_top)
+            - self.offset_of(range.start).min(self.scroll_top);
+
+        self.row_count -= range.len();
+        self.measured.drain(range.clone());
+        if let PuppetRows::Existing(rows) = &mut self.rows {
+            Rc::make_mut(rows).drain(range);
+        }
+        self.scroll_top -= removed_above;
+        self.scroll_to(self.scroll_top);
+
+        self
+    }
+
+    fn first_visible_row(&self) -> usize {
+        match self.row_height {
+            RowHeight::Fixed(height) if height > 0.0 => {
+                ((self.scroll_top / height) as usize).min(self.row_count)
+            }
+            _ => {
+                let mut offset = 0.0;
+
+                for index in 0..self.row_count {
+                    offset += self.height_of(index);
+                    if offset > self.scroll_top {
+                        return index;
+                    }
+                }
+
+                self.row_count
+            }
+        }
+    }
+
+    /// Indexes of the rows to render, overscan included
+    pub fn visible_range(&self) -> Range<usize> {
+        let first = self.first_visible_row();
+
+        let mut last = first;
+        let mut offset = self.offset_of(first);
+        let bottom = self.scroll_top + self.viewport_height;
+        while last < self.row_count && offset < bottom {
+            offset += self.height_of(last);
+            last += 1;
+        }
+
+        first.saturating_sub(self.overscan)..(last + self.overscan).min(self.row_count)
+    }
+
+    /// The scrolling viewport holding a spacer as tall as every row, with the visible
+    /// rows moved down to their place
+    pub fn to_element(&self) -> PuppetElement {
+        let range = self.visible_range();
+
+        let mut window = PuppetElement::new("div");
+        window.attribute(
+            "style",
+            format!(
+                "position: absolute; top: 0; left: 0; right: 0; transform: translateY({}px);",
+                self.offset_of(range.start)
+            ),
+        );
+
+        for index in range {
+            let Some(row) = self.row(index) else {
+                continue;
+            };
+            let mut row = row.to_element();
+            row.attribute("data-index", index.to_string());
+            window.add_child(row);
+        }
+
+        let mut spacer = PuppetElement::new("div");
+        spacer
+            .attribute(
+                "style",
+                format!("position: relative; height: {}px;", self.total_height()),
+            )
+            .add_child(window);
+
+        let mut viewport = PuppetElement::new("div");
+        if let Some(list_id) = self.list_id {
+            viewport.attribute("id", list_id);
+        }
+        viewport
+            .attribute(
+                "style",
+                format!("overflow-y: auto; height: {}px;", self.viewport_height),
+            )
+            .add_child(spacer);
+
+        viewport
+    }
+
+    pub fn to_html_string(&self) -> String {
+        self.to_element().to_html_string()
+    }
+
+    /// Render the list at the end of `parent_node` and render other rows as users
+    /// scroll it
+    pub fn mount(
+        self,
+        document: &Document,
+        parent_node: &Element,
+    ) -> JsValueResult<Rc<PuppetVirtualListHandle>> {
+        self.to_element().to_html(document, parent_node)?;
+
+        let viewport = parent_node
+            .last_element_child()
+            .and_then(|viewport| viewport.dyn_into::<HtmlElement>().ok())
+            .ok_or(JsValue::from_str("Could not find the rendered list"))?;
+
+        let handle = Rc::new(PuppetVirtualListHandle {
+            list: RefCell::new(self),
+            document: document.clone(),
+            viewport: viewport.clone(),
+            rendered: RefCell::new(0..0),
+        });
+        handle.render_window()?;
+
+        let weak_handle = Rc::downgrade(&handle);
+        let on_scroll = Closure::wrap(Box::new(move |_event: Event| {
+            if let Some(handle) = weak_handle.upgrade() {
+                if let Err(error) = handle.on_scroll() {
+                    trace!("{:?}", error);
+                }
+            }
+        }) as Box<dyn FnMut(Event)>);
+        viewport.add_event_listener_with_callback("scroll", on_scroll.as_ref().unchecked_ref())?;
+        // The listener lives as long as the list it is attached to
+        on_scroll.forget();
+
+        Ok(handle)
+    }
+}
+
+impl Debug for PuppetVirtualList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetVirtualList")
+            .field("list_id", &self.list_id)
+            .field("row_count", &self.row_count)
+            .field("row_height", &self.row_height)
+            .field("viewport_height", &self.viewport_height)
+            .field("overscan", &self.overscan)
+            .field("scroll_top", &self.scroll_top)
+            .finish()
+    }
+}
+
+/// A mounted [PuppetVirtualList]
+pub struct PuppetVirtualListHandle {
+    list: RefCell<PuppetVirtualList>,
+    document: Document,
+    viewport: HtmlElement,
+    rendered: RefCell<Range<usize>>,
+}
+
+impl PuppetVirtualListHandle {
+    pub fn with_list<F, R>(&self, callback: F) -> R
+    where
+        F: FnOnce(&PuppetVirtualList) -> R,
+    {
+        callback(&self.list.borrow())
+    }
+
+    /// Insert rows without moving the rows users are looking at
+    pub fn insert_rows(&self, index: usize, count: usize) -> JsValueResult<()> {
+        self.list.borrow_mut().insert_rows(index, count);
+        self.refresh()
+    }
+
+    pub fn insert_existing_rows(&self, index: usize, rows: Vec<PuppetRow>) -> JsValueResult<()> {
+        self.list.borrow_mut().insert_existing_rows(index, rows);
+        self.refresh()
+    }
+
+    pub fn remove_rows(&self, range: Range<usize>) -> JsValueResult<()> {
+        self.list.borrow_mut().remove_rows(range);
+        self.refresh()
+    }
+
+    pub fn scroll_to_row(&self, index: usize) -> JsValueResult<()> {
+        self.list.borrow_mut().scroll_to_row(index);
+        self.refresh()
+    }
+
+    /// Render every visible row again, such as after their data changed
+    pub fn refresh(&self) -> JsValueResult<()> {
+        *self.rendered.borrow_mut() = 0..0;
+        self.render_window()
+    }
+
+    fn on_scroll(&self) -> JsValueResult<()> {
+        self.list
+            .borrow_mut()
+            .scroll_to(self.viewport.scroll_top() as f32);
+
+        if self.list.borrow().visible_range() == *self.rendered.borrow() {
+            return Ok(());
+        }
+
+        self.render_window()
+    }
+
+    /// Replace the spacer and its rows, then measure rows of estimated height and
+    /// render again if the measures moved them
+    fn render_window(&self) -> JsValueResult<()> {
+        let range = self.render_spacer()?;
+
+        if matches!(self.list.borrow().row_height, RowHeight::Estimated(_))
+            && self.measure(range)?
+        {
+            self.render_spacer()?;
+        }
+
+        self.viewport
+            .set_scroll_top(self.list.borrow().scroll_top().round() as i32);
+
+        Ok(())
+    }
+
+    fn render_spacer(&self) -> JsValueResult<Range<usize>> {
+        let list = self.list.borrow();
+
+        self.viewport.set_inner_html("");
+        for child in list.to_element().children() {
+            if let PuppetChild::Element(child) = child {
+                child.to_html(&self.document, &self.viewport)?;
+            }
+        }
+
+        let range = list.visible_range();
+        *self.rendered.borrow_mut() = range.clone();
+
+        Ok(range)
+    }
+
+    /// Whether any rendered row had another height than expected
+    fn measure(&self, range: Range<usize>) -> JsValueResult<bool> {
+        let mut changed = false;
+        let rows = self.viewport.query_selector_all("[data-index]")?;
+
+        for index in 0..rows.length() {
+            let Some(row) = rows
+                .get(index)
+                .and_then(|row| row.dyn_into::<HtmlElement>().ok())
+            else {
+                continue;
+            };
+            let Some(row_index) = row
+                .get_attribute("data-index")
+                .and_then(|row_index| row_index.parse::<usize>().ok())
+                .filter(|row_index| range.contains(row_index))
+            else {
+                continue;
+            };
+
+            let height = row.offset_height() as f32;
+            let mut list = self.list.borrow_mut();
+            if (list.height_of(row_index) - height).abs() >= 1.0 {
+                // Keep the first visible row in place when rows above it change
+                if list.offset_of(row_index) < list.scroll_top {
+                    list.scroll_top += height - list.height_of(row_index);
+                }
+                list.measure_row(row_index, height);
+                changed = true;
+            }
+        }
+
+        Ok(changed)
+    }
+}
+
+impl Debug for PuppetVirtualListHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PuppetVirtualListHandle")
+            .field("list", &self.list.borrow())
+            .finish()
+    }
+}
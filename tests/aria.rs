@@ -0,0 +1,94 @@
+use puppeteer_builders::{
+    puppet, AriaAttribute, AriaLive, LayoutTag, PuppetColumn, PuppetRole, PuppetRow,
+};
+
+#[test]
+fn layout_nodes_are_written_as_semantic_elements() {
+    let column = puppet! {
+        column {
+            tag(LayoutTag::Main)
+            row { tag(LayoutTag::Header) heading("Puppeteer") }
+            row { tag(LayoutTag::Nav) aria(AriaAttribute::Label("Main".into())) }
+            row { tag(LayoutTag::Footer) }
+        }
+    };
+    let html = column.to_html_string();
+
+    assert!(html.starts_with("<main>"));
+    assert!(html.contains("<header>"));
+    assert!(html.contains(r#"<nav aria-label="Main">"#));
+    assert!(html.contains("<footer>"));
+    assert!(html.ends_with("</footer></main>"));
+}
+
+#[test]
+fn list_columns_have_list_item_rows() {
+    let list = puppet! {
+        column {
+            tag(LayoutTag::List)
+            row { paragraph("First") }
+            row { tag(LayoutTag::Section) paragraph("Second") }
+        }
+    };
+    let html = list.to_html_string();
+    assert!(html.starts_with("<ul>"));
+    assert_eq!(html.matches("<li>").count(), 2);
+
+    // List items cannot stand outside of a list, nor rows hold a list
+    let mut row = PuppetRow::new();
+    row.tag(LayoutTag::ListItem);
+    assert!(row.to_html_string().starts_with("<div>"));
+    row.tag(LayoutTag::List);
+    assert_eq!(row.semantics().get_tag(), LayoutTag::ListItem);
+}
+
+#[test]
+fn roles_are_written_unless_implied() {
+    let mut search = PuppetRow::new();
+    search
+        .tag(LayoutTag::Section)
+        .role(PuppetRole::Search)
+        .aria(AriaAttribute::Label("Site".into()));
+    assert!(search
+        .to_html_string()
+        .starts_with(r#"<section role="search" aria-label="Site">"#));
+
+    let mut main = PuppetColumn::new();
+    main.tag(LayoutTag::Main).role(PuppetRole::Main);
+    assert!(main.to_html_string().starts_with("<main>"));
+    assert!(main.semantics().get_role().unwrap().is_landmark());
+
+    let mut status = PuppetRow::new();
+    status
+        .role(PuppetRole::Status)
+        .aria(AriaAttribute::Live(AriaLive::Polite))
+        .aria(AriaAttribute::Atomic(true))
+        .aria(AriaAttribute::Live(AriaLive::Assertive));
+    assert!(status
+        .to_html_string()
+        .starts_with(r#"<div role="status" aria-live="assertive" aria-atomic="true">"#));
+}
+
+#[test]
+fn invalid_combinations_are_rejected() {
+    // Generic containers cannot be named, nor be expanded unless they are buttons
+    let mut row = PuppetRow::new();
+    row.aria(AriaAttribute::Label("Unnamed".into()))
+        .aria(AriaAttribute::Expanded(true));
+    assert!(row.semantics().get_attributes().is_empty());
+
+    row.role(PuppetRole::Button)
+        .aria(AriaAttribute::Expanded(false))
+        .aria(AriaAttribute::Controls("details".into()));
+    assert_eq!(row.semantics().get_attributes().len(), 2);
+
+    // Neither may a tag or role that would invalidate them
+    row.tag(LayoutTag::Nav).role(PuppetRole::Region);
+    assert_eq!(row.semantics().get_tag(), LayoutTag::Div);
+    assert_eq!(row.semantics().get_role(), Some(PuppetRole::Button));
+
+    let mut nav = PuppetColumn::new();
+    nav.tag(LayoutTag::Nav).role(PuppetRole::Toolbar);
+    assert_eq!(nav.semantics().get_role(), Some(PuppetRole::Navigation));
+    assert!(!nav.to_html_string().contains("role="));
+}
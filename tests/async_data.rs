@@ -0,0 +1,116 @@
+use puppeteer_builders::{puppet, AsyncState, PuppetAsync};
+use std::{
+    future::Future,
+    task::{Context, Poll, Waker},
+};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let mut context = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn user_profile() -> PuppetAsync<String, String> {
+    PuppetAsync::new(
+        "profile",
+        |key| {
+            let key = key.map(str::to_string);
+
+            async move {
+                match key.as_deref() {
+                    Some("ada") => Ok("Ada Lovelace".to_string()),
+                    Some(key) => Err(format!("No user {}", key)),
+                    None => Err("No user".to_string()),
+                }
+            }
+        },
+        |name| {
+            let name = name.clone();
+
+            puppet! { column { row { heading(name) } } }
+        },
+    )
+}
+
+#[test]
+fn pending_components_are_busy_skeletons() {
+    let profile = user_profile();
+    assert!(profile.state().is_pending());
+
+    let element = profile.to_element();
+    assert_eq!(element.get_attribute("id").unwrap().resolve(), "profile");
+    assert_eq!(
+        element.get_attribute("aria-busy").unwrap().resolve(),
+        "true"
+    );
+    assert_eq!(
+        element.get_attribute("data-state").unwrap().resolve(),
+        "pending"
+    );
+    assert!(profile
+        .to_html_string()
+        .contains("background-color: #EEEEEE;"));
+
+    let mut custom = user_profile();
+    custom.pending_view(|| puppet! { column { row { paragraph("Loading profile") } } });
+    assert!(custom.to_html_string().contains("<p"));
+    assert!(custom.to_html_string().contains("Loading profile</p>"));
+}
+
+#[test]
+fn loads_run_for_the_current_key() {
+    let mut profile = user_profile();
+    profile.key("ada");
+    assert_eq!(profile.get_key(), Some("ada"));
+
+    let result = block_on(profile.load());
+    profile.resolve(result);
+    assert_eq!(
+        profile.state(),
+        &AsyncState::Ready("Ada Lovelace".to_string())
+    );
+
+    let html = profile.to_html_string();
+    assert!(html.contains(r#"aria-busy="false" aria-live="polite" data-state="ready""#));
+    assert!(html.contains("Ada Lovelace</h1>"));
+
+    profile.reset();
+    assert!(profile.state().is_pending());
+}
+
+#[test]
+fn failures_show_the_error_with_a_retry_button() {
+    let mut profile = user_profile();
+    profile.key("grace");
+
+    let result = block_on(profile.load());
+    profile.resolve(result);
+    assert_eq!(
+        profile.state(),
+        &AsyncState::Failed("No user grace".to_string())
+    );
+
+    let html = profile.to_html_string();
+    assert!(html.contains(r#"data-state="failed""#));
+    assert!(html.contains(r#"role="alert""#));
+    assert!(html.contains("No user grace</p>"));
+    assert!(html.contains(r#"<button type="button" data-async-retry="">Retry</button>"#));
+}
+
+#[test]
+fn error_views_can_be_replaced() {
+    let mut profile = user_profile();
+    profile
+        .error_view(|_error| puppet! { column { row { paragraph("Could not load the profile") } } })
+        .resolve(Err("Timeout".to_string()));
+
+    let html = profile.to_html_string();
+    assert!(html.contains("Could not load the profile</p>"));
+    assert!(!html.contains("Timeout"));
+    assert!(html.contains("data-async-retry"));
+}
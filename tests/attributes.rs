@@ -0,0 +1,90 @@
+use puppeteer_builders::{
+    is_valid_attribute_name, is_valid_data_key, puppet, snapshot, Component, ComponentType,
+    PuppetAttribute, PuppetColumn, PuppetElement, PuppetRow,
+};
+
+#[test]
+fn attributes_are_written_on_every_node() {
+    let column = puppet! {
+        column {
+            attribute(PuppetAttribute::Lang("fr-CA".into()))
+            data("screen", "settings")
+            row {
+                attribute(PuppetAttribute::Hidden)
+                button("Enregistrer") {
+                    attribute(PuppetAttribute::Title("Save the settings".into()))
+                    data("test-id", "save")
+                }
+            }
+        }
+    };
+    let html = column.to_html_string();
+
+    assert!(html.starts_with(r#"<div lang="fr-CA" data-screen="settings">"#));
+    assert!(html.contains(r#"<div hidden="">"#));
+    assert!(html.contains(r#"<button title="Save the settings" data-test-id="save">"#));
+}
+
+#[test]
+fn values_are_escaped() {
+    let mut button = Component::new(ComponentType::Button, "Quote");
+    button
+        .attribute(PuppetAttribute::Title(r#"Say "hi" & <leave>"#.into()))
+        .data("payload", r#"{"a":"<b>"}"#);
+    let html = button.to_html_string();
+
+    assert!(html.contains(r#"title="Say &quot;hi&quot; &amp; &lt;leave&gt;""#));
+    assert!(html.contains(r#"data-payload="{&quot;a&quot;:&quot;&lt;b&gt;&quot;}""#));
+}
+
+#[test]
+fn invalid_names_are_rejected() {
+    assert!(is_valid_attribute_name("autocomplete"));
+    assert!(is_valid_attribute_name("xml:space"));
+    for name in [
+        "onclick",
+        "OnLoad",
+        "Translate",
+        "HREF",
+        "style",
+        "id",
+        "aria-label",
+        "data-x",
+        "a b",
+        "\"x",
+        "",
+    ] {
+        assert!(!is_valid_attribute_name(name), "{}", name);
+    }
+
+    assert!(is_valid_data_key("test-id"));
+    for key in ["", "testId", "xml-thing", "a b", "a\"b"] {
+        assert!(!is_valid_data_key(key), "{}", key);
+    }
+
+    let mut row = PuppetRow::new();
+    row.attribute(PuppetAttribute::Custom("onclick", "alert(1)".into()))
+        .attribute(PuppetAttribute::Lang("not a language".into()))
+        .data("Camel", "x")
+        .attribute(PuppetAttribute::Custom("translate", "no".into()))
+        .attribute(PuppetAttribute::Custom("translate", "yes".into()));
+
+    assert!(row.attributes().get_data("Camel").is_none());
+    assert!(row.attributes().get("lang").is_none());
+    assert!(row.to_html_string().starts_with(r#"<div translate="yes">"#));
+}
+
+#[test]
+fn dataset_is_part_of_snapshots() {
+    let mut element = PuppetElement::new("div");
+    element.data("track", "hero").data("track", "banner");
+    assert_eq!(element.get_data("track"), Some("banner"));
+    assert_eq!(
+        element.to_html_string(),
+        r#"<div data-track="banner"></div>"#
+    );
+
+    let mut column = PuppetColumn::new();
+    column.data("section", "home");
+    assert!(snapshot::render_snapshot(&column.to_element()).contains(r#"data-section="home""#));
+}
@@ -0,0 +1,136 @@
+use puppeteer_builders::{
+    puppet, PuppetLayoutWarning, PuppetLength, PuppetStyle, PuppetUnit, PuppetViewport,
+};
+
+fn viewport() -> PuppetViewport {
+    PuppetViewport::new(400.0, 300.0)
+}
+
+#[test]
+fn minimums_above_maximums_are_reported() {
+    let column = puppet! {
+        column {
+            style { width: PuppetLength::Half }
+            row {
+                style { min_width: PuppetUnit::Percentage(60), max_width: PuppetUnit::Pixels(100) }
+            }
+            row {
+                style { min_height: PuppetUnit::Pixels(50), max_height: PuppetUnit::Pixels(0) }
+            }
+        }
+    };
+
+    let warnings = column.validate_layout(&viewport());
+
+    // 60% of the 200px wide column
+    assert_eq!(
+        warnings,
+        [
+            PuppetLayoutWarning::MinWidthAboveMax {
+                path: "div[0] > div[0]".into(),
+                min: 120.0,
+                max: 100.0
+            },
+            PuppetLayoutWarning::MinHeightAboveMax {
+                path: "div[0] > div[1]".into(),
+                min: 50.0,
+                max: 0.0
+            },
+        ]
+    );
+    assert_eq!(
+        warnings[1].to_string(),
+        "div[0] > div[1]: min-height 50px above max-height 0px"
+    );
+}
+
+#[test]
+fn percentage_heights_need_a_parent_of_definite_height() {
+    let auto = puppet! {
+        column {
+            row {
+                style {
+                    height: PuppetLength::Half,
+                    min_height: PuppetUnit::Percentage(0),
+                    max_height: PuppetUnit::Percentage(80),
+                }
+            }
+        }
+    };
+
+    let warnings = auto.validate_layout(&viewport());
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings
+        .iter()
+        .all(|warning| warning.path() == "div[0] > div[0]"));
+    assert_eq!(
+        warnings[0],
+        PuppetLayoutWarning::PercentageOfAutoHeight {
+            path: "div[0] > div[0]".into(),
+            property: "height"
+        }
+    );
+
+    let definite = puppet! {
+        column {
+            style { height: PuppetLength::ViewPortHeight }
+            row {
+                style { height: PuppetLength::Half }
+                button("OK") { style { height: PuppetLength::Full } }
+            }
+        }
+    };
+    assert!(definite.validate_layout(&viewport()).is_empty());
+}
+
+#[test]
+fn fixed_widths_overflowing_their_parent_are_reported() {
+    let column = puppet! {
+        column {
+            style { width: PuppetLength::Half }
+            row { style { min_width: PuppetUnit::Pixels(300) } }
+            row { style { padding: PuppetUnit::Pixels(80), margin: PuppetUnit::Pixels(30) } }
+            row { style { ..PuppetStyle::reset() } }
+        }
+    };
+
+    let warnings = column.validate_layout(&viewport());
+    let overflows = warnings
+        .iter()
+        .map(|warning| match warning {
+            PuppetLayoutWarning::Overflow {
+                path,
+                width,
+                available,
+            } => (path.as_str(), *width, *available),
+            warning => panic!("unexpected {}", warning),
+        })
+        .collect::<Vec<_>>();
+
+    // The former defaults are 100% wide plus their padding
+    assert_eq!(
+        overflows,
+        [
+            ("div[0] > div[0]", 300.0, 200.0),
+            ("div[0] > div[1]", 220.0, 200.0),
+            ("div[0] > div[2]", 208.0, 200.0),
+        ]
+    );
+}
+
+#[test]
+fn consistent_trees_have_no_warnings() {
+    let column = puppet! {
+        column {
+            style { width: PuppetLength::Half, padding: PuppetUnit::Pixels(10) }
+            row {
+                style { min_width: PuppetUnit::Pixels(100), max_width: PuppetUnit::Percentage(100) }
+                button("OK") { style { width: PuppetLength::Half, min_width: PuppetUnit::Pixels(40) } }
+                paragraph("Saved")
+            }
+            row { style { max_width: PuppetUnit::Pixels(0) } }
+        }
+    };
+
+    assert_eq!(column.validate_layout(&viewport()), []);
+}
@@ -0,0 +1,85 @@
+use puppeteer_builders::{
+    puppet, Component, ComponentType, PuppetColor, PuppetElement, PuppetFocusRing, PuppetStyle,
+};
+
+#[test]
+fn tab_index_overrides_are_written() {
+    let row = puppet! {
+        row {
+            button("Save") { tab_index(1) }
+            link("Help") { href("/help") tab_index(-1) }
+        }
+    };
+    let html = row.to_html_string();
+
+    assert!(html.contains(r#"<button tabindex="1""#));
+    assert!(html.contains(r#"<a href="/help" tabindex="-1""#));
+
+    let mut paragraph = Component::new(ComponentType::Paragraph, "Notes");
+    paragraph.tab_index(0);
+    assert!(paragraph.to_element().is_focusable());
+}
+
+#[test]
+fn focus_order_puts_positive_indexes_first() {
+    let mut root = PuppetElement::new("div");
+
+    let mut hidden = PuppetElement::new("div");
+    hidden
+        .attribute("hidden", "")
+        .add_child(PuppetElement::new("button"));
+
+    let mut second = PuppetElement::new("input");
+    second.attribute("tabindex", "2");
+    let mut first = PuppetElement::new("select");
+    first.attribute("tabindex", "1");
+    let mut skipped = PuppetElement::new("button");
+    skipped.attribute("tabindex", "-1");
+    let mut link = PuppetElement::new("a");
+    link.attribute("href", "/");
+
+    root.add_child(PuppetElement::new("button"))
+        .add_child(hidden)
+        .add_child(second)
+        .add_child(PuppetElement::new("a"))
+        .add_child(skipped)
+        .add_child(first)
+        .add_child(link);
+
+    let order = root
+        .focus_order()
+        .iter()
+        .map(|element| element.tag())
+        .collect::<Vec<_>>();
+    assert_eq!(order, ["select", "input", "button", "a"]);
+}
+
+#[test]
+fn focus_rings_contrast_with_the_background() {
+    assert_eq!(
+        PuppetStyle::default().focus_ring(),
+        PuppetFocusRing::new(PuppetColor::Rgb(0, 0, 0))
+    );
+
+    let mut dark = PuppetStyle::default();
+    dark.background_color(PuppetColor::Hex("202124"));
+    assert_eq!(
+        dark.focus_ring(),
+        PuppetFocusRing::new(PuppetColor::Rgb(255, 255, 255))
+    );
+}
+
+#[test]
+fn focus_rings_are_written_as_css() {
+    let mut ring = PuppetFocusRing::new(PuppetColor::Hex("1A73E8"));
+    ring.width(3).offset(1);
+
+    assert_eq!(
+        ring.to_css(),
+        "outline: 3px solid #1A73E8; outline-offset: 1px;"
+    );
+
+    let stylesheet = ring.to_stylesheet();
+    assert!(stylesheet.starts_with(":focus-visible { outline: 3px solid #1A73E8;"));
+    assert!(stylesheet.contains("[data-skip-link]:not(:focus)"));
+}
@@ -0,0 +1,161 @@
+//! Hydration needs a document, run with `wasm-pack test --headless --firefox`
+#![cfg(target_arch = "wasm32")]
+
+use puppeteer_builders::{
+    PuppetElement, PuppetMismatch, PuppetStyle, PuppetStylePatch, PuppetStylesheet, PuppetUnit,
+    StyleState,
+};
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use web_sys::{Document, Element};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn document() -> Document {
+    web_sys::window().unwrap().document().unwrap()
+}
+
+/// A node of the document holding `html` as a server would have sent it
+fn server_markup(html: &str) -> Element {
+    let root = document().create_element("div").unwrap();
+    root.set_inner_html(html);
+    document().body().unwrap().append_child(&root).unwrap();
+
+    root
+}
+
+fn greeting() -> PuppetElement {
+    let mut style = PuppetStyle::default();
+    style.padding(PuppetUnit::Pixels(4));
+
+    let mut paragraph = PuppetElement::new("p");
+    paragraph.attribute("title", "Greeting").add_child("Hello");
+
+    let mut root = PuppetElement::new("div");
+    root.set_style(&style).add_child(paragraph);
+
+    root
+}
+
+#[wasm_bindgen_test]
+fn server_markup_hydrates_without_mismatches() {
+    let tree = greeting();
+
+    let inline = server_markup(&tree.to_html_string());
+    assert_eq!(tree.hydrate(&document(), &inline).unwrap(), []);
+    assert_eq!(inline.inner_html(), tree.to_html_string());
+
+    // Styles written into a stylesheet are found through their class
+    let mut stylesheet = PuppetStylesheet::new();
+    let html = tree.to_html_string_with_stylesheet(&mut stylesheet);
+    let classes = server_markup(&format!("\n  {}\n<!-- end -->", html));
+    assert_eq!(tree.hydrate(&document(), &classes).unwrap(), []);
+}
+
+#[wasm_bindgen_test]
+fn text_and_attributes_are_repaired() {
+    let tree = greeting();
+    let root = server_markup(
+        &tree
+            .to_html_string()
+            .replace("Hello", "Goodbye")
+            .replace("Greeting", "Farewell"),
+    );
+
+    let mismatches = tree.hydrate(&document(), &root).unwrap();
+
+    assert_eq!(
+        mismatches,
+        [
+            PuppetMismatch::Attribute {
+                path: "div[0] > p[0]".into(),
+                name: "title",
+                expected: "Greeting".into(),
+                found: Some("Farewell".into()),
+            },
+            PuppetMismatch::Text {
+                path: "div[0] > p[0] > #text[0]".into(),
+                expected: "Hello".into(),
+                found: "Goodbye".into(),
+            },
+        ]
+    );
+    assert_eq!(root.inner_html(), tree.to_html_string());
+}
+
+#[wasm_bindgen_test]
+fn extra_and_missing_nodes_are_repaired() {
+    let tree = greeting();
+    let html = tree.to_html_string();
+
+    let extra = server_markup(&html.replace("</p>", "</p><span>Ad</span>"));
+    assert_eq!(
+        tree.hydrate(&document(), &extra).unwrap(),
+        [PuppetMismatch::Extra {
+            path: "div[0]".into(),
+            found: "<span>".into(),
+        }]
+    );
+    assert_eq!(extra.inner_html(), html);
+
+    let missing = server_markup(&html.replace(r#"<p title="Greeting">Hello</p>"#, ""));
+    assert_eq!(
+        tree.hydrate(&document(), &missing).unwrap(),
+        [PuppetMismatch::Missing {
+            path: "div[0] > p[0]".into(),
+            expected: "<p>".into(),
+        }]
+    );
+    assert_eq!(missing.inner_html(), html);
+}
+
+#[wasm_bindgen_test]
+fn empty_text_is_not_missing() {
+    let mut paragraph = PuppetElement::new("p");
+    paragraph.add_child("Hello").add_child("");
+    let mut tree = PuppetElement::new("div");
+    tree.add_child(paragraph).add_child("");
+
+    let root = server_markup(&tree.to_html_string());
+
+    assert_eq!(tree.hydrate(&document(), &root).unwrap(), []);
+    assert_eq!(root.inner_html(), tree.to_html_string());
+}
+
+#[wasm_bindgen_test]
+fn state_rules_are_installed() {
+    let mut hover = PuppetStylePatch::new();
+    hover.padding(PuppetUnit::Pixels(8));
+    let mut button = PuppetElement::new("button");
+    button
+        .state_style(StyleState::Hover, hover)
+        .add_child("Save");
+
+    let mut stylesheet = PuppetStylesheet::new();
+    let class_name = stylesheet.state_class_name(button.states());
+
+    let root = server_markup(&button.to_html_string());
+    assert_eq!(button.hydrate(&document(), &root).unwrap(), []);
+
+    let rules = document()
+        .query_selector("style[data-puppet-states]")
+        .unwrap()
+        .and_then(|style| style.text_content())
+        .unwrap_or_default();
+    assert!(rules.contains(&format!(".{}:hover", class_name)));
+
+    // Markup without the class of the states is given it
+    let stale = server_markup("<button>Save</button>");
+    assert_eq!(
+        button.hydrate(&document(), &stale).unwrap(),
+        [PuppetMismatch::Attribute {
+            path: "button[0]".into(),
+            name: "class",
+            expected: class_name.clone(),
+            found: None,
+        }]
+    );
+    assert_eq!(
+        stale.inner_html(),
+        format!(r#"<button class="{}">Save</button>"#, class_name)
+    );
+}
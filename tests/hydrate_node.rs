@@ -0,0 +1,167 @@
+use puppeteer_builders::{
+    PuppetElement, PuppetMismatch, PuppetNode, PuppetStyle, PuppetStylePatch, PuppetUnit,
+    StyleState,
+};
+
+/// A node holding `element` as a browser would have parsed it
+fn server_markup(element: &PuppetElement) -> PuppetNode {
+    let mut root = PuppetNode::element("div");
+    root.add_child(PuppetNode::from_element(element));
+
+    root
+}
+
+fn greeting() -> PuppetElement {
+    let mut style = PuppetStyle::default();
+    style.padding(PuppetUnit::Pixels(4));
+
+    let mut paragraph = PuppetElement::new("p");
+    paragraph.attribute("title", "Greeting").add_child("Hello");
+
+    let mut root = PuppetElement::new("div");
+    root.set_style(&style).add_child(paragraph);
+
+    root
+}
+
+#[test]
+fn server_markup_hydrates_without_mismatches() {
+    let tree = greeting();
+
+    let root = server_markup(&tree);
+    assert_eq!(tree.hydrate_node(&root), []);
+    assert_eq!(root.inner_html(), tree.to_html_string());
+
+    // Whitespace and comments between elements are left alone
+    let mut spaced = PuppetNode::element("div");
+    spaced
+        .add_child(PuppetNode::text("\n  "))
+        .add_child(PuppetNode::from_element(&tree))
+        .add_child(PuppetNode::comment(" end "));
+    assert_eq!(tree.hydrate_node(&spaced), []);
+}
+
+#[test]
+fn adjacent_text_is_split_again() {
+    let mut paragraph = PuppetElement::new("p");
+    paragraph
+        .add_child("Hello, ")
+        .add_child("")
+        .add_child("Ada")
+        .add_child("!");
+
+    let root = server_markup(&paragraph);
+    let parsed = root.children()[0].clone();
+    assert_eq!(parsed.children().len(), 1);
+
+    assert_eq!(paragraph.hydrate_node(&root), []);
+    let texts = parsed
+        .children()
+        .iter()
+        .map(PuppetNode::to_html_string)
+        .collect::<Vec<_>>();
+    assert_eq!(texts, ["Hello, ", "", "Ada", "!"]);
+
+    // Text differing from the tree is reported once for the merged node
+    let mut renamed = PuppetElement::new("p");
+    renamed.add_child("Hello, ").add_child("Bob").add_child("!");
+    let root = server_markup(&renamed);
+    assert_eq!(
+        paragraph.hydrate_node(&root),
+        [PuppetMismatch::Text {
+            path: "p[0] > #text[0]".into(),
+            expected: "Hello, Ada!".into(),
+            found: "Hello, Bob!".into(),
+        }]
+    );
+    assert_eq!(root.children()[0].children().len(), 4);
+    assert_eq!(root.inner_html(), paragraph.to_html_string());
+}
+
+#[test]
+fn attributes_the_tree_lacks_are_removed() {
+    let mut hover = PuppetStylePatch::new();
+    hover.padding(PuppetUnit::Pixels(8));
+    let mut button = PuppetElement::new("button");
+    button
+        .attribute("class", "primary")
+        .data("action", "save")
+        .state_style(StyleState::Hover, hover)
+        .add_child("Save");
+
+    // The class of the states follows the class of the tree
+    let root = server_markup(&button);
+    assert_eq!(button.hydrate_node(&root), []);
+
+    let mut server = PuppetNode::from_element(&button);
+    server
+        .attribute("onclick", "steal()")
+        .attribute("data-tracking", "1");
+    let mut root = PuppetNode::element("div");
+    root.add_child(server.clone());
+
+    assert_eq!(
+        button.hydrate_node(&root),
+        [
+            PuppetMismatch::ExtraAttribute {
+                path: "button[0]".into(),
+                name: "onclick".into(),
+                found: "steal()".into(),
+            },
+            PuppetMismatch::ExtraAttribute {
+                path: "button[0]".into(),
+                name: "data-tracking".into(),
+                found: "1".into(),
+            },
+        ]
+    );
+    assert_eq!(server.get_attribute("onclick"), None);
+    assert_eq!(root.inner_html(), button.to_html_string());
+}
+
+#[test]
+fn nodes_are_repaired() {
+    let tree = greeting();
+
+    let mut farewell = PuppetElement::new("p");
+    farewell.attribute("title", "Farewell").add_child("Goodbye");
+    let mut server = PuppetNode::element("div");
+    server
+        .attribute("style", "padding: 4px;")
+        .add_child(PuppetNode::from_element(&farewell))
+        .add_child(PuppetNode::element("span"));
+    let mut root = PuppetNode::element("div");
+    root.add_child(server);
+
+    assert_eq!(
+        tree.hydrate_node(&root),
+        [
+            PuppetMismatch::Attribute {
+                path: "div[0] > p[0]".into(),
+                name: "title",
+                expected: "Greeting".into(),
+                found: Some("Farewell".into()),
+            },
+            PuppetMismatch::Text {
+                path: "div[0] > p[0] > #text[0]".into(),
+                expected: "Hello".into(),
+                found: "Goodbye".into(),
+            },
+            PuppetMismatch::Extra {
+                path: "div[0]".into(),
+                found: "<span>".into(),
+            },
+        ]
+    );
+    assert_eq!(root.inner_html(), tree.to_html_string());
+
+    let empty = PuppetNode::element("div");
+    assert_eq!(
+        tree.hydrate_node(&empty),
+        [PuppetMismatch::Missing {
+            path: "div[0]".into(),
+            expected: "<div>".into(),
+        }]
+    );
+    assert_eq!(empty.inner_html(), tree.to_html_string());
+}
@@ -0,0 +1,109 @@
+use puppeteer_builders::{
+    current_direction, current_locale, register_locale, set_locale, PluralCategory,
+    PuppetDirection, PuppetLocale, PuppetMessage,
+};
+
+fn files(tag: &'static str) -> PuppetLocale {
+    let mut locale = PuppetLocale::new(tag);
+    locale.plural(
+        "files",
+        &[
+            (PluralCategory::Zero, "zero"),
+            (PluralCategory::One, "one"),
+            (PluralCategory::Two, "two"),
+            (PluralCategory::Few, "few"),
+            (PluralCategory::Many, "many"),
+            (PluralCategory::Other, "other"),
+        ],
+    );
+
+    locale
+}
+
+fn forms(counts: &[i64]) -> Vec<String> {
+    counts
+        .iter()
+        .map(|count| PuppetMessage::new("files").count(*count).resolve())
+        .collect()
+}
+
+#[test]
+fn plural_forms_follow_the_language() {
+    for tag in ["en", "fr", "ja", "ar", "pl", "ru-RU"] {
+        register_locale(files(tag));
+    }
+
+    set_locale("en").unwrap();
+    assert_eq!(forms(&[0, 1, 2, -1]), ["other", "one", "other", "one"]);
+
+    set_locale("fr").unwrap();
+    assert_eq!(forms(&[0, 1, 2]), ["one", "one", "other"]);
+
+    set_locale("ja").unwrap();
+    assert_eq!(forms(&[0, 1, 2]), ["other", "other", "other"]);
+
+    set_locale("ar").unwrap();
+    assert_eq!(
+        forms(&[0, 1, 2, 3, 11, 100, 103]),
+        ["zero", "one", "two", "few", "many", "other", "few"]
+    );
+}
+
+#[test]
+fn polish_only_uses_the_singular_for_one() {
+    register_locale(files("pl"));
+    register_locale(files("ru-RU"));
+
+    set_locale("pl").unwrap();
+    assert_eq!(
+        forms(&[1, 2, 4, 5, 12, 14, 21, 22, 101, 112, 0]),
+        ["one", "few", "few", "many", "many", "many", "many", "few", "many", "many", "many"]
+    );
+
+    set_locale("ru-RU").unwrap();
+    assert_eq!(
+        forms(&[1, 2, 5, 11, 12, 21, 22, 101, 111]),
+        ["one", "few", "many", "many", "many", "one", "few", "one", "many"]
+    );
+}
+
+#[test]
+fn missing_messages_fall_back_to_the_first_locale() {
+    let mut english = PuppetLocale::new("en");
+    english
+        .message("save", "Save")
+        .message("greeting", "Hello, {name}!")
+        .plural("inbox", &[(PluralCategory::Other, "{count} messages")]);
+    let mut german = PuppetLocale::new("de");
+    german.message("save", "Speichern");
+
+    register_locale(english);
+    register_locale(german);
+    assert_eq!(current_locale(), Some("en"));
+
+    set_locale("de").unwrap();
+    assert_eq!(PuppetMessage::new("save").resolve(), "Speichern");
+    assert_eq!(
+        PuppetMessage::new("greeting").arg("name", "Ada").resolve(),
+        "Hello, Ada!"
+    );
+    // Forms missing from a plural message use the other form
+    assert_eq!(PuppetMessage::new("inbox").count(1).resolve(), "1 messages");
+    // Neither locale knows the key
+    assert_eq!(PuppetMessage::new("unknown").resolve(), "unknown");
+}
+
+#[test]
+fn unknown_locales_keep_the_current_one() {
+    register_locale(PuppetLocale::new("he"));
+    register_locale(PuppetLocale::new("en"));
+
+    assert_eq!(current_locale(), Some("he"));
+    assert_eq!(current_direction(), PuppetDirection::Rtl);
+
+    set_locale("xx").unwrap();
+    assert_eq!(current_locale(), Some("he"));
+
+    set_locale("en").unwrap();
+    assert_eq!(current_direction(), PuppetDirection::Ltr);
+}
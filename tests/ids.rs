@@ -0,0 +1,120 @@
+use puppeteer_builders::{
+    puppet, seed_ids, Component, ComponentType, PuppetColumn, PuppetRow, PUPPET_ID_PREFIX,
+};
+
+#[test]
+fn seeded_ids_are_reproducible() {
+    seed_ids(42);
+    let first = (0..3)
+        .map(|_| PuppetRow::new().id().into_owned())
+        .collect::<Vec<_>>();
+
+    seed_ids(42);
+    let second = (0..3)
+        .map(|_| PuppetRow::new().id().into_owned())
+        .collect::<Vec<_>>();
+
+    assert_eq!(first, second);
+    assert!(first.iter().all(|id| id.starts_with(PUPPET_ID_PREFIX)));
+
+    seed_ids(7);
+    assert_ne!(PuppetRow::new().id(), first[0]);
+}
+
+#[test]
+fn generated_ids_are_unique_but_not_written() {
+    let ids = (0..100)
+        .map(|_| PuppetColumn::new().id().into_owned())
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(ids.len(), 100);
+
+    // Ids are only written when something refers to them, and trees built alike
+    // stay equal
+    let column = puppet! { column { row { button("Save") } } };
+    assert!(!column.to_html_string().contains(" id="));
+    assert_eq!(column, puppet! { column { row { button("Save") } } });
+
+    let mut exposed = PuppetColumn::new();
+    exposed.expose_id();
+    assert!(exposed
+        .to_html_string()
+        .starts_with(&format!(r#"<div id="{}""#, exposed.id())));
+}
+
+#[test]
+fn labels_and_descriptions_refer_to_generated_ids() {
+    let mut heading = Component::new(ComponentType::Heading, "Delete account");
+    let mut hint = Component::new(ComponentType::Paragraph, "This cannot be undone");
+    let mut button = Component::new(ComponentType::Button, "Delete");
+    button
+        .labelled_by(&mut heading)
+        .described_by(&mut hint)
+        .described_by_id("terms");
+
+    let (heading_id, hint_id) = (heading.id(), hint.id());
+    let mut row = PuppetRow::new();
+    row.add_component(heading)
+        .add_component(hint)
+        .add_component(button);
+    let html = row.to_html_string();
+
+    assert!(html.contains(&format!(r#"<h1 id="{}""#, heading_id)));
+    assert!(html.contains(&format!(r#"<p id="{}""#, hint_id)));
+    assert!(html.contains(&format!(
+        r#"aria-labelledby="{}" aria-describedby="{} terms""#,
+        heading_id, hint_id
+    )));
+}
+
+#[test]
+fn clones_keep_their_ids() {
+    let mut heading = Component::new(ComponentType::Heading, "Settings");
+    let mut button = Component::new(ComponentType::Button, "Save");
+    button.labelled_by(&mut heading);
+    let mut row = PuppetRow::new();
+    row.add_component(heading).add_component(button);
+
+    // Labels of a cloned tree still point to an element of that tree
+    let clone = row.clone();
+    assert_eq!(clone.id(), row.id());
+    let html = clone.to_html_string();
+    let target = html
+        .split(r#"aria-labelledby=""#)
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap();
+    assert!(html.contains(&format!(r#"<h1 id="{}""#, target)));
+
+    // Rendering a tree next to its clone writes its ids twice
+    let mut column = PuppetColumn::new();
+    column.add_row(row.clone()).add_row(row);
+    let element = column.to_element();
+    assert_eq!(element.duplicate_ids().len(), 1);
+    assert_eq!(element.duplicate_ids()[0], target);
+}
+
+#[test]
+fn user_ids_are_validated() {
+    let column = puppet! {
+        column {
+            id("settings")
+            row { id("settings") button("Save") { id("save") } }
+            row { button("Cancel") { id("save") } }
+        }
+    };
+    assert_eq!(column.to_element().duplicate_ids(), ["settings", "save"]);
+
+    let mut row = PuppetRow::new();
+    row.column_id("has spaces");
+    assert_ne!(row.id(), "has spaces");
+    assert!(!row.to_html_string().contains(" id="));
+
+    let mut labelled = Component::new(ComponentType::Button, "Close");
+    labelled
+        .component_id("close")
+        .labelled_by_id("dialog-title");
+    assert_eq!(labelled.id(), "close");
+    assert!(labelled
+        .to_html_string()
+        .starts_with(r#"<button id="close" aria-labelledby="dialog-title""#));
+}
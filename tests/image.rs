@@ -0,0 +1,112 @@
+use puppeteer_builders::{
+    puppet, Component, ComponentType, ImageAlt, ImageDescriptor, ImageLoading, ObjectFit,
+    PuppetChild, PuppetColor, PuppetImage, PuppetSite,
+};
+
+#[test]
+fn image_components_keep_their_alt_and_src() {
+    let mut logo = Component::new(ComponentType::Image, "Logo");
+    logo.src("logo.png");
+
+    let html = logo.to_html_string();
+    assert!(html.starts_with(r#"<img alt="Logo" src="logo.png">"#));
+    assert!(!html.contains("loading="));
+    assert!(!html.contains("object-fit"));
+
+    let without_src = Component::new(ComponentType::Image, "Nothing yet").to_html_string();
+    assert!(without_src.starts_with(r#"<img alt="Nothing yet">"#));
+}
+
+#[test]
+fn decorative_images_have_an_empty_alt() {
+    let divider = PuppetImage::decorative("divider.svg");
+    assert!(divider.is_decorative());
+    assert!(divider
+        .to_html_string()
+        .starts_with(r#"<img alt="" src="divider.svg""#));
+
+    // The text of a component is ignored once its image is decorative
+    let mut component = Component::new(ComponentType::Image, "Divider");
+    component.image(PuppetImage::decorative("divider.svg"));
+    assert!(component.to_html_string().starts_with(r#"<img alt="" "#));
+
+    let component = Component::from(PuppetImage::new("logo.png", "Logo"));
+    assert!(component
+        .to_html_string()
+        .starts_with(r#"<img alt="Logo" "#));
+}
+
+#[test]
+fn density_candidates_and_lazy_loading() {
+    let mut avatar = PuppetImage::new("avatar.png", "Ada");
+    avatar
+        .candidate("avatar.png", ImageDescriptor::Density(1))
+        .candidate("avatar@2x.png", ImageDescriptor::Density(2))
+        .dimensions(64, 64)
+        .loading(ImageLoading::Lazy);
+
+    assert_eq!(
+        avatar.to_html_srcset().as_deref(),
+        Some("avatar.png 1x, avatar@2x.png 2x")
+    );
+    assert_eq!(avatar.get_alt(), &ImageAlt::Text("Ada".into()));
+
+    let element = avatar.to_element();
+    assert_eq!(element.get_attribute("width").unwrap().resolve(), "64");
+    assert_eq!(element.get_attribute("height").unwrap().resolve(), "64");
+    assert_eq!(element.get_attribute("loading").unwrap().resolve(), "lazy");
+    assert!(element.get_attribute("sizes").is_none());
+}
+
+#[test]
+fn placeholders_and_fit_are_part_of_the_style() {
+    let mut cover = PuppetImage::new("cover.jpg", "Cover");
+    cover
+        .object_fit(ObjectFit::Contain)
+        .placeholder(PuppetColor::Rgb(200, 200, 200));
+
+    let column = puppet! { column { row { image("Cover") { model(cover) } } } };
+    let html = column.to_html_string();
+    assert!(html.contains("background-color: rgb(200,200,200); object-fit: contain;"));
+
+    // The DOM backend clears the placeholder once the image has loaded
+    let element = column.to_element();
+    let PuppetChild::Element(row) = &element.children()[0] else {
+        panic!("expected a row");
+    };
+    let PuppetChild::Element(image) = &row.children()[0] else {
+        panic!("expected an image");
+    };
+    assert_eq!(image.handlers()[0].event(), "load");
+}
+
+#[test]
+fn sites_copy_every_candidate() {
+    let mut hero = PuppetImage::new("images/hero.jpg", "Hero");
+    hero.candidate("images/hero.jpg", ImageDescriptor::Width(800))
+        .candidate("images/hero-wide.jpg", ImageDescriptor::Width(1600))
+        .candidate(
+            "https://cdn.example.com/hero.jpg",
+            ImageDescriptor::Width(3200),
+        );
+
+    let mut site = PuppetSite::new("Docs");
+    site.page(
+        "guides/intro",
+        "Intro",
+        puppet! { column { row { image("Hero") { model(hero) } } } },
+    );
+
+    assert_eq!(site.assets(), ["images/hero-wide.jpg", "images/hero.jpg"]);
+
+    let files = site.render();
+    let page = &files
+        .iter()
+        .find(|(path, _)| path == "guides/intro.html")
+        .unwrap()
+        .1;
+    assert!(page.contains(concat!(
+        r#"srcset="../images/hero.jpg 800w, ../images/hero-wide.jpg 1600w, "#,
+        r#"https://cdn.example.com/hero.jpg 3200w""#
+    )));
+}
@@ -0,0 +1,186 @@
+use puppeteer_builders::{
+    puppet, AlignContent, AlignItems, AlignSelf, FlexDirection, FlexGap, FlexWrap, JustifyContent,
+    PuppetAlignment, PuppetDirection, PuppetElement, PuppetLength, PuppetRect, PuppetStyle,
+    PuppetUnit, PuppetViewport,
+};
+
+fn flex(flex_direction: FlexDirection, justify_content: JustifyContent) -> PuppetAlignment {
+    PuppetAlignment::Flex {
+        flex_direction,
+        flex_wrap: FlexWrap::NoWrap,
+        justify_content,
+        align_self: AlignSelf::Auto,
+        align_items: AlignItems::FlexStart,
+        align_content: AlignContent::FlexStart,
+        gap: FlexGap::Column(PuppetUnit::Pixels(10)),
+    }
+}
+
+#[test]
+fn root_fills_the_viewport_width() {
+    let column = puppet! {
+        column {
+            id("root")
+            style { width: PuppetLength::Half, height: PuppetLength::ViewPortHeight }
+        }
+    };
+
+    let root = column.compute_layout(&PuppetViewport::new(1000.0, 600.0));
+
+    assert_eq!(root.rect(), PuppetRect::new(0.0, 0.0, 500.0, 600.0));
+}
+
+#[test]
+fn min_and_max_widths_are_honored() {
+    let column = puppet! {
+        column {
+            style { padding: PuppetUnit::Pixels(0) }
+            row {
+                id("narrow")
+                style { width: PuppetLength::Eighth, min_width: PuppetUnit::Pixels(200), padding: PuppetUnit::Pixels(0) }
+            }
+            row {
+                id("wide")
+                style { max_width: PuppetUnit::Pixels(300), padding: PuppetUnit::Pixels(0) }
+            }
+        }
+    };
+
+    let root = column.compute_layout(&PuppetViewport::new(1000.0, 600.0));
+
+    assert_eq!(root.find("narrow").unwrap().rect().width, 200.0);
+    assert_eq!(root.find("wide").unwrap().rect().width, 300.0);
+}
+
+#[test]
+fn rows_are_justified_and_mirrored_in_rtl() {
+    let row = puppet! {
+        row {
+            id("row")
+            style {
+                padding: PuppetUnit::Pixels(0),
+                width: PuppetLength::Normal,
+                align_items: flex(FlexDirection::Row, JustifyContent::FlexEnd),
+            }
+            button("OK") { style { padding: PuppetUnit::Pixels(0), width: PuppetLength::Normal } }
+            button("Cancel") { style { padding: PuppetUnit::Pixels(0), width: PuppetLength::Normal } }
+        }
+    };
+
+    let mut viewport = PuppetViewport::new(400.0, 300.0);
+    let layout = row.to_element().compute_layout(&viewport);
+    let buttons = layout.children();
+
+    // 8px per character, pushed to the end of the row with a 10px gap
+    assert_eq!(buttons[0].rect(), PuppetRect::new(326.0, 0.0, 16.0, 16.0));
+    assert_eq!(buttons[1].rect(), PuppetRect::new(352.0, 0.0, 48.0, 16.0));
+
+    viewport.direction(PuppetDirection::Rtl);
+    let layout = row.to_element().compute_layout(&viewport);
+    let buttons = layout.children();
+
+    assert_eq!(buttons[0].rect().x, 58.0);
+    assert_eq!(buttons[1].rect().x, 0.0);
+}
+
+#[test]
+fn full_width_rows_wrap_onto_their_own_lines() {
+    let column = puppet! {
+        column {
+            style { padding: PuppetUnit::Pixels(0) }
+            row { id("first") style { padding: PuppetUnit::Pixels(10) } }
+            row { id("second") style { padding: PuppetUnit::Pixels(10) } }
+        }
+    };
+
+    let root = column.compute_layout(&PuppetViewport::new(800.0, 600.0));
+    let first = root.find("first").unwrap().rect();
+    let second = root.find("second").unwrap().rect();
+
+    assert_eq!(first.width, 800.0);
+    assert!(second.y >= first.bottom());
+}
+
+#[test]
+fn alignments_are_written_as_they_are_laid_out() {
+    let mut style = PuppetStyle::default();
+    style.align_items(flex(FlexDirection::Row, JustifyContent::FlexEnd));
+
+    assert_eq!(
+        style.to_css(),
+        "display: flex; flex-direction: row; flex-wrap: nowrap; justify-content: flex-end; \
+         align-self: auto; align-items: flex-start; align-content: flex-start; column-gap: 10px;"
+    );
+
+    style.align_items(PuppetAlignment::Grid);
+    assert_eq!(style.to_css(), "display: grid;");
+}
+
+#[test]
+fn blocks_ignore_align_self() {
+    let mut centered = flex(FlexDirection::Row, JustifyContent::FlexStart);
+    if let PuppetAlignment::Flex { align_self, .. } = &mut centered {
+        *align_self = AlignSelf::Center;
+    }
+
+    let column = puppet! {
+        column {
+            row {
+                id("block")
+                style { align_items: centered }
+                button("OK")
+            }
+        }
+    };
+
+    let root = column.compute_layout(&PuppetViewport::new(400.0, 300.0));
+
+    // Without `display: flex` on the column its rows stretch across
+    assert_eq!(
+        root.find("block").unwrap().rect(),
+        PuppetRect::new(0.0, 0.0, 400.0, 16.0)
+    );
+}
+
+#[test]
+fn deep_trees_are_measured_once_per_level() {
+    let mut element = PuppetElement::new("p");
+    element.add_child("Leaf");
+
+    // Laying every level out twice would take 2^64 passes
+    for _ in 0..64 {
+        let mut parent = PuppetElement::new("div");
+        parent.add_child(element);
+        element = parent;
+    }
+
+    let root = element.compute_layout(&PuppetViewport::new(400.0, 300.0));
+    assert_eq!(root.rect(), PuppetRect::new(0.0, 0.0, 400.0, 16.0));
+}
+
+#[test]
+fn column_items_do_not_shrink_below_their_content() {
+    let mut style = PuppetStyle::default();
+    style
+        .height(PuppetLength::Full)
+        .padding(PuppetUnit::Pixels(0))
+        .align_items(flex(FlexDirection::Column, JustifyContent::FlexStart));
+
+    let mut column = PuppetElement::new("div");
+    column.set_style(&style);
+    for id in ["first", "second"] {
+        let mut paragraph = PuppetElement::new("p");
+        paragraph
+            .attribute("id", id)
+            .add_child(["ab"; 10].join(" "));
+        column.add_child(paragraph);
+    }
+
+    // Ten lines of two characters in a 16px wide column of 100px
+    let root = column.compute_layout(&PuppetViewport::new(16.0, 100.0));
+
+    let first = root.find("first").unwrap().rect();
+    let second = root.find("second").unwrap().rect();
+    assert_eq!(first.height, 160.0);
+    assert_eq!(second, PuppetRect::new(0.0, 160.0, 16.0, 160.0));
+}
@@ -0,0 +1,90 @@
+use puppeteer_builders::{
+    puppet, PuppetBreadcrumbs, PuppetChild, PuppetMenu, PuppetNavLink, PuppetNavbar, PuppetTab,
+    PuppetTabs,
+};
+
+#[test]
+fn navbars_are_labelled_landmarks_with_a_collapsed_toggle() {
+    let mut users = PuppetNavLink::new("Users", "/users");
+    users.current(true);
+
+    let mut navbar = PuppetNavbar::new("main-nav");
+    navbar
+        .label("Primary")
+        .brand("Admin")
+        .add_link(PuppetNavLink::new("Home", "/"))
+        .add_link(users);
+
+    let element = navbar.to_element();
+    assert_eq!(element.tag(), "nav");
+    assert_eq!(
+        element.get_attribute("aria-label").unwrap().resolve(),
+        "Primary"
+    );
+
+    let html = navbar.to_html_string();
+    assert!(html.contains(
+        r#"type="button" aria-controls="main-nav-links" aria-expanded="true" hidden="""#
+    ));
+    assert!(html.contains(r#"<a href="/users" aria-current="page""#));
+    assert!(!html.contains(r#"<a href="/" aria-current"#));
+}
+
+#[test]
+fn tabs_link_each_tab_to_its_panel() {
+    let mut tabs = PuppetTabs::new("settings");
+    tabs.label("Settings")
+        .add_tab(PuppetTab::new("Profile", puppet! { column {} }))
+        .add_tab(PuppetTab::new("Security", puppet! { column {} }))
+        .select(7);
+    assert_eq!(tabs.selected(), 1);
+
+    let element = tabs.to_element();
+    let Some(PuppetChild::Element(tab_list)) = element.children().first() else {
+        panic!("expected a tab list");
+    };
+    assert_eq!(tab_list.get_attribute("role").unwrap().resolve(), "tablist");
+    assert_eq!(element.children().len(), 3);
+
+    let html = tabs.to_html_string();
+    assert!(html.contains(
+        r#"role="tab" id="settings-tab-0" aria-selected="false" aria-controls="settings-panel-0" tabindex="-1""#
+    ));
+    assert!(html.contains(
+        r#"role="tab" id="settings-tab-1" aria-selected="true" aria-controls="settings-panel-1" tabindex="0""#
+    ));
+    assert!(html.contains(
+        r#"<div role="tabpanel" id="settings-panel-1" aria-labelledby="settings-tab-1" tabindex="0">"#
+    ));
+}
+
+#[test]
+fn breadcrumbs_mark_the_last_link_current() {
+    let mut breadcrumbs = PuppetBreadcrumbs::new();
+    breadcrumbs
+        .add_link(PuppetNavLink::new("Home", "/"))
+        .add_link(PuppetNavLink::new("Users", "/users"))
+        .add_link(PuppetNavLink::new("Ada", "/users/ada"));
+
+    let html = breadcrumbs.to_html_string();
+    assert!(html.starts_with(r#"<nav aria-label="Breadcrumb"><ol><li>"#));
+    assert_eq!(html.matches("<li>").count(), 3);
+    assert_eq!(html.matches("aria-current").count(), 1);
+    assert!(html.contains(r#"<a href="/users/ada" aria-current="page""#));
+}
+
+#[test]
+fn menus_start_closed_with_unfocusable_items() {
+    let mut menu = PuppetMenu::new("account", "Account");
+    menu.add_link(PuppetNavLink::new("Profile", "/profile"))
+        .add_link(PuppetNavLink::new("Sign out", "/sign-out"));
+
+    let html = menu.to_html_string();
+    assert!(html.contains(
+        r#"id="account-button" aria-haspopup="menu" aria-expanded="false" aria-controls="account-menu""#
+    ));
+    assert!(html
+        .contains(r#"id="account-menu" role="menu" aria-labelledby="account-button" hidden="""#));
+    assert_eq!(html.matches(r#"role="menuitem" tabindex="-1""#).count(), 2);
+    assert_eq!(html.matches(r#"role="none""#).count(), 2);
+}
@@ -0,0 +1,73 @@
+use puppeteer_builders::{
+    puppet, DrawerSide, PopoverPlacement, PuppetColor, PuppetOverlay, PuppetStyle,
+};
+
+#[test]
+fn modal_dialogs_sit_on_a_backdrop() {
+    let mut dialog = PuppetOverlay::modal(puppet! { column { row { button("Close") } } });
+    dialog.overlay_id("confirm").label("Confirm");
+
+    let element = dialog.to_element();
+    assert_eq!(element.tag(), "div");
+    assert_eq!(
+        element.get_attribute("data-overlay").unwrap().resolve(),
+        "modal"
+    );
+    assert!(element
+        .style()
+        .unwrap()
+        .to_css()
+        .contains("background-color: rgb(0,0,0,0.5);"));
+
+    let html = dialog.to_html_string();
+    assert!(html.contains(
+        r#"<div id="confirm" role="dialog" aria-modal="true" tabindex="-1" aria-label="Confirm">"#
+    ));
+}
+
+#[test]
+fn drawers_name_their_side_and_custom_backdrops() {
+    let mut backdrop = PuppetStyle::default();
+    backdrop.background_color(PuppetColor::Hex("202020"));
+
+    let mut drawer = PuppetOverlay::drawer(DrawerSide::End, puppet! { column {} });
+    drawer.backdrop(&backdrop);
+
+    let element = drawer.to_element();
+    assert_eq!(element.get_attribute("data-side").unwrap().resolve(), "end");
+    assert_eq!(element.style(), Some(&backdrop));
+}
+
+#[test]
+fn tooltips_are_never_modal_nor_focusable() {
+    let tooltip = PuppetOverlay::tooltip(
+        "save",
+        PopoverPlacement::Top,
+        puppet! { column { row { paragraph("Saves the draft") } } },
+    );
+
+    let element = tooltip.to_element();
+    assert_eq!(tooltip.panel_id().as_deref(), Some("save-tooltip"));
+    assert_eq!(element.get_attribute("role").unwrap().resolve(), "tooltip");
+    assert_eq!(
+        element.get_attribute("data-placement").unwrap().resolve(),
+        "top"
+    );
+    assert!(element.get_attribute("aria-modal").is_none());
+    assert!(element.get_attribute("tabindex").is_none());
+    assert!(element.style().is_none());
+}
+
+#[test]
+fn popovers_are_non_modal_dialogs() {
+    let popover =
+        PuppetOverlay::popover("filters", PopoverPlacement::Bottom, puppet! { column {} });
+
+    let element = popover.to_element();
+    assert_eq!(element.get_attribute("role").unwrap().resolve(), "dialog");
+    assert_eq!(
+        element.get_attribute("id").unwrap().resolve(),
+        "filters-popover"
+    );
+    assert!(element.get_attribute("aria-modal").is_none());
+}
@@ -0,0 +1,140 @@
+use puppeteer_builders::{
+    puppet, MemoryHistory, PuppetColumn, PuppetHistory, PuppetParams, PuppetRoute, PuppetRouter,
+};
+use std::rc::Rc;
+
+fn screen(id: &'static str) -> PuppetColumn {
+    let mut column = PuppetColumn::new();
+    column.column_id(id);
+
+    column
+}
+
+fn router(history: &Rc<MemoryHistory>) -> PuppetRouter {
+    let mut settings = PuppetRoute::layout("/settings", |_, child| {
+        let mut layout = screen("settings");
+        layout.extend_rows(child.into_rows());
+        layout
+    });
+    settings
+        .add_child(PuppetRoute::new("/profile", |_| {
+            puppet! { column { row { paragraph("Profile") } } }
+        }))
+        .add_child(PuppetRoute::new("/", |_| {
+            puppet! { column { row { paragraph("Overview") } } }
+        }));
+
+    let mut router = PuppetRouter::new(Rc::clone(history));
+    router
+        .route(PuppetRoute::new("/", |_| screen("home")))
+        .route(settings)
+        .route(PuppetRoute::new("/users/:id/posts/:post", |params| {
+            let mut column = screen("post");
+            column.add_row(
+                puppet! { row { paragraph(format!("{} {}", params.param("id").unwrap(), params.param("post").unwrap())) } },
+            );
+            column
+        }))
+        .route(PuppetRoute::new("/files/*", |params| {
+            let mut column = screen("files");
+            column.add_row(puppet! { row { paragraph(params.param("*").unwrap().to_string()) } });
+            column
+        }));
+
+    router
+}
+
+#[test]
+fn routes_match_nested_layouts() {
+    let history = Rc::new(MemoryHistory::new("/"));
+    let router = router(&history);
+
+    assert_eq!(router.current(), screen("home"));
+
+    let profile = router.resolve("/settings/profile").to_html_string();
+    assert!(profile.starts_with(r#"<div id="settings">"#));
+    assert!(profile.contains("Profile"));
+    assert!(router
+        .resolve("/settings")
+        .to_html_string()
+        .contains("Overview"));
+}
+
+#[test]
+fn params_and_query_strings_are_decoded() {
+    let history = Rc::new(MemoryHistory::new("/"));
+    let router = router(&history);
+
+    let post = router
+        .resolve("/users/ada%20l/posts/7?tab=a+b")
+        .to_html_string();
+    assert!(post.contains("<p>ada l 7</p>"));
+    assert!(router
+        .resolve("/files/docs/guide.md")
+        .to_html_string()
+        .contains("<p>docs/guide.md</p>"));
+
+    let params = PuppetParams::parse("/search?tag=rust&tag=wasm&q=a%26b#top");
+    assert_eq!(params.path(), "/search");
+    assert_eq!(params.query("q"), Some("a&b"));
+    assert_eq!(
+        params.query_all("tag").collect::<Vec<&str>>(),
+        ["rust", "wasm"]
+    );
+}
+
+#[test]
+fn back_and_forward_move_through_the_history() {
+    let history = Rc::new(MemoryHistory::new("/"));
+    let router = router(&history);
+
+    router.navigate("/settings").unwrap();
+    router.navigate("/files/a").unwrap();
+    // Following a link to the current page does not add an entry
+    router.navigate("/files/a").unwrap();
+    assert_eq!(history.entries(), ["/", "/settings", "/files/a"]);
+
+    assert!(history.back());
+    assert!(router.current().to_html_string().contains("Overview"));
+    assert!(history.back());
+    assert_eq!(router.current(), screen("home"));
+    assert!(!history.back());
+
+    assert!(history.forward());
+    assert_eq!(history.location(), "/settings");
+
+    // Navigating from the middle of the history drops the entries ahead
+    router.navigate("/users/1/posts/2").unwrap();
+    assert_eq!(history.entries(), ["/", "/settings", "/users/1/posts/2"]);
+    assert!(!history.forward());
+
+    router.redirect("/").unwrap();
+    assert_eq!(history.entries(), ["/", "/settings", "/"]);
+}
+
+#[test]
+fn unknown_paths_render_the_not_found_screen() {
+    let history = Rc::new(MemoryHistory::new("/nowhere"));
+    let mut router = router(&history);
+
+    assert_eq!(router.current(), PuppetRouter::default_not_found());
+    // Layouts only match through their children, and parameters need a segment
+    assert_eq!(
+        router.resolve("/settings/unknown"),
+        PuppetRouter::default_not_found()
+    );
+    assert_eq!(
+        router.resolve("/users/1/posts"),
+        PuppetRouter::default_not_found()
+    );
+
+    router.not_found(|params| {
+        let mut column = screen("missing");
+        column.add_row(puppet! { row { paragraph(params.path().to_string()) } });
+        column
+    });
+    assert!(router
+        .current()
+        .to_html_string()
+        .contains("<p>/nowhere</p>"));
+}
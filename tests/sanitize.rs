@@ -0,0 +1,121 @@
+use puppeteer_builders::{
+    escape_html_attribute, escape_html_text, puppet, sanitize_image_url, sanitize_url, snapshot,
+    Component, ComponentType, ImageDescriptor, PuppetAttribute, PuppetElement, PuppetImage,
+    PuppetViewport,
+};
+
+#[test]
+fn text_is_never_markup() {
+    let row = puppet! {
+        row {
+            paragraph("<script>alert(1)</script>")
+            button(r#"" onclick="alert(1)"#)
+        }
+    };
+    let html = row.to_html_string();
+
+    assert!(!html.contains("<script>"));
+    assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    assert_eq!(escape_html_text("a < b & c"), "a &lt; b &amp; c");
+    assert_eq!(escape_html_attribute(r#""x""#), "&quot;x&quot;");
+}
+
+#[test]
+fn raw_html_is_explicit() {
+    let mut legal = Component::new(ComponentType::Paragraph, "ignored");
+    legal.raw_html("Read the <strong>terms</strong>");
+
+    let html = legal.to_html_string();
+    assert!(html.ends_with(">Read the <strong>terms</strong></p>"));
+    assert!(!html.contains("ignored"));
+
+    let element = legal.to_element();
+    assert!(snapshot::render_snapshot(&element).contains("Read the <strong>terms</strong>"));
+
+    // Layout lays out the text of the markup
+    let layout = element.compute_layout(&PuppetViewport::new(800.0, 600.0));
+    let lines = layout
+        .children()
+        .iter()
+        .flat_map(|child| child.lines())
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(lines, ["Read the terms"]);
+}
+
+#[test]
+fn unsafe_urls_are_blocked() {
+    for url in [
+        "javascript:alert(1)",
+        " JAVASCRIPT:alert(1)",
+        "java\tscript:alert(1)",
+        "\u{1}javascript:alert(1)",
+        "vbscript:msgbox",
+        "data:text/html,<script>alert(1)</script>",
+    ] {
+        assert_eq!(sanitize_url(url), None, "{:?}", url);
+    }
+
+    for url in [
+        "/a/b",
+        "b?c=d:e",
+        "#top",
+        "https://x.dev",
+        "mailto:a@b.c",
+        "tel:+1",
+    ] {
+        assert_eq!(sanitize_url(url).as_deref(), Some(url), "{:?}", url);
+    }
+
+    assert!(sanitize_url("data:image/png;base64,AAAA").is_none());
+    assert!(sanitize_image_url("data:image/png;base64,AAAA").is_some());
+}
+
+#[test]
+fn links_and_images_drop_unsafe_urls() {
+    let link = puppet! { link("Click") { href("javascript:alert(1)") } };
+    assert!(link.to_html_string().starts_with("<a>"));
+
+    let safe = puppet! { link("Docs") { href("/docs") } };
+    assert!(safe.to_html_string().starts_with(r#"<a href="/docs""#));
+
+    let mut image = PuppetImage::new("javascript:alert(1)", "Logo");
+    image.candidate("logo.png", ImageDescriptor::Density(1));
+    let html = image.to_html_string();
+    assert!(!html.contains("javascript"));
+    assert!(html.contains(r#"srcset="logo.png 1x""#));
+
+    let mut row = Component::new(ComponentType::Paragraph, "Quote");
+    row.attribute(PuppetAttribute::Custom(
+        "cite",
+        "javascript:alert(1)".into(),
+    ));
+    assert!(!row.to_html_string().contains("cite="));
+}
+
+#[test]
+fn url_attributes_are_checked_whatever_their_case() {
+    let mut element = PuppetElement::new("a");
+    element
+        .attribute("HREF", "javascript:alert(1)")
+        .attribute("hReF", "javascript:alert(1)")
+        .attribute("Src", "javascript:alert(1)")
+        .attribute("SRCSET", "logo.png 1x, javascript:alert(1) 2x");
+    assert!(element.attributes().is_empty());
+    assert_eq!(element.to_html_string(), "<a></a>");
+
+    element
+        .attribute("HREF", "/docs")
+        .attribute("Src", "data:image/png;base64,AAAA");
+    assert_eq!(
+        element.to_html_string(),
+        r#"<a HREF="/docs" Src="data:image/png;base64,AAAA"></a>"#
+    );
+
+    let mut link = Component::new(ComponentType::Link, "Docs");
+    link.attribute(PuppetAttribute::Custom(
+        "HREF",
+        "javascript:alert(1)".into(),
+    ));
+    assert_eq!(link.to_html_string(), "<a>Docs</a>");
+}
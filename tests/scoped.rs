@@ -0,0 +1,118 @@
+use puppeteer_builders::{
+    puppet, snapshot::render_snapshot, PuppetRow, PuppetScopedStyle, PuppetSite, PuppetStylesheet,
+    PuppetUnit, SITE_STYLESHEET,
+};
+
+fn striped() -> PuppetScopedStyle {
+    let mut striped = PuppetScopedStyle::new();
+    striped.rule("& > div:nth-child(even)", [("background-color", "#F5F5F5")]);
+
+    striped
+}
+
+#[test]
+fn selectors_are_scoped_to_the_root() {
+    let mut scoped = PuppetScopedStyle::new();
+    scoped
+        .rule("&:hover, & > p:is(.a, .b)", [("color", "red")])
+        .rule("p::after", [("content", "\"!\""), ("--accent", "blue")]);
+
+    let mut stylesheet = PuppetStylesheet::new();
+    let class_name = stylesheet.scope_class_name(&scoped);
+    let css = stylesheet.to_css();
+
+    assert_eq!(class_name, scoped.class_name());
+    assert!(css.contains(&format!(
+        ".{0}:hover, .{0} > p:is(.a, .b) {{\n  color: red;\n}}",
+        class_name
+    )));
+    assert!(css.contains(&format!(
+        ".{} p::after {{\n  content: \"!\";\n  --accent: blue;\n}}",
+        class_name
+    )));
+}
+
+#[test]
+fn rules_are_written_once_for_every_instance() {
+    let list = puppet! {
+        column {
+            row { scoped(striped()) paragraph("One") }
+            row { scoped(striped()) paragraph("Two") }
+            row { scoped(striped()) paragraph("Three") }
+        }
+    };
+
+    let snapshot = render_snapshot(&list.to_element());
+    let class_name = striped().class_name();
+    assert_eq!(
+        snapshot
+            .matches(&format!(r#"class="{}""#, class_name))
+            .count(),
+        3
+    );
+    assert_eq!(snapshot.matches(":nth-child(even) {").count(), 1);
+
+    let mut site = PuppetSite::new("Docs");
+    site.page("one", "One", list.clone())
+        .page("two", "Two", list);
+    let files = site.render();
+    let (_, css) = files
+        .iter()
+        .find(|(path, _)| path == SITE_STYLESHEET)
+        .unwrap();
+    assert_eq!(css.matches(":nth-child(even) {").count(), 1);
+}
+
+#[test]
+fn invalid_rules_are_ignored() {
+    let mut scoped = PuppetScopedStyle::new();
+    scoped
+        .rule("", [("color", "red")])
+        .rule("p", Vec::<(&str, &str)>::new())
+        .rule("p } body {", [("color", "red")])
+        .rule("p", [("color", "red; background: url(x)")])
+        .rule("p", [("color", "red</style><script>")])
+        .rule("@import url(x)", [("color", "red")])
+        .rule("p", [("color", "red /* rest")])
+        .rule("p", [("Color", "red")]);
+
+    assert!(scoped.is_empty());
+
+    let mut row = PuppetRow::new();
+    row.scoped_style(&scoped);
+    assert_eq!(row.to_html_string(), "<div></div>");
+}
+
+#[test]
+fn scope_classes_follow_the_style_class() {
+    let row = puppet! {
+        row {
+            style { padding: PuppetUnit::Pixels(4) }
+            scoped(striped())
+        }
+    };
+
+    let mut stylesheet = PuppetStylesheet::new();
+    let html = row
+        .to_element()
+        .to_html_string_with_stylesheet(&mut stylesheet);
+    let style_class = stylesheet.class_name(row.to_element().style().unwrap());
+    assert_eq!(
+        html,
+        format!(
+            r#"<div class="{} {}"></div>"#,
+            style_class,
+            striped().class_name()
+        )
+    );
+
+    // Written inline, the markup keeps the class to match the DOM
+    assert!(row.to_html_string().starts_with(&format!(
+        r#"<div class="{}" style="#,
+        striped().class_name()
+    )));
+
+    let mut other = PuppetScopedStyle::new();
+    other.rule("& > div:nth-child(odd)", [("background-color", "#F5F5F5")]);
+    assert_ne!(other.class_name(), striped().class_name());
+}
@@ -0,0 +1,121 @@
+use puppeteer_builders::{puppet, PuppetSite, PuppetUnit, SITE_STYLESHEET};
+use std::{env, fs, process};
+
+fn docs_site() -> PuppetSite {
+    let mut site = PuppetSite::new("Docs");
+    site.page(
+        "intro",
+        "Introduction",
+        puppet! {
+            column {
+                style { padding: PuppetUnit::Pixels(8) }
+                row { heading("Hello") image("Logo") { src("images/logo.png") } }
+            }
+        },
+    )
+    .page(
+        "guides/layout",
+        "Layout",
+        puppet! {
+            column {
+                style { padding: PuppetUnit::Pixels(8) }
+                row {
+                    image("Logo") { src("/images/logo.png") }
+                    image("Remote") { src("https://example.com/remote.png") }
+                }
+            }
+        },
+    );
+
+    site
+}
+
+fn file<'f>(files: &'f [(String, String)], path: &str) -> &'f str {
+    files
+        .iter()
+        .find(|(name, _)| name == path)
+        .map(|(_, contents)| contents.as_str())
+        .unwrap_or_else(|| panic!("{} was not rendered", path))
+}
+
+#[test]
+fn pages_share_one_deduplicated_stylesheet() {
+    let files = docs_site().render();
+
+    let paths = files
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        paths,
+        [
+            "intro.html",
+            "guides/layout.html",
+            "index.html",
+            SITE_STYLESHEET
+        ]
+    );
+
+    // Unstyled elements get no rule and both columns share the same one
+    let stylesheet = file(&files, SITE_STYLESHEET);
+    assert_eq!(stylesheet.matches(".puppet-").count(), 1);
+
+    let intro = file(&files, "intro.html");
+    assert!(intro.contains("<title>Introduction - Docs</title>"));
+    assert!(intro.contains(r#"<link rel="stylesheet" href="styles.css">"#));
+    assert!(!intro.contains("style="));
+}
+
+#[test]
+fn nested_pages_reach_the_root_of_the_site() {
+    let files = docs_site().render();
+    let layout = file(&files, "guides/layout.html");
+
+    assert!(layout.contains(r#"href="../styles.css""#));
+    assert!(layout.contains(r#"src="../images/logo.png""#));
+    assert!(layout.contains(r#"src="https://example.com/remote.png""#));
+}
+
+#[test]
+fn index_links_every_page() {
+    let files = docs_site().render();
+    let index = file(&files, "index.html");
+
+    assert!(index.contains(r#"<a href="intro.html">Introduction</a>"#));
+    assert!(index.contains(r#"<a href="guides/layout.html">Layout</a>"#));
+}
+
+#[test]
+fn export_copies_local_images_once() {
+    let directory = env::temp_dir().join(format!("puppeteer-site-{}", process::id()));
+    let assets = directory.join("assets");
+    let out = directory.join("out");
+    fs::create_dir_all(assets.join("images")).unwrap();
+    fs::write(assets.join("images/logo.png"), b"png").unwrap();
+
+    let mut site = docs_site();
+    assert_eq!(site.assets(), ["images/logo.png"]);
+
+    site.asset_root(&assets).export(&out).unwrap();
+
+    assert_eq!(fs::read(out.join("images/logo.png")).unwrap(), b"png");
+    assert!(out.join("guides/layout.html").is_file());
+    assert!(out.join(SITE_STYLESHEET).is_file());
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn missing_images_fail_the_export() {
+    let directory = env::temp_dir().join(format!("puppeteer-missing-{}", process::id()));
+
+    let mut site = docs_site();
+    let error = site
+        .asset_root(directory.join("nowhere"))
+        .export(directory.join("out"))
+        .unwrap_err();
+
+    assert!(error.to_string().contains("logo.png"));
+
+    let _ = fs::remove_dir_all(&directory);
+}
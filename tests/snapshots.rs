@@ -0,0 +1,86 @@
+use puppeteer_builders::{
+    puppet,
+    snapshot::{
+        assert_snapshot, assert_text_snapshot, diff_lines, render_snapshot, snapshot_path,
+        UPDATE_SNAPSHOTS_VAR,
+    },
+    PuppetColor, PuppetLength, PuppetUnit,
+};
+
+#[test]
+fn demo_column() {
+    let column = puppet! {
+        column {
+            id("foo")
+            style {
+                width: PuppetLength::Half,
+                height: PuppetLength::ViewPortHeight,
+                min_width: PuppetUnit::Pixels(50),
+                min_height: PuppetUnit::Pixels(50),
+                background_color: PuppetColor::Rgb(0, 0, 0),
+            }
+            row {
+                heading("Puppeteer")
+                paragraph("Opinionated   layouts\n  for the web")
+                link("Docs") { href("/docs") }
+                image("A puppet")
+                button("Click me") { on click => |_event| () }
+            }
+        }
+    };
+
+    assert_snapshot("demo_column", &column.to_element());
+}
+
+#[test]
+fn text_is_escaped() {
+    let row = puppet! { row { paragraph("<b>bold</b> & \"quoted\"") } };
+
+    assert_eq!(
+        row.to_html_string(),
+        "<div><p>&lt;b&gt;bold&lt;/b&gt; &amp; \"quoted\"</p></div>"
+    );
+}
+
+#[test]
+fn identical_styles_share_a_rule() {
+    let snapshot = render_snapshot(
+        &puppet! {
+            row {
+                paragraph("a") { style { padding: PuppetUnit::Pixels(4) } }
+                paragraph("b") { style { padding: PuppetUnit::Pixels(4) } }
+            }
+        }
+        .to_element(),
+    );
+
+    assert_eq!(snapshot.matches(" {\n").count(), 1);
+}
+
+#[test]
+fn diff_shows_changed_lines_with_context() {
+    let expected = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+    let actual = "1\n2\n3\n4\n5\nsix\n7\n8\n9\n";
+
+    assert_eq!(
+        diff_lines(expected, actual),
+        "...\n  3\n  4\n  5\n- 6\n+ six\n  7\n  8\n  9\n"
+    );
+}
+
+#[test]
+fn missing_snapshots_fail() {
+    // Updating writes the missing snapshot instead
+    if std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() {
+        return;
+    }
+
+    let missing = std::panic::catch_unwind(|| assert_text_snapshot("never_committed", "text"));
+
+    let message = missing.unwrap_err();
+    assert!(message
+        .downcast_ref::<String>()
+        .unwrap()
+        .starts_with("snapshot `never_committed` could not be read"));
+    assert!(!snapshot_path("never_committed").exists());
+}
@@ -0,0 +1,69 @@
+use puppeteer_builders::{
+    puppet, PuppetColor, PuppetLength, PuppetRect, PuppetStyle, PuppetUnit, PuppetViewport,
+};
+
+#[test]
+fn unset_properties_are_not_written() {
+    let column = puppet! {
+        column {
+            style { background_color: PuppetColor::Hex("000080") }
+            row { paragraph("Inherits the background") }
+        }
+    };
+
+    assert_eq!(
+        column.to_html_string(),
+        r#"<div style="background-color: #000080;"><div><p>Inherits the background</p></div></div>"#
+    );
+}
+
+#[test]
+fn zero_is_written_as_zero() {
+    let mut style = PuppetStyle::default();
+    style
+        .margin(PuppetUnit::Pixels(0))
+        .max_width(PuppetUnit::Pixels(0))
+        .max_height(PuppetUnit::Percentage(0));
+
+    assert_eq!(style.to_css(), "margin: 0; max-width: 0; max-height: 0;");
+}
+
+#[test]
+fn reset_restores_the_former_defaults_with_a_zero_margin() {
+    let row = puppet! { row { style { ..PuppetStyle::reset(), width: PuppetLength::Half } } };
+    let html = row.to_html_string();
+
+    // The former zero margin was written `auto`
+    assert!(html.contains("margin: 0;"));
+    assert!(!html.contains("margin: auto"));
+    assert!(html.contains("padding: 2%;"));
+    assert!(html.contains("width: 50%;"));
+    assert!(html.contains("background-color: #FFFFFF;"));
+    assert!(!html.contains("min-width"));
+
+    assert!(PuppetStyle::default().is_empty());
+    assert!(!PuppetStyle::reset().is_empty());
+}
+
+#[test]
+fn unset_sizes_are_laid_out_as_blocks() {
+    let column = puppet! {
+        column {
+            row { id("first") paragraph("One") }
+            row { id("second") style { ..PuppetStyle::reset() } paragraph("Two") }
+        }
+    };
+
+    let root = column.compute_layout(&PuppetViewport::new(400.0, 300.0));
+
+    // Rows stack and stretch to the width of the column, while the reset one is 100%
+    // wide and padded by 2% on top of it, as with content-box sizing
+    assert_eq!(
+        root.find("first").unwrap().rect(),
+        PuppetRect::new(0.0, 0.0, 400.0, 16.0)
+    );
+    assert_eq!(
+        root.find("second").unwrap().rect(),
+        PuppetRect::new(0.0, 16.0, 416.0, 32.0)
+    );
+}
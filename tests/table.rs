@@ -0,0 +1,131 @@
+use puppeteer_builders::{
+    CellAlignment, PuppetLength, PuppetTable, PuppetTableColumn, SortDirection,
+};
+
+#[derive(Debug)]
+struct Service {
+    name: &'static str,
+    instances: u32,
+}
+
+fn services() -> PuppetTable<Service> {
+    let mut name = PuppetTableColumn::new("Name", |service: &Service| service.name);
+    name.width(PuppetLength::Half)
+        .sortable_by_key(|service| service.name);
+
+    let mut instances = PuppetTableColumn::new("Instances", |service: &Service| {
+        service.instances.to_string()
+    });
+    instances
+        .alignment(CellAlignment::End)
+        .sortable_by_key(|service| service.instances);
+
+    let mut table = PuppetTable::new();
+    table.add_column(name).add_column(instances).extend_rows([
+        Service {
+            name: "gateway",
+            instances: 4,
+        },
+        Service {
+            name: "auth",
+            instances: 2,
+        },
+        Service {
+            name: "billing",
+            instances: 1,
+        },
+        Service {
+            name: "search",
+            instances: 8,
+        },
+        Service {
+            name: "mailer",
+            instances: 3,
+        },
+    ]);
+
+    table
+}
+
+fn visible_names(table: &PuppetTable<Service>) -> Vec<&'static str> {
+    table
+        .visible_rows()
+        .map(|(_, service)| service.name)
+        .collect()
+}
+
+#[test]
+fn headers_toggle_the_sort_direction() {
+    let mut table = services();
+
+    table.toggle_sort(1);
+    assert_eq!(table.sort(), Some((1, SortDirection::Ascending)));
+    assert_eq!(
+        visible_names(&table),
+        ["billing", "auth", "mailer", "gateway", "search"]
+    );
+
+    table.toggle_sort(1);
+    assert_eq!(table.sort(), Some((1, SortDirection::Descending)));
+    assert_eq!(visible_names(&table)[0], "search");
+
+    let html = table.to_html_string();
+    assert!(html.contains(r#"<th scope="col" style="width: auto; text-align: end;" aria-sort="descending"><button type="button" data-sort="1">Instances</button></th>"#));
+    assert!(html.contains(r#"aria-sort="none"><button type="button" data-sort="0">Name</button>"#));
+}
+
+#[test]
+fn pages_follow_the_sort_order() {
+    let mut table = services();
+    table
+        .page_size(Some(2))
+        .sort_by(0, SortDirection::Ascending);
+
+    assert_eq!(table.page_count(), 3);
+    assert_eq!(visible_names(&table), ["auth", "billing"]);
+
+    table.page(2);
+    assert_eq!(visible_names(&table), ["search"]);
+
+    // Past the last page
+    table.page(10);
+    assert_eq!(table.current_page(), 2);
+
+    let html = table.to_html_string();
+    assert!(html.contains(r#"<nav aria-label="Pagination">"#));
+    assert!(html.contains(r#"<button type="button" data-page="2" disabled="">Next</button>"#));
+    assert!(html.contains("3 / 3"));
+}
+
+#[test]
+fn selection_survives_sorting_and_paging() {
+    let mut table = services();
+    table.selectable(true).page_size(Some(2));
+
+    table.select(3, true).toggle_sort(1).toggle_sort(1);
+    assert_eq!(visible_names(&table), ["search", "gateway"]);
+    assert!(table.is_selected(3));
+
+    table.toggle_page_selection();
+    let selected = table
+        .selected()
+        .map(|(_, service)| service.name)
+        .collect::<Vec<&str>>();
+    assert_eq!(selected, ["gateway", "search"]);
+
+    let html = table.to_html_string();
+    assert!(html.contains(r#"<table aria-multiselectable="true">"#));
+    assert!(html.contains(r#"<tr aria-selected="true"><td><input type="checkbox" aria-label="Select row" data-select="3" checked="">"#));
+    assert!(html.contains(r#"aria-label="Select all rows" data-select="page" checked="">"#));
+}
+
+#[test]
+fn sticky_headers_and_captions() {
+    let mut table = services();
+    table.caption("Services").sticky_header(true);
+
+    let html = table.to_html_string();
+    assert!(html.starts_with("<div><table><caption>Services</caption><thead>"));
+    assert!(html.contains("text-align: start; position: sticky; top: 0;"));
+    assert!(!html.contains("<nav"));
+}
@@ -0,0 +1,119 @@
+use puppeteer_builders::{
+    puppet, snapshot::assert_text_snapshot, AlignContent, AlignItems, AlignSelf, FlexDirection,
+    FlexGap, FlexWrap, JustifyContent, PuppetAlignment, PuppetColor, PuppetColorDepth,
+    PuppetDirection, PuppetLength, PuppetTerminal, PuppetUnit, Transparency,
+};
+
+fn start_row() -> PuppetAlignment {
+    PuppetAlignment::Flex {
+        flex_direction: FlexDirection::Row,
+        flex_wrap: FlexWrap::NoWrap,
+        justify_content: JustifyContent::FlexStart,
+        align_self: AlignSelf::Auto,
+        align_items: AlignItems::FlexStart,
+        align_content: AlignContent::FlexStart,
+        gap: FlexGap::Column(PuppetUnit::Pixels(8)),
+    }
+}
+
+fn admin_screen() -> puppeteer_builders::PuppetColumn {
+    puppet! {
+        column {
+            style { padding: PuppetUnit::Pixels(0) }
+            row {
+                style { padding: PuppetUnit::Pixels(0) }
+                heading("Users") { style { background_color: PuppetColor::Hex("000080") } }
+            }
+            row {
+                style { padding: PuppetUnit::Pixels(0), align_items: start_row() }
+                button("Add") { style { width: PuppetLength::Normal, padding: PuppetUnit::Pixels(0) } }
+                button("Remove") { style { width: PuppetLength::Normal, padding: PuppetUnit::Pixels(0) } }
+            }
+            row {
+                style { padding: PuppetUnit::Pixels(0), width: PuppetLength::Half }
+                paragraph("Half of the screen wide")
+            }
+        }
+    }
+}
+
+#[test]
+fn admin_screen_as_text() {
+    let mut terminal = PuppetTerminal::new(30, 5);
+    terminal.draw(&admin_screen().to_element());
+
+    assert_text_snapshot("terminal_admin_screen", &terminal.to_text());
+}
+
+#[test]
+fn rows_are_mirrored_in_rtl() {
+    let mut terminal = PuppetTerminal::new(30, 5);
+    terminal
+        .direction(PuppetDirection::Rtl)
+        .draw(&admin_screen().to_element());
+
+    let text = terminal.to_text();
+    let buttons = text.lines().nth(1).unwrap();
+
+    assert_eq!(buttons, format!("{:>30}", "Remove Add"));
+}
+
+#[test]
+fn colors_are_written_as_ansi_escapes() {
+    let mut terminal = PuppetTerminal::new(30, 5);
+    terminal.draw(&admin_screen().to_element());
+
+    let ansi = terminal.to_ansi();
+    // White bold text on the navy heading row
+    assert!(ansi.contains("\x1b[0m\x1b[1m\x1b[38;2;255;255;255m\x1b[48;2;0;0;128mUsers"));
+
+    terminal.color_depth(PuppetColorDepth::Palette256);
+    assert!(terminal.to_ansi().contains("\x1b[48;5;19m"));
+
+    terminal.color_depth(PuppetColorDepth::Monochrome);
+    assert!(!terminal.to_ansi().contains(";2;"));
+}
+
+#[test]
+fn translucent_backgrounds_are_blended() {
+    let column = puppet! {
+        column {
+            style { padding: PuppetUnit::Pixels(0) }
+            row {
+                style { padding: PuppetUnit::Pixels(0), background_color: PuppetColor::Rgb(0, 0, 0) }
+                paragraph("Grey") {
+                    style {
+                        padding: PuppetUnit::Pixels(0),
+                        background_color: PuppetColor::Rgba(255, 255, 255, Transparency::Fifty),
+                    }
+                }
+            }
+        }
+    };
+
+    let mut terminal = PuppetTerminal::new(10, 2);
+    terminal.draw(&column.to_element());
+
+    assert!(terminal.to_ansi().contains("\x1b[48;2;128;128;128mGrey"));
+}
+
+#[test]
+fn control_characters_are_not_written() {
+    let row = puppet! {
+        row {
+            style { padding: PuppetUnit::Pixels(0) }
+            paragraph("a\u{1b}[2Jb\u{9b}31mc\u{7}") { style { padding: PuppetUnit::Pixels(0) } }
+        }
+    };
+
+    let mut terminal = PuppetTerminal::new(20, 1);
+    terminal.draw(&row.to_element());
+
+    let ansi = terminal.to_ansi();
+    assert!(!ansi.contains("\x1b[2J"));
+    assert!(!ansi.contains(['\u{7}', '\u{9b}']));
+    assert_eq!(
+        terminal.to_text().trim_end(),
+        "a\u{FFFD}[2Jb\u{FFFD}31mc\u{FFFD}"
+    );
+}
@@ -0,0 +1,71 @@
+use puppeteer_builders::{
+    clear_toasts, dismiss_toast, notify, pending_toasts, PuppetToast, PuppetToastQueue,
+    ToastSeverity, TOAST_LIMIT, TOAST_TIMEOUT,
+};
+
+#[test]
+fn queues_stack_up_to_their_limit() {
+    let mut queue = PuppetToastQueue::new();
+    let ids = (0..5)
+        .map(|index| queue.push(PuppetToast::new(format!("Toast {}", index))))
+        .collect::<Vec<_>>();
+
+    assert_eq!(queue.len(), 5);
+    assert_eq!(queue.visible().count(), TOAST_LIMIT);
+    assert_eq!(queue.waiting(), 2);
+
+    queue.dismiss(ids[1]);
+    let visible = queue.visible().map(|(id, _)| id).collect::<Vec<_>>();
+    assert_eq!(visible, [ids[0], ids[2], ids[3]]);
+    assert!(!queue.contains(ids[1]));
+
+    queue.limit(0);
+    assert_eq!(queue.visible().count(), 1);
+    assert!(queue.dismiss(ids[1]).is_none());
+}
+
+#[test]
+fn severities_decide_the_role_and_color() {
+    let mut queue = PuppetToastQueue::new();
+    let mut error = PuppetToast::error("Could not save");
+    error.timeout(None);
+    let id = queue.push(error.clone());
+
+    assert_eq!(error.get_severity(), ToastSeverity::Error);
+    assert_eq!(error.get_timeout(), None);
+    assert_eq!(
+        PuppetToast::info("Saved").get_timeout(),
+        Some(TOAST_TIMEOUT)
+    );
+
+    let html = error.to_element(id).to_html_string();
+    assert!(html.starts_with(&format!(
+        r#"<div id="{}" data-toast="{}" data-severity="error" role="alert" aria-atomic="true""#,
+        id.to_html_id(),
+        id
+    )));
+    assert!(html.contains("background-color: #FCE8E6;"));
+    assert!(html.contains(r#"<p>Could not save</p>"#));
+    assert!(html.contains(r#"aria-label="Dismiss" data-toast-dismiss="""#));
+}
+
+#[test]
+fn status_toasts_are_polite() {
+    assert_eq!(ToastSeverity::Info.to_html_role(), "status");
+    assert_eq!(ToastSeverity::Success.to_html_role(), "status");
+    assert_eq!(ToastSeverity::Warning.to_html_role(), "alert");
+    assert_eq!(ToastSeverity::Warning.to_string(), "Warning");
+}
+
+#[test]
+fn notify_queues_until_the_toasts_are_mounted() {
+    let saved = notify(PuppetToast::success("Saved"));
+    let synced = notify(PuppetToast::info("Synced"));
+    assert_eq!(pending_toasts(), [saved, synced]);
+
+    dismiss_toast(saved);
+    assert_eq!(pending_toasts(), [synced]);
+
+    clear_toasts();
+    assert!(pending_toasts().is_empty());
+}
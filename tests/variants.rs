@@ -0,0 +1,108 @@
+use puppeteer_builders::{
+    puppet, puppet_style, snapshot, Component, ComponentType, PuppetColor, PuppetElement,
+    PuppetLength, PuppetStyle, PuppetStylePatch, PuppetStyleVariants, PuppetStylesheet, PuppetUnit,
+    PuppetVariant, StyleState,
+};
+
+#[test]
+fn patches_only_override_what_they_set() {
+    let base = puppet_style! { width: PuppetLength::Half, padding: PuppetUnit::Pixels(8) };
+
+    let mut patch = PuppetStylePatch::new();
+    patch.padding(PuppetUnit::Pixels(16));
+    let extended = base.extend(&patch);
+
+    assert_eq!(extended.css_properties().len(), base.css_properties().len());
+    assert!(extended.to_css().contains("padding: 16px;"));
+    assert!(extended.to_css().contains("width: 50%;"));
+
+    // Diffing gives back the patch, and an empty one for equal styles
+    assert_eq!(base.diff(&extended), patch);
+    assert!(base.diff(&base).is_empty());
+    assert_eq!(patch.css_properties(), [("padding", "16px".to_string())]);
+}
+
+#[test]
+fn later_patches_win() {
+    let mut first = PuppetStylePatch::new();
+    first
+        .padding(PuppetUnit::Pixels(1))
+        .background_color(PuppetColor::Hex("111111"));
+    let mut second = PuppetStylePatch::new();
+    second.background_color(PuppetColor::Hex("222222"));
+
+    let merged = first.merge(&second);
+    let style = PuppetStyle::default().extend(&merged);
+    assert!(style.to_css().contains("padding: 1px;"));
+    assert!(style.to_css().contains("background-color: #222222;"));
+    assert_eq!(PuppetStyle::default().extend(&first).extend(&second), style);
+}
+
+#[test]
+fn variants_and_states_have_a_fixed_precedence() {
+    let variants = PuppetStyleVariants::buttons();
+
+    let mut danger = Component::new(ComponentType::Button, "Delete");
+    danger
+        .style_variants(&variants)
+        .variant(PuppetVariant::Danger);
+    assert!(danger
+        .resolved_style()
+        .to_css()
+        .contains("background-color: #FCE8E6;"));
+
+    // States are sorted by precedence, the variant patch merged over the shared one
+    let states = variants.resolve_states(Some(PuppetVariant::Danger));
+    let order = states.iter().map(|(state, _)| *state).collect::<Vec<_>>();
+    assert_eq!(
+        order,
+        [StyleState::Hover, StyleState::Active, StyleState::Disabled]
+    );
+
+    let mut hover = PuppetStylePatch::new();
+    hover.padding(PuppetUnit::Pixels(4));
+    let mut shared = variants.clone();
+    shared.state(StyleState::Hover, hover);
+    let (_, hovered) = shared.resolve_states(Some(PuppetVariant::Danger))[0];
+    let hovered = PuppetStyle::default().extend(&hovered).to_css();
+    assert!(hovered.contains("padding: 4px;"));
+    assert!(hovered.contains("background-color: #FAD2CF;"));
+
+    // Without a variant only the shared states apply
+    assert_eq!(variants.resolve_states(None).len(), 1);
+}
+
+#[test]
+fn states_are_written_as_pseudo_class_rules() {
+    let variants = PuppetStyleVariants::buttons();
+    let row = puppet! {
+        row {
+            button("Save") { variants(variants) variant(PuppetVariant::Primary) }
+            button("Save again") { variants(variants) variant(PuppetVariant::Primary) }
+        }
+    };
+
+    let mut stylesheet = PuppetStylesheet::new();
+    let html = row
+        .to_element()
+        .to_html_string_with_stylesheet(&mut stylesheet);
+    let css = stylesheet.to_css();
+
+    assert_eq!(css.matches(":hover {").count(), 1);
+    assert!(css.contains("  background-color: #AECBFA !important;"));
+    let disabled = css.find(":disabled").unwrap();
+    assert!(css.find(":hover").unwrap() < disabled);
+    assert!(css.find(":active").unwrap() < disabled);
+    assert!(css.contains(r#"[aria-disabled="true"] {"#));
+
+    // The inline markup carries the same classes as the stylesheet markup
+    let state_class = html.split("puppet-s").nth(1).unwrap()[..8].to_string();
+    assert!(row
+        .to_html_string()
+        .contains(&format!("puppet-s{}", state_class)));
+
+    let mut element = PuppetElement::new("button");
+    element.state_style(StyleState::Hover, PuppetStylePatch::new());
+    assert!(element.states().is_empty());
+    assert!(snapshot::render_snapshot(&row.to_element()).contains(":hover {"));
+}
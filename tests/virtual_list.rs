@@ -0,0 +1,122 @@
+use puppeteer_builders::{puppet, PuppetVirtualList, RowHeight};
+
+fn list(row_count: usize) -> PuppetVirtualList {
+    let mut list = PuppetVirtualList::new(row_count, |index| {
+        puppet! { row { paragraph(format!("Row {}", index)) } }
+    });
+    list.row_height(RowHeight::Fixed(20.0))
+        .viewport_height(100.0)
+        .overscan(2);
+
+    list
+}
+
+#[test]
+fn only_visible_rows_are_rendered() {
+    let mut list = list(10_000);
+    assert_eq!(list.visible_range(), 0..7);
+
+    list.scroll_to(4_000.0);
+    assert_eq!(list.visible_range(), 198..207);
+
+    let element = list.to_element();
+    let html = element.to_html_string();
+    assert_eq!(html.matches("data-index=").count(), 9);
+    assert!(html.contains(r#"<div style="position: relative; height: 200000px;">"#));
+    assert!(html.contains("transform: translateY(3960px);"));
+    assert!(html.contains(r#"data-index="198""#));
+    assert!(!html.contains(r#"data-index="207""#));
+}
+
+#[test]
+fn scrolling_is_clamped_to_the_rows() {
+    let mut list = list(10);
+
+    list.scroll_to(1_000.0);
+    assert_eq!(list.scroll_top(), 100.0);
+    assert_eq!(list.visible_range(), 3..10);
+
+    list.scroll_to(-5.0);
+    assert_eq!(list.scroll_top(), 0.0);
+}
+
+#[test]
+fn measured_rows_move_the_rows_below() {
+    let mut list = list(100);
+    list.row_height(RowHeight::Estimated(20.0));
+
+    list.measure_row(0, 60.0).measure_row(1, 50.0);
+    assert_eq!(list.offset_of(2), 110.0);
+    assert_eq!(list.total_height(), 110.0 + 98.0 * 20.0);
+
+    // Rows 0 and 1 fill the viewport
+    assert_eq!(list.visible_range(), 0..4);
+
+    list.scroll_to(115.0);
+    // Rows 2 to 7 are visible, plus 2 rows of overscan
+    assert_eq!(list.visible_range(), 0..10);
+}
+
+#[test]
+fn fixed_rows_ignore_measures() {
+    let mut list = list(100);
+
+    list.measure_row(0, 60.0);
+    assert_eq!(list.height_of(0), 20.0);
+}
+
+#[test]
+fn rows_inserted_above_keep_the_viewport_in_place() {
+    let mut list = list(100);
+    list.scroll_to(500.0);
+    let first_visible = list.visible_range().start + 2;
+    assert_eq!(first_visible, 25);
+
+    list.insert_rows(10, 5);
+    assert_eq!(list.row_count(), 105);
+    assert_eq!(list.scroll_top(), 600.0);
+    assert_eq!(list.visible_range().start + 2, 30);
+
+    // Rows inserted below the viewport do not scroll
+    list.insert_rows(90, 5);
+    assert_eq!(list.scroll_top(), 600.0);
+
+    list.remove_rows(0..10);
+    assert_eq!(list.scroll_top(), 400.0);
+}
+
+#[test]
+fn rows_inserted_at_the_top_show_up_when_not_scrolled() {
+    let mut list = list(100);
+
+    list.insert_rows(0, 3);
+    assert_eq!(list.scroll_top(), 0.0);
+    assert_eq!(list.visible_range(), 0..7);
+}
+
+#[test]
+fn existing_rows_are_inserted_with_the_list() {
+    let rows = ["a", "b"]
+        .map(|name| puppet! { row { paragraph(name) } })
+        .to_vec();
+    let mut list = PuppetVirtualList::from_rows(rows);
+
+    // A list of existing rows has no renderer to build a counted insert
+    list.insert_rows(0, 1);
+    assert_eq!(list.row_count(), 2);
+    assert!(list.row(2).is_none());
+
+    list.insert_existing_rows(0, vec![puppet! { row { paragraph("new") } }]);
+    assert_eq!(list.row_count(), 3);
+    let html = list.to_html_string();
+    assert_eq!(html.matches("data-index=").count(), 3);
+    assert!(html.find("new").unwrap() < html.find("<p>a</p>").unwrap());
+
+    list.remove_rows(1..2);
+    assert!(!list.to_html_string().contains("<p>a</p>"));
+
+    list.set_render_row(|index| puppet! { row { paragraph(format!("Row {}", index)) } });
+    list.insert_rows(0, 1);
+    assert_eq!(list.row_count(), 3);
+    assert!(list.to_html_string().contains("Row 2"));
+}